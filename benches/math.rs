@@ -0,0 +1,60 @@
+// Compares the scalar and SIMD math kernels. Record the scalar numbers first, then run
+// with the `simd` feature against them:
+//
+//     cargo bench --bench math -- --save-baseline scalar
+//     cargo bench --bench math --features simd -- --baseline scalar
+
+use std::f32::consts::FRAC_PI_3;
+use std::hint::black_box;
+
+use chaos_engine::math::{Vec3, Vec4, matrix::Mat4, quaternion::Quaternion};
+use criterion::{Criterion, criterion_group, criterion_main};
+
+fn vectors(c: &mut Criterion) {
+    let a = Vec4::new(1.5, -2.0, 0.25, 8.0);
+    let b = Vec4::new(-3.0, 0.5, 4.0, 2.0);
+    c.bench_function("vec4 add", |bench| {
+        bench.iter(|| black_box(a) + black_box(b))
+    });
+    c.bench_function("vec4 mul", |bench| {
+        bench.iter(|| black_box(a) * black_box(b))
+    });
+    c.bench_function("vec4 scale", |bench| {
+        bench.iter(|| black_box(a) * black_box(3.0))
+    });
+    c.bench_function("vec4 dot", |bench| {
+        bench.iter(|| Vec4::dot(black_box(&a), black_box(&b)))
+    });
+
+    let a = Vec3::new(1.5, -2.0, 0.25);
+    let b = Vec3::new(-3.0, 0.5, 4.0);
+    c.bench_function("vec3 dot", |bench| {
+        bench.iter(|| Vec3::dot(black_box(&a), black_box(&b)))
+    });
+    c.bench_function("vec3 cross", |bench| {
+        bench.iter(|| Vec3::cross(black_box(&a), black_box(&b)))
+    });
+}
+
+fn matrices(c: &mut Criterion) {
+    let view = Mat4::look_at(&Vec3::new(1.0, 2.0, 5.0), &Vec3::zero(), &Vec3::y_axis());
+    let projection = Mat4::perspective_projection(FRAC_PI_3, 16.0 / 9.0, 0.1, 100.0);
+    c.bench_function("mat4 mul", |bench| {
+        bench.iter(|| black_box(view) * black_box(projection))
+    });
+    let view_projection = view * projection;
+    c.bench_function("mat4 inverse", |bench| {
+        bench.iter(|| black_box(&view_projection).inverse())
+    });
+}
+
+fn quaternions(c: &mut Criterion) {
+    let a = Quaternion::from_axis_angle(&Vec3::y_axis(), FRAC_PI_3);
+    let b = Quaternion::from_axis_angle(&Vec3::normalized(&Vec3::new(1.0, 1.0, 0.0)), 0.5);
+    c.bench_function("quaternion mul", |bench| {
+        bench.iter(|| black_box(a) * black_box(b))
+    });
+}
+
+criterion_group!(benches, vectors, matrices, quaternions);
+criterion_main!(benches);
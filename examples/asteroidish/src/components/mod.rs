@@ -1,4 +1,3 @@
 pub mod camera;
 pub mod shape;
-pub mod transform;
 pub mod velocity;
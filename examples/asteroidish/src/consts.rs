@@ -8,3 +8,9 @@ pub enum SpecializedEntities {
     Camera,
     Ship,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameState {
+    Playing,
+    GameOver,
+}
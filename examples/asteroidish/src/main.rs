@@ -5,17 +5,21 @@ mod systems;
 
 use chaos_engine::device::bindings::{ChaosBindingEvent, ChaosButton, ChaosDeviceEventMatcher};
 use chaos_engine::device::events::ChaosKeyCode;
+use chaos_engine::ecs::state::{ChaosStateMachine, InState};
 use chaos_engine::engine::ChaosEngine;
 use chaos_engine::log;
 use chaos_engine::logger::ChaosLogger;
+use chaos_engine::physics::system::PhysicsSystem;
+use chaos_engine::spatial::system::SpatialIndexSystem;
+use chaos_engine::transform::system::TransformSystem;
 use std::path::PathBuf;
 
-use crate::consts::DeviceEvent;
+use crate::consts::{DeviceEvent, GameState};
 use crate::systems::asteroid::AsteroidSystem;
 use crate::systems::camera::CameraSystem;
 use crate::systems::impact::ImpactSystem;
 use crate::systems::ship::ShipSystem;
-use crate::systems::transform::TransformSystem;
+use crate::systems::velocity::VelocitySystem;
 
 use crate::systems::ship::ShipEvent;
 
@@ -77,12 +81,22 @@ fn main() {
         .device_event_system()
         .bind(fire_event, ShipEvent::Fire);
 
+    engine.world_mut().add_state(GameState::Playing);
     engine
         .world_mut()
+        .get_resource_mut::<ChaosStateMachine<GameState>>()
+        .unwrap()
+        .on_enter(GameState::GameOver, |_| log::info!("Game over"));
+
+    engine
+        .world_mut()
+        .add_system(PhysicsSystem::new().with_cell_size(16.0))
+        .add_system(VelocitySystem::new())
         .add_system(TransformSystem::new())
-        .add_system(ShipSystem::new())
-        .add_system(AsteroidSystem::new())
-        .add_system(ImpactSystem::new())
+        .add_system(SpatialIndexSystem::new(8.0))
+        .add_system(InState::new(GameState::Playing, ShipSystem::new()))
+        .add_system(InState::new(GameState::Playing, AsteroidSystem::new()))
+        .add_system(InState::new(GameState::Playing, ImpactSystem::new()))
         .add_system(CameraSystem::new(width, height));
 
     engine.device_event_system().bind(
@@ -15,8 +15,8 @@ use vulkano::pipeline::graphics::input_assembly::PrimitiveTopology;
 
 use crate::components::camera::CameraComponent;
 use crate::components::shape::ShapeComponent;
-use crate::components::transform::TransformComponent;
 use crate::consts::SpecializedEntities;
+use crate::renderables::model_matrix;
 
 #[derive(BufferContents, Vertex)]
 #[repr(C)]
@@ -103,10 +103,7 @@ impl ChaosRenderableTrait for AsteroidRenderable {
             .set_data(points.clone())
             .map_err(|_| "Failed to set asteroid vertex buffer data")?;
 
-        self.push_constants.model = world
-            .get_component::<TransformComponent>(entity_id)
-            .map(|tc| tc.as_mat4())
-            .unwrap_or(Mat4::identity());
+        self.push_constants.model = model_matrix(world, entity_id);
 
         self.effect = Some(effect.unwrap());
         self.buffer = Some(buffer);
@@ -168,17 +165,11 @@ impl ChaosRenderableTrait for AsteroidRenderable {
 
         let effect = self.effect.as_ref().unwrap();
 
-        let transform_component = world.get_component::<TransformComponent>(entity_id);
-
         effect.bind_descriptor_sets(command_buffer)?;
         effect.bind_push_constants(
             command_buffer,
             0,
-            PushConstants::from_model(
-                transform_component
-                    .map(|tc| tc.as_mat4())
-                    .unwrap_or(Mat4::identity()),
-            ),
+            PushConstants::from_model(model_matrix(world, entity_id)),
         )?;
         command_buffer
             .bind_pipeline_graphics(effect.pipeline())?
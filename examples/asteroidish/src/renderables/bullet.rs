@@ -14,8 +14,8 @@ use vulkano::command_buffer::{AutoCommandBufferBuilder, PrimaryAutoCommandBuffer
 
 use crate::components::camera::CameraComponent;
 use crate::components::shape::ShapeComponent;
-use crate::components::transform::TransformComponent;
 use crate::consts::SpecializedEntities;
+use crate::renderables::model_matrix;
 
 #[derive(BufferContents, Vertex)]
 #[repr(C)]
@@ -100,13 +100,9 @@ impl ChaosRenderableTrait for BulletRenderable {
             .set_data(points)
             .map_err(|_| "Failed to set triangle vertex buffer data")?;
 
-        let transform_component = world
-            .get_component::<TransformComponent>(entity_id)
-            .unwrap();
-
         self.effect = Some(effect.unwrap());
         self.buffer = Some(buffer);
-        self.push_constants = BulletPushConstants::from_model(transform_component.as_mat4());
+        self.push_constants = BulletPushConstants::from_model(model_matrix(world, entity_id));
         Ok(())
     }
 
@@ -141,10 +137,7 @@ impl ChaosRenderableTrait for BulletRenderable {
             )
             .map_err(|_| "Failed to set uniform data")?;
 
-        let transform_component = world
-            .get_component::<TransformComponent>(entity_id)
-            .unwrap();
-        self.push_constants.model = transform_component.as_mat4();
+        self.push_constants.model = model_matrix(world, entity_id);
 
         Ok(())
     }
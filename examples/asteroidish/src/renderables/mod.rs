@@ -1,3 +1,21 @@
+use chaos_engine::ecs::EntityID;
+use chaos_engine::ecs::world::ChaosWorld;
+use chaos_engine::math::matrix::Mat4;
+use chaos_engine::transform::component::{GlobalTransform, Transform};
+
 pub mod asteroid;
 pub mod bullet;
 pub mod ship;
+
+// Entities spawned this frame have no global transform until the transform system runs.
+fn model_matrix(world: &ChaosWorld, entity_id: EntityID) -> Mat4 {
+    world
+        .get_component::<GlobalTransform>(entity_id)
+        .map(|global| global.as_mat4())
+        .or_else(|| {
+            world
+                .get_component::<Transform>(entity_id)
+                .map(|transform| transform.as_mat4())
+        })
+        .unwrap_or(Mat4::identity())
+}
@@ -1,16 +1,13 @@
 use chaos_engine::{
     ecs::{EntityID, system::ChaosSystem, world::ChaosWorld},
-    math::{Vec2, Vec3},
+    math::{Vec2, Vec3, transform::Transform2D},
+    physics::{body::RigidBody2D, collider::Collider2D},
+    random::resource::ChaosRandom,
     rendering::rendering_system::ChaosRenderableContainer,
+    transform::component::Transform,
 };
-use rand::random_range;
 
-use crate::{
-    components::{
-        shape::ShapeComponent, transform::TransformComponent, velocity::VelocityComponent,
-    },
-    renderables::asteroid::AsteroidRenderable,
-};
+use crate::{components::shape::ShapeComponent, renderables::asteroid::AsteroidRenderable};
 
 pub struct AsteroidSystem {
     spawned_asteroids: Vec<EntityID>,
@@ -29,8 +26,15 @@ impl ChaosSystem for AsteroidSystem {
         let mut spheres: Vec<Vec3> = vec![Vec3::new(0.0, 0.0, 2.5)]; // Start with a sphere at the origin with radius 1.0
 
         while self.spawned_asteroids.len() < 10 {
-            let pos = Vec2::new(random_range(-100.0..100.0), random_range(-100.0..100.0));
-            let radius = random_range(1.0..15.0);
+            let rng = world
+                .get_resource_mut::<ChaosRandom>()
+                .ok_or("Missing random resource")?
+                .system_stream("asteroids");
+            let pos = rng.in_rect(Vec2::new(-100.0, -100.0), Vec2::new(100.0, 100.0));
+            let radius = rng.range(1.0..15.0);
+            let roughness = rng.range(0.25..0.75);
+            let seed = rng.range(0..1000u32);
+            let drift = rng.in_circle(2.0);
 
             let mut collision = false;
             for sphere in &spheres {
@@ -45,16 +49,21 @@ impl ChaosSystem for AsteroidSystem {
             }
 
             spheres.push(Vec3::new(pos.x, pos.y, radius));
+            let shape = ShapeComponent::asteroid(radius, roughness, seed);
+            let collider = Collider2D::from_triangles(&shape.shape)
+                .map_err(|_| "Failed to build asteroid collider")?;
+            let body = RigidBody2D::dynamic(radius * radius)
+                .with_position(pos)
+                .with_velocity(drift)
+                .with_restitution(0.8);
             self.spawned_asteroids.push(
                 world
                     .spawn()
-                    .with(TransformComponent::new().with_position(pos))
-                    .with(VelocityComponent::new())
-                    .with(ShapeComponent::asteroid(
-                        radius,
-                        random_range(0.25..0.75),
-                        random_range(0..1000) as u32,
-                    ))
+                    .with(Transform(Transform2D::from_translation(pos)))
+                    .with(body)
+                    .with(collider)
+                    .with(shape.spatial_bounds(pos))
+                    .with(shape)
                     .with(ChaosRenderableContainer::new(AsteroidRenderable::new()))
                     .build(),
             );
@@ -2,12 +2,11 @@ use chaos_engine::{
     ChaosReceiver,
     ecs::{system::ChaosSystem, world::ChaosWorld},
     math::Vec2,
+    transform::component::Transform,
 };
 
 use crate::{
-    components::{
-        camera::CameraComponent, transform::TransformComponent, velocity::VelocityComponent,
-    },
+    components::{camera::CameraComponent, velocity::VelocityComponent},
     consts::{DeviceEvent, SpecializedEntities},
 };
 
@@ -50,7 +49,7 @@ impl ChaosSystem for CameraSystem {
         // focus on the ship
         let ship_transform: Option<Vec2> = world
             .get_specialized_entity_component(SpecializedEntities::Ship)
-            .map(|transform: &TransformComponent| transform.position);
+            .map(|transform: &Transform| transform.translation);
 
         let ship_velocity: Option<Vec2> = world
             .get_specialized_entity_component(SpecializedEntities::Ship)
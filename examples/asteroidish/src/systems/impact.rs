@@ -1,10 +1,12 @@
 use chaos_engine::ecs::{system::ChaosSystem, world::ChaosWorld};
 use chaos_engine::log;
 use chaos_engine::math::shape::triangle::Triangle2D;
+use chaos_engine::spatial::index::SpatialIndex;
+use chaos_engine::transform::component::GlobalTransform;
 
 use crate::{
-    components::{shape::ShapeComponent, transform::TransformComponent},
-    consts::SpecializedEntities,
+    components::shape::ShapeComponent,
+    consts::{GameState, SpecializedEntities},
 };
 
 pub struct ImpactSystem {}
@@ -28,11 +30,11 @@ impl ChaosSystem for ImpactSystem {
         };
 
         // Snapshot the ship's collision data so we can release the world borrow
-        // before running the query below.
+        // before looking up the other entities below.
         let (ship_position, ship_radius, ship_triangles) = {
             let transform = world
-                .get_component::<TransformComponent>(ship_entity)
-                .ok_or("Ship missing TransformComponent")?;
+                .get_component::<GlobalTransform>(ship_entity)
+                .ok_or("Ship missing GlobalTransform")?;
             let shape = world
                 .get_component::<ShapeComponent>(ship_entity)
                 .ok_or("Ship missing ShapeComponent")?;
@@ -44,26 +46,27 @@ impl ChaosSystem for ImpactSystem {
                     tri * matrix
                 })
                 .collect();
-            (transform.position, shape.bounding_radius, triangles)
+            (transform.translation, shape.bounding_radius, triangles)
         };
 
-        // Broad phase: bounding-sphere overlap. Narrow phase: triangle-vs-triangle SAT.
-        let mut query = world
-            .query::<(&TransformComponent, &ShapeComponent)>()
-            .map_err(|_| "Failed to query for collidable entities")?;
+        // Broad phase: bounding-circle overlap from the spatial index. Narrow phase:
+        // triangle-vs-triangle SAT.
+        let candidates = world
+            .get_resource::<SpatialIndex>()
+            .ok_or("Missing spatial index")?
+            .query_radius(ship_position, ship_radius);
 
         let mut collided = false;
-        'outer: for (entity, (transform, shape)) in query.iter_mut() {
+        'outer: for entity in candidates {
             if entity == ship_entity {
                 continue;
             }
-
-            // Broad phase.
-            let combined = ship_radius + shape.bounding_radius;
-            let distance_sq = (transform.position - ship_position).length_squared();
-            if distance_sq > combined * combined {
+            let (Some(transform), Some(shape)) = (
+                world.get_component::<GlobalTransform>(entity),
+                world.get_component::<ShapeComponent>(entity),
+            ) else {
                 continue;
-            }
+            };
 
             // Narrow phase: check every ship triangle against every asteroid triangle.
             for ship_tri in &ship_triangles {
@@ -77,13 +80,11 @@ impl ChaosSystem for ImpactSystem {
             }
         }
 
-        // Drop the query borrow before mutating the world.
-        drop(query);
-
         if collided {
             log::info!("Ship destroyed by asteroid impact");
             world.despawn(ship_entity);
             world.unregister_specialized_entity(SpecializedEntities::Ship);
+            world.request_state(GameState::GameOver);
         }
 
         Ok(())
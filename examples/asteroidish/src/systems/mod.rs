@@ -2,4 +2,4 @@ pub mod asteroid;
 pub mod camera;
 pub mod impact;
 pub mod ship;
-pub mod transform;
+pub mod velocity;
@@ -1,14 +1,13 @@
 use chaos_engine::{
     ChaosReceiver,
     ecs::{system::ChaosSystem, world::ChaosWorld},
-    math::{Vec2, matrix::Mat3},
+    math::{Vec2, matrix::Mat3, transform::Transform2D},
     rendering::rendering_system::ChaosRenderableContainer,
+    transform::component::Transform,
 };
 
 use crate::{
-    components::{
-        shape::ShapeComponent, transform::TransformComponent, velocity::VelocityComponent,
-    },
+    components::{shape::ShapeComponent, velocity::VelocityComponent},
     consts::SpecializedEntities,
     renderables::bullet::BulletRenderable,
 };
@@ -88,11 +87,13 @@ impl ChaosSystem for ShipSystem {
         self.rotate_right_receiver = Some(world.register_for_trigger(ShipEvent::RotateRight));
 
         // create the ship
+        let shape = ShapeComponent::ship();
         world
             .spawn()
-            .with(TransformComponent::new())
+            .with(Transform::default())
             .with(VelocityComponent::new())
-            .with(ShapeComponent::ship())
+            .with(shape.spatial_bounds(Vec2::zero()))
+            .with(shape)
             .with(ChaosRenderableContainer::new(ShipRenderable::new()))
             .specialized(SpecializedEntities::Ship)
             .build();
@@ -110,9 +111,7 @@ impl ChaosSystem for ShipSystem {
 
         let (transform_component, velocity_component) = {
             let query = world
-                .query_for_entity::<(&mut TransformComponent, &mut VelocityComponent)>(
-                    ship_entity.unwrap(),
-                );
+                .query_for_entity::<(&mut Transform, &mut VelocityComponent)>(ship_entity.unwrap());
 
             if query.is_none() {
                 return Err("Failed to query ship components");
@@ -145,7 +144,7 @@ impl ChaosSystem for ShipSystem {
             }
         }
 
-        let ship_position = transform_component.position;
+        let ship_position = transform_component.translation;
         let ship_rotation = transform_component.rotation;
         let ship_velocity = velocity_component.velocity;
 
@@ -154,17 +153,19 @@ impl ChaosSystem for ShipSystem {
             let firing_direction = Mat3::rotation(ship_rotation) * Vec2::new(0.0, -1.0);
             let initial_position = ship_position + firing_direction * 0.5; // Offset the bullet's initial position
             let initial_velocity = ship_velocity + firing_direction * firing_speed;
+            let shape = ShapeComponent::bullet();
             world
                 .spawn()
-                .with(TransformComponent {
-                    position: initial_position,
-                    rotation: ship_rotation,
-                    scale: Vec2::one(),
-                })
+                .with(Transform(Transform2D::new(
+                    initial_position,
+                    ship_rotation,
+                    Vec2::one(),
+                )))
                 .with(VelocityComponent {
                     velocity: initial_velocity,
                 })
-                .with(ShapeComponent::bullet())
+                .with(shape.spatial_bounds(initial_position))
+                .with(shape)
                 .with(ChaosRenderableContainer::new(BulletRenderable::new()))
                 .build();
         }
@@ -1,15 +1,18 @@
-use crate::components::transform::TransformComponent;
 use crate::components::velocity::VelocityComponent;
-use chaos_engine::{ecs::system::ChaosSystem, ecs::world::ChaosWorld};
-pub struct TransformSystem {}
+use chaos_engine::{
+    ecs::system::ChaosSystem, ecs::world::ChaosWorld, transform::component::Transform,
+};
 
-impl TransformSystem {
+// Moves the entities that aren't simulated by the physics system, i.e. the ship and bullets.
+pub struct VelocitySystem {}
+
+impl VelocitySystem {
     pub fn new() -> Self {
         Self {}
     }
 }
 
-impl ChaosSystem for TransformSystem {
+impl ChaosSystem for VelocitySystem {
     fn initialize(&mut self, _world: &mut ChaosWorld) -> Result<(), &'static str> {
         Ok(())
     }
@@ -17,11 +20,11 @@ impl ChaosSystem for TransformSystem {
     fn update(&mut self, world: &mut ChaosWorld) -> Result<(), &'static str> {
         let delta_time = world.get_time().delta_time();
         let mut query = world
-            .query::<(&mut TransformComponent, &VelocityComponent)>()
+            .query::<(&mut Transform, &VelocityComponent)>()
             .map_err(|_| "Failed to query transform components")?;
 
         for (_, (transform, velocity)) in query.iter_mut() {
-            transform.position += velocity.velocity * delta_time;
+            transform.translation += velocity.velocity * delta_time;
         }
 
         Ok(())
@@ -0,0 +1,402 @@
+use std::fmt::Display;
+
+use crate::{
+    ai::blackboard::Blackboard,
+    ecs::{EntityID, world::ChaosWorld},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeStatus {
+    Success,
+    Failure,
+    /// Not done yet; the node is ticked again on the next update.
+    Running,
+}
+
+/// What conditions and actions see when they are ticked.
+pub struct BehaviourContext<'a> {
+    pub world: &'a mut ChaosWorld,
+    pub entity: EntityID,
+    pub blackboard: &'a mut Blackboard,
+    pub delta_time: f32,
+}
+
+pub type ConditionFn = Box<dyn FnMut(&BehaviourContext) -> bool>;
+pub type ActionFn = Box<dyn FnMut(&mut BehaviourContext) -> NodeStatus>;
+pub type UtilityScoreFn = Box<dyn FnMut(&BehaviourContext) -> f32>;
+
+/// Changes the result of a decorated node.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Decorator {
+    /// Turns success into failure and the other way round.
+    Invert,
+    AlwaysSucceed,
+    AlwaysFail,
+    /// Runs the child until it succeeded the given number of times; fails if it fails.
+    Repeat(u32),
+    /// Runs the child again after failures, up to the given number of attempts.
+    Retry(u32),
+}
+
+enum NodeKind {
+    // Remembers the running child, so it continues there on the next tick.
+    Sequence {
+        children: Vec<BehaviourNode>,
+        current: usize,
+    },
+    // Starts from the first child on every tick; the running child is remembered only to
+    // reset it when a higher priority child takes over.
+    Selector {
+        children: Vec<BehaviourNode>,
+        running: Option<usize>,
+    },
+    Utility {
+        children: Vec<(UtilityScoreFn, BehaviourNode)>,
+        running: Option<usize>,
+    },
+    Decorator {
+        decorator: Decorator,
+        child: Box<BehaviourNode>,
+        count: u32,
+    },
+    Condition(ConditionFn),
+    Action(ActionFn),
+}
+
+/// A node of a behaviour tree.
+///
+/// ```rust
+/// use chaos_engine::ai::behaviour::{BehaviourNode, NodeStatus};
+///
+/// let attack = BehaviourNode::selector(vec![
+///     BehaviourNode::sequence(vec![
+///         BehaviourNode::condition("has target", |context| context.blackboard.contains("target")),
+///         BehaviourNode::action("fire", |_| NodeStatus::Success),
+///     ]),
+///     BehaviourNode::action("patrol", |_| NodeStatus::Running),
+/// ])
+/// .named("attack or patrol");
+/// ```
+pub struct BehaviourNode {
+    name: String,
+    // Status of the last tick of the tree, `None` if the node wasn't ticked in it.
+    status: Option<NodeStatus>,
+    kind: NodeKind,
+}
+
+impl BehaviourNode {
+    fn new(name: &str, kind: NodeKind) -> Self {
+        Self {
+            name: name.to_string(),
+            status: None,
+            kind,
+        }
+    }
+
+    /// Ticks the children in order until one fails. Succeeds if all of them succeed.
+    pub fn sequence(children: Vec<BehaviourNode>) -> Self {
+        Self::new(
+            "Sequence",
+            NodeKind::Sequence {
+                children,
+                current: 0,
+            },
+        )
+    }
+
+    /// Ticks the children in order until one succeeds or runs. Fails if all of them fail.
+    /// Earlier children have priority: they are checked again on every tick, even while a
+    /// later child is running.
+    pub fn selector(children: Vec<BehaviourNode>) -> Self {
+        Self::new(
+            "Selector",
+            NodeKind::Selector {
+                children,
+                running: None,
+            },
+        )
+    }
+
+    /// Ticks the child with the highest score, scored every time a new choice is made
+    /// (not while the chosen child is running). Fails without children.
+    pub fn utility(children: Vec<(UtilityScoreFn, BehaviourNode)>) -> Self {
+        Self::new(
+            "Utility",
+            NodeKind::Utility {
+                children,
+                running: None,
+            },
+        )
+    }
+
+    pub fn decorate(decorator: Decorator, child: BehaviourNode) -> Self {
+        Self::new(
+            &format!("{decorator:?}"),
+            NodeKind::Decorator {
+                decorator,
+                child: Box::new(child),
+                count: 0,
+            },
+        )
+    }
+
+    pub fn invert(child: BehaviourNode) -> Self {
+        Self::decorate(Decorator::Invert, child)
+    }
+
+    pub fn condition(
+        name: &str,
+        condition: impl FnMut(&BehaviourContext) -> bool + 'static,
+    ) -> Self {
+        Self::new(name, NodeKind::Condition(Box::new(condition)))
+    }
+
+    pub fn action(
+        name: &str,
+        action: impl FnMut(&mut BehaviourContext) -> NodeStatus + 'static,
+    ) -> Self {
+        Self::new(name, NodeKind::Action(Box::new(action)))
+    }
+
+    /// Names the node in [`NodeState`]s, for debugging.
+    pub fn named(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn tick(&mut self, context: &mut BehaviourContext) -> NodeStatus {
+        let status = match &mut self.kind {
+            NodeKind::Sequence { children, current } => tick_sequence(children, current, context),
+            NodeKind::Selector { children, running } => tick_selector(children, running, context),
+            NodeKind::Utility { children, running } => {
+                let chosen = running.or_else(|| {
+                    children
+                        .iter_mut()
+                        .enumerate()
+                        .map(|(index, (score, _))| (index, score(context)))
+                        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                        .map(|(index, _)| index)
+                });
+                match chosen {
+                    Some(index) => {
+                        let status = children[index].1.tick(context);
+                        *running = (status == NodeStatus::Running).then_some(index);
+                        status
+                    }
+                    None => NodeStatus::Failure,
+                }
+            }
+            NodeKind::Decorator {
+                decorator,
+                child,
+                count,
+            } => tick_decorator(*decorator, child, count, context),
+            NodeKind::Condition(condition) => {
+                if condition(context) {
+                    NodeStatus::Success
+                } else {
+                    NodeStatus::Failure
+                }
+            }
+            NodeKind::Action(action) => action(context),
+        };
+        self.status = Some(status);
+        status
+    }
+
+    /// Forgets the running child of composites, so the next tick starts from the beginning.
+    pub fn reset(&mut self) {
+        match &mut self.kind {
+            NodeKind::Sequence { children, current } => {
+                *current = 0;
+                children.iter_mut().for_each(BehaviourNode::reset);
+            }
+            NodeKind::Selector { children, running } => {
+                *running = None;
+                children.iter_mut().for_each(BehaviourNode::reset);
+            }
+            NodeKind::Utility { children, running } => {
+                *running = None;
+                children.iter_mut().for_each(|(_, child)| child.reset());
+            }
+            NodeKind::Decorator { child, count, .. } => {
+                *count = 0;
+                child.reset();
+            }
+            NodeKind::Condition(_) | NodeKind::Action(_) => (),
+        }
+    }
+
+    pub fn state(&self) -> NodeState {
+        let children = match &self.kind {
+            NodeKind::Sequence { children, .. } | NodeKind::Selector { children, .. } => {
+                children.iter().map(BehaviourNode::state).collect()
+            }
+            NodeKind::Utility { children, .. } => {
+                children.iter().map(|(_, child)| child.state()).collect()
+            }
+            NodeKind::Decorator { child, .. } => vec![child.state()],
+            NodeKind::Condition(_) | NodeKind::Action(_) => Vec::new(),
+        };
+        NodeState {
+            name: self.name.clone(),
+            status: self.status,
+            children,
+        }
+    }
+
+    fn clear_status(&mut self) {
+        self.status = None;
+        match &mut self.kind {
+            NodeKind::Sequence { children, .. } | NodeKind::Selector { children, .. } => {
+                children.iter_mut().for_each(BehaviourNode::clear_status);
+            }
+            NodeKind::Utility { children, .. } => {
+                children
+                    .iter_mut()
+                    .for_each(|(_, child)| child.clear_status());
+            }
+            NodeKind::Decorator { child, .. } => child.clear_status(),
+            NodeKind::Condition(_) | NodeKind::Action(_) => (),
+        }
+    }
+}
+
+fn tick_sequence(
+    children: &mut [BehaviourNode],
+    current: &mut usize,
+    context: &mut BehaviourContext,
+) -> NodeStatus {
+    while let Some(child) = children.get_mut(*current) {
+        match child.tick(context) {
+            NodeStatus::Success => *current += 1,
+            NodeStatus::Running => return NodeStatus::Running,
+            NodeStatus::Failure => {
+                *current = 0;
+                children.iter_mut().for_each(BehaviourNode::reset);
+                return NodeStatus::Failure;
+            }
+        }
+    }
+    *current = 0;
+    children.iter_mut().for_each(BehaviourNode::reset);
+    NodeStatus::Success
+}
+
+fn tick_selector(
+    children: &mut [BehaviourNode],
+    running: &mut Option<usize>,
+    context: &mut BehaviourContext,
+) -> NodeStatus {
+    for index in 0..children.len() {
+        let status = children[index].tick(context);
+        if status == NodeStatus::Failure {
+            continue;
+        }
+        // A higher priority child interrupts the one that was running.
+        if let Some(previous) = running.filter(|previous| *previous != index) {
+            children[previous].reset();
+        }
+        *running = (status == NodeStatus::Running).then_some(index);
+        return status;
+    }
+    *running = None;
+    NodeStatus::Failure
+}
+
+fn tick_decorator(
+    decorator: Decorator,
+    child: &mut BehaviourNode,
+    count: &mut u32,
+    context: &mut BehaviourContext,
+) -> NodeStatus {
+    let status = child.tick(context);
+    match (decorator, status) {
+        (_, NodeStatus::Running) => NodeStatus::Running,
+        (Decorator::Invert, NodeStatus::Success) => NodeStatus::Failure,
+        (Decorator::Invert, _) => NodeStatus::Success,
+        (Decorator::AlwaysSucceed, _) => NodeStatus::Success,
+        (Decorator::AlwaysFail, _) => NodeStatus::Failure,
+        (Decorator::Repeat(times), NodeStatus::Success)
+        | (Decorator::Retry(times), NodeStatus::Failure) => {
+            *count += 1;
+            child.reset();
+            if *count >= times {
+                *count = 0;
+                status
+            } else {
+                NodeStatus::Running
+            }
+        }
+        (Decorator::Repeat(_) | Decorator::Retry(_), status) => {
+            *count = 0;
+            status
+        }
+    }
+}
+
+/// Snapshot of a node and its children after the last tick, for debugging. Displays as an
+/// indented tree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeState {
+    pub name: String,
+    pub status: Option<NodeStatus>,
+    pub children: Vec<NodeState>,
+}
+
+impl NodeState {
+    fn write(&self, f: &mut std::fmt::Formatter<'_>, depth: usize) -> std::fmt::Result {
+        let status = match self.status {
+            Some(status) => format!("{status:?}"),
+            None => "-".to_string(),
+        };
+        writeln!(
+            f,
+            "{:indent$}{} [{}]",
+            "",
+            self.name,
+            status,
+            indent = depth * 2
+        )?;
+        for child in &self.children {
+            child.write(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for NodeState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+/// Component holding the behaviour tree of an entity, ticked by the `BehaviourSystem`.
+/// When the root finishes, the tree starts over on the next tick.
+pub struct BehaviourTree {
+    // Taken out while the tree is ticked, so nodes can use the world.
+    pub(crate) root: Option<BehaviourNode>,
+}
+
+impl BehaviourTree {
+    pub fn new(root: BehaviourNode) -> Self {
+        Self { root: Some(root) }
+    }
+
+    /// Status of the root after the last tick.
+    pub fn status(&self) -> Option<NodeStatus> {
+        self.root.as_ref()?.status
+    }
+
+    pub fn state(&self) -> Option<NodeState> {
+        self.root.as_ref().map(BehaviourNode::state)
+    }
+
+    pub(crate) fn tick_root(
+        root: &mut BehaviourNode,
+        context: &mut BehaviourContext,
+    ) -> NodeStatus {
+        root.clear_status();
+        root.tick(context)
+    }
+}
@@ -0,0 +1,68 @@
+use std::{any::Any, collections::HashMap};
+
+/// Per-entity memory of a behaviour tree, e.g. the current target or a patrol point. Stored
+/// as a component next to the entity's `BehaviourTree`.
+#[derive(Default)]
+pub struct Blackboard {
+    values: HashMap<String, Box<dyn Any>>,
+}
+
+impl Blackboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with<T: Any>(mut self, key: &str, value: T) -> Self {
+        self.set(key, value);
+        self
+    }
+
+    /// Stores `value` under `key`, replacing any previous value, also of another type.
+    pub fn set<T: Any>(&mut self, key: &str, value: T) {
+        self.values.insert(key.to_string(), Box::new(value));
+    }
+
+    /// The value under `key`, `None` if there is none or it isn't a `T`.
+    pub fn get<T: Any>(&self, key: &str) -> Option<&T> {
+        self.values.get(key)?.downcast_ref()
+    }
+
+    pub fn get_mut<T: Any>(&mut self, key: &str) -> Option<&mut T> {
+        self.values.get_mut(key)?.downcast_mut()
+    }
+
+    pub fn remove(&mut self, key: &str) -> bool {
+        self.values.remove(key).is_some()
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_are_typed() {
+        let mut blackboard = Blackboard::new().with("target", 7u64);
+
+        assert_eq!(blackboard.get::<u64>("target"), Some(&7));
+        assert_eq!(blackboard.get::<f32>("target"), None);
+
+        *blackboard.get_mut::<u64>("target").unwrap() += 1;
+        blackboard.set("target", "none");
+        assert_eq!(blackboard.get::<&str>("target"), Some(&"none"));
+        assert!(blackboard.remove("target"));
+        assert!(blackboard.is_empty());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod behaviour;
+pub mod blackboard;
+pub mod system;
@@ -0,0 +1,247 @@
+use crate::{
+    ai::{
+        behaviour::{BehaviourContext, BehaviourNode, BehaviourTree},
+        blackboard::Blackboard,
+    },
+    ecs::{EntityID, system::ChaosSystem, world::ChaosWorld},
+};
+
+/// Ticks the [`BehaviourTree`] of every entity once per update, with the entity's
+/// [`Blackboard`] component (an empty one is added when the tree first stores a value).
+pub struct BehaviourSystem;
+
+impl BehaviourSystem {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub(crate) fn tick(world: &mut ChaosWorld, delta_time: f32) -> Result<(), &'static str> {
+        let mut trees: Vec<(EntityID, BehaviourNode)> = Vec::new();
+        for (entity, (tree,)) in world
+            .query::<(&mut BehaviourTree,)>()
+            .map_err(|_| "Failed to query behaviour trees")?
+        {
+            if let Some(root) = tree.root.take() {
+                trees.push((entity, root));
+            }
+        }
+
+        for (entity, mut root) in trees {
+            let mut blackboard = world
+                .get_component_mut::<Blackboard>(entity)
+                .map(std::mem::take)
+                .unwrap_or_default();
+
+            let mut context = BehaviourContext {
+                world,
+                entity,
+                blackboard: &mut blackboard,
+                delta_time,
+            };
+            BehaviourTree::tick_root(&mut root, &mut context);
+
+            // A node may have despawned the entity or replaced its tree; then this tree
+            // is dropped.
+            if let Some(tree) = world.get_component_mut::<BehaviourTree>(entity) {
+                tree.root.get_or_insert(root);
+            }
+            let has_tree = world.get_component::<BehaviourTree>(entity).is_some();
+            match world.get_component_mut::<Blackboard>(entity) {
+                Some(stored) => *stored = blackboard,
+                None if has_tree && !blackboard.is_empty() => {
+                    world
+                        .add_component(entity, blackboard)
+                        .map_err(|_| "Failed to add blackboard")?;
+                }
+                None => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for BehaviourSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChaosSystem for BehaviourSystem {
+    fn initialize(&mut self, _world: &mut ChaosWorld) -> Result<(), &'static str> {
+        Ok(())
+    }
+
+    fn update(&mut self, world: &mut ChaosWorld) -> Result<(), &'static str> {
+        let delta_time = world.get_time().delta_time();
+        Self::tick(world, delta_time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::behaviour::{Decorator, NodeState, NodeStatus};
+
+    struct Health(f32);
+    struct Fled;
+
+    fn enemy_tree() -> BehaviourTree {
+        BehaviourTree::new(
+            BehaviourNode::selector(vec![
+                BehaviourNode::sequence(vec![
+                    BehaviourNode::condition("low health", |context| {
+                        context
+                            .world
+                            .get_component::<Health>(context.entity)
+                            .is_some_and(|health| health.0 < 10.0)
+                    }),
+                    BehaviourNode::action("flee", |context| {
+                        let entity = context.entity;
+                        context.world.add_component(entity, Fled).unwrap();
+                        NodeStatus::Success
+                    }),
+                ])
+                .named("flee when hurt"),
+                BehaviourNode::action("attack", |context| {
+                    *context.blackboard.get_mut::<u32>("shots").unwrap() += 1;
+                    NodeStatus::Running
+                }),
+            ])
+            .named("root"),
+        )
+    }
+
+    #[test]
+    fn tree_ticks_with_world_and_blackboard_access() {
+        let mut world = ChaosWorld::new();
+        let enemy = world
+            .spawn()
+            .with(Health(100.0))
+            .with(Blackboard::new().with("shots", 0u32))
+            .with(enemy_tree())
+            .build();
+
+        BehaviourSystem::tick(&mut world, 0.1).unwrap();
+        BehaviourSystem::tick(&mut world, 0.1).unwrap();
+        let blackboard = world.get_component::<Blackboard>(enemy).unwrap();
+        assert_eq!(blackboard.get::<u32>("shots"), Some(&2));
+        let tree = world.get_component::<BehaviourTree>(enemy).unwrap();
+        assert_eq!(tree.status(), Some(NodeStatus::Running));
+
+        world.get_component_mut::<Health>(enemy).unwrap().0 = 5.0;
+        BehaviourSystem::tick(&mut world, 0.1).unwrap();
+        assert!(world.get_component::<Fled>(enemy).is_some());
+    }
+
+    #[test]
+    fn state_shows_what_was_ticked() {
+        let mut world = ChaosWorld::new();
+        let enemy = world
+            .spawn()
+            .with(Health(100.0))
+            .with(Blackboard::new().with("shots", 0u32))
+            .with(enemy_tree())
+            .build();
+
+        BehaviourSystem::tick(&mut world, 0.1).unwrap();
+
+        let state = world
+            .get_component::<BehaviourTree>(enemy)
+            .unwrap()
+            .state()
+            .unwrap();
+        let leaf = |name: &str, status| NodeState {
+            name: name.to_string(),
+            status,
+            children: Vec::new(),
+        };
+        assert_eq!(
+            state,
+            NodeState {
+                name: "root".to_string(),
+                status: Some(NodeStatus::Running),
+                children: vec![
+                    NodeState {
+                        name: "flee when hurt".to_string(),
+                        status: Some(NodeStatus::Failure),
+                        children: vec![
+                            leaf("low health", Some(NodeStatus::Failure)),
+                            leaf("flee", None),
+                        ],
+                    },
+                    leaf("attack", Some(NodeStatus::Running)),
+                ],
+            }
+        );
+        assert_eq!(
+            state.to_string(),
+            "root [Running]\n  flee when hurt [Failure]\n    low health [Failure]\n    flee [-]\n  attack [Running]\n"
+        );
+    }
+
+    #[test]
+    fn sequences_resume_running_children_and_decorators_change_results() {
+        let mut world = ChaosWorld::new();
+        let entity = world.spawn().build();
+        let mut blackboard = Blackboard::new();
+        let mut context = BehaviourContext {
+            world: &mut world,
+            entity,
+            blackboard: &mut blackboard,
+            delta_time: 0.0,
+        };
+        let mut ticks = 0;
+        let mut sequence = BehaviourNode::sequence(vec![
+            BehaviourNode::action("count", |context| {
+                let count = context.blackboard.get::<u32>("count").copied().unwrap_or(0);
+                context.blackboard.set("count", count + 1);
+                NodeStatus::Success
+            }),
+            BehaviourNode::decorate(
+                Decorator::Repeat(2),
+                BehaviourNode::action("step", |_| NodeStatus::Success),
+            ),
+            BehaviourNode::invert(BehaviourNode::condition("never", |_| false)),
+        ]);
+
+        let mut status = NodeStatus::Running;
+        while status == NodeStatus::Running {
+            status = sequence.tick(&mut context);
+            ticks += 1;
+        }
+
+        assert_eq!(status, NodeStatus::Success);
+        assert_eq!(ticks, 2);
+        assert_eq!(context.blackboard.get::<u32>("count"), Some(&1));
+    }
+
+    #[test]
+    fn utility_picks_the_highest_score() {
+        let mut world = ChaosWorld::new();
+        let entity = world.spawn().build();
+        let mut blackboard = Blackboard::new();
+        let mut context = BehaviourContext {
+            world: &mut world,
+            entity,
+            blackboard: &mut blackboard,
+            delta_time: 0.0,
+        };
+        let mut utility = BehaviourNode::utility(vec![
+            (
+                Box::new(|_: &BehaviourContext| 0.2),
+                BehaviourNode::action("wander", |_| NodeStatus::Failure),
+            ),
+            (
+                Box::new(|_: &BehaviourContext| 0.8),
+                BehaviourNode::action("hunt", |_| NodeStatus::Success),
+            ),
+        ]);
+
+        assert_eq!(utility.tick(&mut context), NodeStatus::Success);
+        assert_eq!(
+            utility.state().children[1].status,
+            Some(NodeStatus::Success)
+        );
+        assert_eq!(utility.state().children[0].status, None);
+    }
+}
@@ -3,10 +3,14 @@ use std::time::{Duration, Instant};
 use winit::event::WindowEvent;
 
 use crate::device::{
-    events::{ChaosDeviceEvent, ChaosInputEvent, ChaosKeyCode, ChaosMouseButton},
+    events::{ChaosDeviceEvent, ChaosInputEvent, ChaosKeyCode, ChaosModifiers, ChaosMouseButton},
+    gestures::{ChaosGesture, ChaosSwipeDirection},
     system::ChaosBindingContext,
 };
 
+// Maximum time between the clicks of `ChaosBindingEvent::double_click`/`triple_click`.
+pub const DEFAULT_MULTI_TAP_INTERVAL: Duration = Duration::from_millis(500);
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum ChaosBindingEvent {
     Input(ChaosInputEventMatcher),
@@ -23,6 +27,32 @@ pub enum ChaosBindingEvent {
     Chord {
         keys: Vec<ChaosButton>,
     },
+    // Press and release within `max_duration`. Fires on release.
+    Tap {
+        button: ChaosButton,
+        max_duration: Duration,
+    },
+    // The `count`th press in a row with at most `max_interval` between presses, e.g. a
+    // double click. Fires on that press; a triple click fires both the double and the
+    // triple binding, each once.
+    MultiTap {
+        button: ChaosButton,
+        count: u32,
+        max_interval: Duration,
+    },
+    // Release after holding for at least `min_duration` (charge and release).
+    ReleasedAfterHeld {
+        button: ChaosButton,
+        min_duration: Duration,
+    },
+    // Matches `matcher` only while exactly `modifiers` are held, e.g. Ctrl+S but not
+    // Ctrl+Shift+S.
+    Modified {
+        matcher: ChaosInputEventMatcher,
+        modifiers: ChaosModifiers,
+    },
+    // A gesture recognized from touch input, see `ChaosGestureRecognizer`.
+    Gesture(ChaosGestureMatcher),
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -35,8 +65,30 @@ pub enum ChaosButton {
 pub enum ChaosInputEventMatcher {
     Pressed(ChaosButton),
     Released(ChaosButton),
+    // OS-level key repeat while the key is held down.
+    Repeated(ChaosButton),
     MouseMoved,
     MouseWheel,
+    // Relative mouse movement, see `ChaosInputEvent::MouseMotion`.
+    MouseMotion,
+    // Any event that enters text, see `ChaosInputEvent::text`.
+    Text,
+    ModifiersChanged,
+    Ime,
+    // Raw touch events of any finger and phase.
+    Touch,
+    PinchGesture,
+    RotationGesture,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ChaosGestureMatcher {
+    Tap,
+    DoubleTap,
+    LongPress,
+    Swipe(ChaosSwipeDirection),
+    Pinch,
+    Rotation,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -60,6 +112,18 @@ impl ChaosButton {
     }
 }
 
+impl From<ChaosKeyCode> for ChaosButton {
+    fn from(key: ChaosKeyCode) -> Self {
+        ChaosButton::Keyboard(key)
+    }
+}
+
+impl From<ChaosMouseButton> for ChaosButton {
+    fn from(button: ChaosMouseButton) -> Self {
+        ChaosButton::Mouse(button)
+    }
+}
+
 impl ChaosBindingEvent {
     pub fn matches(&self, context: &ChaosBindingContext, event: &WindowEvent) -> bool {
         self.matches_at(context, event, Instant::now())
@@ -82,6 +146,31 @@ impl ChaosBindingEvent {
                 button, duration, ..
             } => context.button_held_for_at(button, *duration, now),
             ChaosBindingEvent::Chord { keys } => context.chord_matches(keys),
+            ChaosBindingEvent::Tap {
+                button,
+                max_duration,
+            } => ChaosInputEvent::try_from(event)
+                .is_ok_and(|input_event| context.tap_matches(button, *max_duration, &input_event)),
+            ChaosBindingEvent::MultiTap {
+                button,
+                count,
+                max_interval,
+            } => ChaosInputEvent::try_from(event).is_ok_and(|input_event| {
+                context.multi_tap_matches(button, *count, *max_interval, &input_event)
+            }),
+            ChaosBindingEvent::ReleasedAfterHeld {
+                button,
+                min_duration,
+            } => ChaosInputEvent::try_from(event).is_ok_and(|input_event| {
+                context.released_after_held_matches(button, *min_duration, &input_event)
+            }),
+            ChaosBindingEvent::Modified { matcher, modifiers } => {
+                context.modifiers() == *modifiers && matcher.matches(context, event)
+            }
+            ChaosBindingEvent::Gesture(matcher) => context
+                .recognized_gestures()
+                .iter()
+                .any(|gesture| matcher.matches_gesture(gesture)),
         }
     }
 
@@ -124,6 +213,59 @@ impl ChaosBindingEvent {
     pub fn chord(keys: Vec<ChaosButton>) -> Self {
         ChaosBindingEvent::Chord { keys }
     }
+
+    pub fn tap(button: ChaosButton, max_duration: Duration) -> Self {
+        ChaosBindingEvent::Tap {
+            button,
+            max_duration,
+        }
+    }
+
+    pub fn multi_tap(button: ChaosButton, count: u32, max_interval: Duration) -> Self {
+        ChaosBindingEvent::MultiTap {
+            button,
+            count,
+            max_interval,
+        }
+    }
+
+    pub fn double_click(button: ChaosMouseButton) -> Self {
+        Self::multi_tap(ChaosButton::Mouse(button), 2, DEFAULT_MULTI_TAP_INTERVAL)
+    }
+
+    pub fn triple_click(button: ChaosMouseButton) -> Self {
+        Self::multi_tap(ChaosButton::Mouse(button), 3, DEFAULT_MULTI_TAP_INTERVAL)
+    }
+
+    pub fn released_after_held(button: ChaosButton, min_duration: Duration) -> Self {
+        ChaosBindingEvent::ReleasedAfterHeld {
+            button,
+            min_duration,
+        }
+    }
+
+    pub fn with_modifiers(matcher: ChaosInputEventMatcher, modifiers: ChaosModifiers) -> Self {
+        ChaosBindingEvent::Modified { matcher, modifiers }
+    }
+
+    pub fn shortcut(modifiers: ChaosModifiers, key: ChaosKeyCode) -> Self {
+        ChaosBindingEvent::Modified {
+            matcher: ChaosInputEventMatcher::Pressed(ChaosButton::Keyboard(key)),
+            modifiers,
+        }
+    }
+
+    pub fn text() -> Self {
+        ChaosBindingEvent::Input(ChaosInputEventMatcher::Text)
+    }
+
+    pub fn mouse_motion() -> Self {
+        ChaosBindingEvent::Input(ChaosInputEventMatcher::MouseMotion)
+    }
+
+    pub fn gesture(gesture: ChaosGestureMatcher) -> Self {
+        ChaosBindingEvent::Gesture(gesture)
+    }
 }
 
 impl ChaosInputEventMatcher {
@@ -136,10 +278,13 @@ impl ChaosInputEventMatcher {
     pub(crate) fn matches_input_event(&self, input_event: &ChaosInputEvent) -> bool {
         match self {
             ChaosInputEventMatcher::Pressed(button) => {
-                Self::button_matches(button, input_event, true)
+                Self::button_matches(button, input_event, true, false)
             }
             ChaosInputEventMatcher::Released(button) => {
-                Self::button_matches(button, input_event, false)
+                Self::button_matches(button, input_event, false, false)
+            }
+            ChaosInputEventMatcher::Repeated(button) => {
+                Self::button_matches(button, input_event, true, true)
             }
             ChaosInputEventMatcher::MouseMoved => {
                 matches!(input_event, ChaosInputEvent::MousePosition { .. })
@@ -147,6 +292,21 @@ impl ChaosInputEventMatcher {
             ChaosInputEventMatcher::MouseWheel => {
                 matches!(input_event, ChaosInputEvent::MouseWheel { .. })
             }
+            ChaosInputEventMatcher::MouseMotion => {
+                matches!(input_event, ChaosInputEvent::MouseMotion { .. })
+            }
+            ChaosInputEventMatcher::Text => input_event.text().is_some(),
+            ChaosInputEventMatcher::ModifiersChanged => {
+                matches!(input_event, ChaosInputEvent::ModifiersChanged { .. })
+            }
+            ChaosInputEventMatcher::Ime => matches!(input_event, ChaosInputEvent::Ime { .. }),
+            ChaosInputEventMatcher::Touch => matches!(input_event, ChaosInputEvent::Touch { .. }),
+            ChaosInputEventMatcher::PinchGesture => {
+                matches!(input_event, ChaosInputEvent::PinchGesture { .. })
+            }
+            ChaosInputEventMatcher::RotationGesture => {
+                matches!(input_event, ChaosInputEvent::RotationGesture { .. })
+            }
         }
     }
 
@@ -154,21 +314,38 @@ impl ChaosInputEventMatcher {
         button: &ChaosButton,
         input_event: &ChaosInputEvent,
         expected_pressed: bool,
+        expected_repeat: bool,
     ) -> bool {
         match (button, input_event) {
             (
                 ChaosButton::Keyboard(expected_keycode),
-                ChaosInputEvent::KeyboardInput { keycode, pressed },
-            ) => expected_keycode == keycode && *pressed == expected_pressed,
+                ChaosInputEvent::KeyboardInput {
+                    keycode,
+                    pressed,
+                    repeat,
+                    ..
+                },
+            ) => {
+                expected_keycode == keycode
+                    && *pressed == expected_pressed
+                    && *repeat == expected_repeat
+            }
+            // Mouse buttons never repeat.
             (
                 ChaosButton::Mouse(expected_button),
                 ChaosInputEvent::MouseButton { button, pressed },
-            ) => expected_button == button && *pressed == expected_pressed,
+            ) => expected_button == button && *pressed == expected_pressed && !expected_repeat,
             _ => false,
         }
     }
 }
 
+impl ChaosGestureMatcher {
+    pub fn matches_gesture(&self, gesture: &ChaosGesture) -> bool {
+        gesture.matcher() == *self
+    }
+}
+
 impl ChaosDeviceEventMatcher {
     pub fn matches(&self, windows_event: &WindowEvent) -> bool {
         ChaosDeviceEvent::try_from(windows_event)
@@ -210,7 +387,8 @@ impl ChaosDeviceEventMatcher {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
+    use winit::event::{DeviceId, ElementState, Ime, MouseButton, WindowEvent};
+    use winit::keyboard::{KeyCode, ModifiersState};
 
     #[test]
     fn input_matcher_matches_mouse_button_window_event() {
@@ -257,4 +435,56 @@ mod tests {
                 .matches(&context, &WindowEvent::CloseRequested)
         );
     }
+
+    #[test]
+    fn text_matcher_matches_ime_commit_but_not_preedit() {
+        let context = ChaosBindingContext::new();
+        let commit = WindowEvent::Ime(Ime::Commit("ő".to_string()));
+        let preedit = WindowEvent::Ime(Ime::Preedit("o".to_string(), Some((0, 1))));
+
+        assert!(ChaosInputEventMatcher::Text.matches(&context, &commit));
+        assert!(!ChaosInputEventMatcher::Text.matches(&context, &preedit));
+        assert!(ChaosInputEventMatcher::Ime.matches(&context, &preedit));
+    }
+
+    #[test]
+    fn repeated_matcher_only_matches_key_repeats() {
+        let repeat = ChaosInputEvent::KeyboardInput {
+            keycode: KeyCode::Backspace,
+            logical_key: winit::keyboard::Key::Named(winit::keyboard::NamedKey::Backspace),
+            text: None,
+            pressed: true,
+            repeat: true,
+        };
+        let button = ChaosButton::Keyboard(KeyCode::Backspace);
+
+        assert!(ChaosInputEventMatcher::Repeated(button).matches_input_event(&repeat));
+        assert!(!ChaosInputEventMatcher::Pressed(button).matches_input_event(&repeat));
+        assert!(
+            !ChaosInputEventMatcher::Repeated(button)
+                .matches_input_event(&ChaosInputEvent::key(KeyCode::Backspace, true))
+        );
+    }
+
+    #[test]
+    fn text_ignores_control_characters_and_releases() {
+        let text_event = |text: &str, pressed: bool| ChaosInputEvent::KeyboardInput {
+            keycode: KeyCode::KeyA,
+            logical_key: winit::keyboard::Key::Character(text.into()),
+            text: Some(text.to_string()),
+            pressed,
+            repeat: false,
+        };
+
+        assert_eq!(text_event("a", true).text(), Some("a"));
+        assert_eq!(text_event("a", false).text(), None);
+        assert_eq!(text_event("\u{8}", true).text(), None);
+        assert_eq!(
+            ChaosInputEvent::ModifiersChanged {
+                modifiers: ModifiersState::SHIFT
+            }
+            .text(),
+            None
+        );
+    }
 }
@@ -0,0 +1,102 @@
+use crate::math::{Vec3, matrix::Mat4};
+
+/// Converts a cursor position in window pixels to normalized device coordinates.
+/// Like the renderer's Vulkan viewport, NDC y points down: the top of the window is -1.
+pub fn cursor_to_ndc(cursor: (f64, f64), window_size: (u32, u32)) -> Option<(f32, f32)> {
+    if window_size.0 == 0 || window_size.1 == 0 {
+        return None;
+    }
+    Some((
+        (2.0 * cursor.0 / window_size.0 as f64 - 1.0) as f32,
+        (2.0 * cursor.1 / window_size.1 as f64 - 1.0) as f32,
+    ))
+}
+
+/// The world space ray through the cursor, as `(origin, direction)` with a normalized
+/// direction. `view` and `projection` are the matrices passed to the shaders.
+pub fn cursor_to_world_ray(
+    cursor: (f64, f64),
+    window_size: (u32, u32),
+    view: &Mat4,
+    projection: &Mat4,
+) -> Option<(Vec3, Vec3)> {
+    let (x, y) = cursor_to_ndc(cursor, window_size)?;
+    let inverse = (*view * *projection).inverse()?;
+
+    // Any two depths inside the clip volume lie on the ray, for both [-1, 1] and [0, 1]
+    // depth conventions.
+    let near = inverse.transform_point(&Vec3::new(x, y, 0.0))?;
+    let far = inverse.transform_point(&Vec3::new(x, y, 1.0))?;
+    let direction = far - near;
+    if direction.length_squared() <= f32::EPSILON {
+        return None;
+    }
+
+    Some((near, Vec3::normalized(&direction)))
+}
+
+/// The point on the plane `z = plane_z` under the cursor, e.g. to aim at the cursor in a
+/// game that renders on the `z = 0` plane. Returns `None` if the cursor ray is parallel
+/// to the plane.
+pub fn cursor_to_world(
+    cursor: (f64, f64),
+    window_size: (u32, u32),
+    view: &Mat4,
+    projection: &Mat4,
+    plane_z: f32,
+) -> Option<Vec3> {
+    let (origin, direction) = cursor_to_world_ray(cursor, window_size, view, projection)?;
+    if direction.z.abs() <= f32::EPSILON {
+        return None;
+    }
+
+    let t = (plane_z - origin.z) / direction.z;
+    Some(origin + direction * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera() -> (Mat4, Mat4) {
+        let view = Mat4::look_at(&Vec3::new(0.0, 0.0, 5.0), &Vec3::zero(), &Vec3::y_axis());
+        let projection = Mat4::perspective_projection(std::f32::consts::PI / 2.0, 2.0, 0.1, 100.0);
+        (view, projection)
+    }
+
+    fn assert_close(actual: Vec3, expected: Vec3) {
+        assert!(
+            Vec3::distance(&actual, &expected) < 1e-3,
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn window_center_maps_to_camera_target() {
+        let (view, projection) = camera();
+
+        let point = cursor_to_world((400.0, 200.0), (800, 400), &view, &projection, 0.0);
+
+        assert_close(point.unwrap(), Vec3::zero());
+    }
+
+    #[test]
+    fn window_edges_map_to_frustum_edges_on_plane() {
+        let (view, projection) = camera();
+
+        // A 90 degree vertical fov at distance 5 spans 10 units vertically, and the 2:1
+        // aspect ratio 20 units horizontally.
+        let right = cursor_to_world((800.0, 200.0), (800, 400), &view, &projection, 0.0);
+        let top = cursor_to_world((400.0, 0.0), (800, 400), &view, &projection, 0.0);
+
+        assert_close(right.unwrap(), Vec3::new(10.0, 0.0, 0.0));
+        assert_close(top.unwrap(), Vec3::new(0.0, -5.0, 0.0));
+    }
+
+    #[test]
+    fn empty_window_has_no_world_position() {
+        let (view, projection) = camera();
+
+        assert!(cursor_to_world((0.0, 0.0), (0, 0), &view, &projection, 0.0).is_none());
+    }
+}
@@ -1,5 +1,6 @@
-use winit::event::{MouseButton, WindowEvent};
-use winit::keyboard::{KeyCode, PhysicalKey};
+use winit::event::{DeviceEvent, Ime, MouseButton, TouchPhase, WindowEvent};
+use winit::keyboard::{Key, KeyCode, ModifiersState, NativeKey, PhysicalKey};
+use winit::window::{CursorGrabMode, CursorIcon};
 
 use chaos_communicator::message::{ChaosMessage, ChaosMessageBuilder};
 
@@ -7,13 +8,37 @@ use crate::device::bindings::{ChaosButton, ChaosDeviceEventMatcher, ChaosInputEv
 
 pub type ChaosKeyCode = KeyCode;
 
+pub type ChaosLogicalKey = Key;
+
+pub type ChaosModifiers = ModifiersState;
+
+pub type ChaosImeEvent = Ime;
+
 pub type ChaosMouseButton = MouseButton;
 
+pub type ChaosTouchPhase = TouchPhase;
+
+pub type ChaosCursorGrabMode = CursorGrabMode;
+
+pub type ChaosCursorIcon = CursorIcon;
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum ChaosInputEvent {
     KeyboardInput {
         keycode: ChaosKeyCode,
+        // The key after applying the keyboard layout (and modifiers), e.g. `Key::Character("a")`.
+        logical_key: ChaosLogicalKey,
+        // Text produced by the key press, if any.
+        text: Option<String>,
         pressed: bool,
+        // OS-level key repeat of a key that is already held down.
+        repeat: bool,
+    },
+    ModifiersChanged {
+        modifiers: ChaosModifiers,
+    },
+    Ime {
+        event: ChaosImeEvent,
     },
     MousePosition {
         x: f64,
@@ -27,6 +52,29 @@ pub enum ChaosInputEvent {
         delta_x: f32,
         delta_y: f32,
     },
+    // Raw, unaccelerated mouse movement. Keeps arriving while the cursor is grabbed or at
+    // the edge of the window, unlike `MousePosition`.
+    MouseMotion {
+        delta_x: f64,
+        delta_y: f64,
+    },
+    Touch {
+        // Identifies the finger for the duration of the touch, from `Started` until `Ended`.
+        id: u64,
+        phase: ChaosTouchPhase,
+        x: f64,
+        y: f64,
+    },
+    // Trackpad pinch; positive deltas magnify.
+    PinchGesture {
+        delta: f64,
+        phase: ChaosTouchPhase,
+    },
+    // Trackpad rotation in degrees, counterclockwise positive.
+    RotationGesture {
+        delta: f32,
+        phase: ChaosTouchPhase,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -50,9 +98,16 @@ impl TryFrom<&WindowEvent> for ChaosInputEvent {
                 PhysicalKey::Unidentified(_) => Err(()),
                 PhysicalKey::Code(key) => Ok(ChaosInputEvent::KeyboardInput {
                     keycode: key,
+                    logical_key: event.logical_key.clone(),
+                    text: event.text.as_ref().map(|text| text.to_string()),
                     pressed: event.state == winit::event::ElementState::Pressed,
+                    repeat: event.repeat,
                 }),
             },
+            WindowEvent::ModifiersChanged(modifiers) => Ok(ChaosInputEvent::ModifiersChanged {
+                modifiers: modifiers.state(),
+            }),
+            WindowEvent::Ime(ime) => Ok(ChaosInputEvent::Ime { event: ime.clone() }),
             WindowEvent::MouseInput { button, state, .. } => match button {
                 MouseButton::Left | MouseButton::Right | MouseButton::Middle => {
                     Ok(ChaosInputEvent::MouseButton {
@@ -80,6 +135,36 @@ impl TryFrom<&WindowEvent> for ChaosInputEvent {
                     })
                 }
             },
+            WindowEvent::Touch(touch) => Ok(ChaosInputEvent::Touch {
+                id: touch.id,
+                phase: touch.phase,
+                x: touch.location.x,
+                y: touch.location.y,
+            }),
+            WindowEvent::PinchGesture { delta, phase, .. } => Ok(ChaosInputEvent::PinchGesture {
+                delta: *delta,
+                phase: *phase,
+            }),
+            WindowEvent::RotationGesture { delta, phase, .. } => {
+                Ok(ChaosInputEvent::RotationGesture {
+                    delta: *delta,
+                    phase: *phase,
+                })
+            }
+            _ => Err(()),
+        }
+    }
+}
+
+// Device events are not tied to a window; only relative mouse motion is of interest.
+impl TryFrom<&DeviceEvent> for ChaosInputEvent {
+    type Error = ();
+    fn try_from(event: &DeviceEvent) -> Result<Self, Self::Error> {
+        match event {
+            DeviceEvent::MouseMotion { delta } => Ok(ChaosInputEvent::MouseMotion {
+                delta_x: delta.0,
+                delta_y: delta.1,
+            }),
             _ => Err(()),
         }
     }
@@ -107,15 +192,97 @@ impl TryFrom<&WindowEvent> for ChaosDeviceEvent {
 }
 
 impl ChaosInputEvent {
+    /// A `KeyboardInput` event for a physical key without a logical key or text, e.g. for
+    /// synthesized input.
+    pub fn key(keycode: ChaosKeyCode, pressed: bool) -> Self {
+        ChaosInputEvent::KeyboardInput {
+            keycode,
+            logical_key: Key::Unidentified(NativeKey::Unidentified),
+            text: None,
+            pressed,
+            repeat: false,
+        }
+    }
+
+    /// The keyboard key or mouse button this event is about, if any.
+    pub fn button(&self) -> Option<ChaosButton> {
+        match self {
+            ChaosInputEvent::KeyboardInput { keycode, .. } => Some(ChaosButton::Keyboard(*keycode)),
+            ChaosInputEvent::MouseButton { button, .. } => Some(ChaosButton::Mouse(*button)),
+            _ => None,
+        }
+    }
+
+    /// Whether this is a press (`Some(true)`) or release (`Some(false)`) of a button.
+    /// Key repeats count as neither.
+    pub fn button_pressed(&self) -> Option<bool> {
+        match self {
+            ChaosInputEvent::KeyboardInput {
+                pressed,
+                repeat: false,
+                ..
+            }
+            | ChaosInputEvent::MouseButton { pressed, .. } => Some(*pressed),
+            _ => None,
+        }
+    }
+
+    /// The text entered by this event: the text of a key press (including repeats) or a
+    /// committed IME composition. Text consisting only of control characters (e.g. the
+    /// `"\u{8}"` some platforms report for Backspace) is not considered text input.
+    pub fn text(&self) -> Option<&str> {
+        let text = match self {
+            ChaosInputEvent::KeyboardInput {
+                text: Some(text),
+                pressed: true,
+                ..
+            } => text.as_str(),
+            ChaosInputEvent::Ime {
+                event: Ime::Commit(text),
+            } => text.as_str(),
+            _ => return None,
+        };
+
+        if text.chars().any(|c| !c.is_control()) {
+            Some(text)
+        } else {
+            None
+        }
+    }
+
     /// Extend `builder` with the parameters relevant to this input event, so downstream
     /// consumers of a signal message can inspect e.g. cursor position or wheel delta
     /// without also having to unpack the `input_event` param.
     pub(crate) fn enrich_message(&self, builder: ChaosMessageBuilder) -> ChaosMessageBuilder {
         let builder = builder.with_param("input_event", self.clone());
         match self {
-            ChaosInputEvent::KeyboardInput { keycode, pressed } => builder
-                .with_param("keycode", *keycode)
-                .with_param("pressed", *pressed),
+            ChaosInputEvent::KeyboardInput {
+                keycode,
+                logical_key,
+                text,
+                pressed,
+                repeat,
+            } => {
+                let builder = builder
+                    .with_param("keycode", *keycode)
+                    .with_param("logical_key", logical_key.clone())
+                    .with_param("pressed", *pressed)
+                    .with_param("repeat", *repeat);
+                match text {
+                    Some(text) => builder.with_param("text", text.clone()),
+                    None => builder,
+                }
+            }
+            ChaosInputEvent::ModifiersChanged { modifiers } => {
+                builder.with_param("modifiers", *modifiers)
+            }
+            ChaosInputEvent::Ime { event } => match event {
+                Ime::Preedit(text, cursor) => builder
+                    .with_param("text", text.clone())
+                    .with_param("cursor", *cursor),
+                Ime::Commit(text) => builder.with_param("text", text.clone()),
+                Ime::Enabled | Ime::Disabled => builder,
+            },
             ChaosInputEvent::MousePosition { x, y } => {
                 builder.with_param("x", *x).with_param("y", *y)
             }
@@ -125,6 +292,20 @@ impl ChaosInputEvent {
             ChaosInputEvent::MouseWheel { delta_x, delta_y } => builder
                 .with_param("delta_x", *delta_x)
                 .with_param("delta_y", *delta_y),
+            ChaosInputEvent::MouseMotion { delta_x, delta_y } => builder
+                .with_param("delta_x", *delta_x)
+                .with_param("delta_y", *delta_y),
+            ChaosInputEvent::Touch { id, phase, x, y } => builder
+                .with_param("touch_id", *id)
+                .with_param("phase", *phase)
+                .with_param("x", *x)
+                .with_param("y", *y),
+            ChaosInputEvent::PinchGesture { delta, phase } => builder
+                .with_param("delta", *delta)
+                .with_param("phase", *phase),
+            ChaosInputEvent::RotationGesture { delta, phase } => builder
+                .with_param("delta", *delta)
+                .with_param("phase", *phase),
         }
     }
 }
@@ -152,14 +333,27 @@ impl From<ChaosInputEvent> for ChaosMessage {
     fn from(event: ChaosInputEvent) -> Self {
         let builder = event.enrich_message(ChaosMessageBuilder::new());
         match &event {
-            ChaosInputEvent::KeyboardInput { keycode, pressed } => {
-                let event_key = if *pressed {
-                    ChaosInputEventMatcher::Pressed(ChaosButton::Keyboard(*keycode))
-                } else {
-                    ChaosInputEventMatcher::Released(ChaosButton::Keyboard(*keycode))
+            ChaosInputEvent::KeyboardInput {
+                keycode,
+                pressed,
+                repeat,
+                ..
+            } => {
+                let event_key = match (*pressed, *repeat) {
+                    (true, false) => {
+                        ChaosInputEventMatcher::Pressed(ChaosButton::Keyboard(*keycode))
+                    }
+                    (true, true) => {
+                        ChaosInputEventMatcher::Repeated(ChaosButton::Keyboard(*keycode))
+                    }
+                    (false, _) => ChaosInputEventMatcher::Released(ChaosButton::Keyboard(*keycode)),
                 };
                 builder.build_for_event(event_key)
             }
+            ChaosInputEvent::ModifiersChanged { .. } => {
+                builder.build_for_event(ChaosInputEventMatcher::ModifiersChanged)
+            }
+            ChaosInputEvent::Ime { .. } => builder.build_for_event(ChaosInputEventMatcher::Ime),
             ChaosInputEvent::MousePosition { .. } => {
                 builder.build_for_event(ChaosInputEventMatcher::MouseMoved)
             }
@@ -174,6 +368,16 @@ impl From<ChaosInputEvent> for ChaosMessage {
             ChaosInputEvent::MouseWheel { .. } => {
                 builder.build_for_event(ChaosInputEventMatcher::MouseWheel)
             }
+            ChaosInputEvent::MouseMotion { .. } => {
+                builder.build_for_event(ChaosInputEventMatcher::MouseMotion)
+            }
+            ChaosInputEvent::Touch { .. } => builder.build_for_event(ChaosInputEventMatcher::Touch),
+            ChaosInputEvent::PinchGesture { .. } => {
+                builder.build_for_event(ChaosInputEventMatcher::PinchGesture)
+            }
+            ChaosInputEvent::RotationGesture { .. } => {
+                builder.build_for_event(ChaosInputEventMatcher::RotationGesture)
+            }
         }
     }
 }
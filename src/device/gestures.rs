@@ -0,0 +1,642 @@
+use std::{
+    collections::HashMap,
+    f64::consts::{PI, TAU},
+    time::{Duration, Instant},
+};
+
+use chaos_communicator::message::{ChaosMessage, ChaosMessageBuilder};
+
+use crate::device::{bindings::ChaosGestureMatcher, events::ChaosTouchPhase};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ChaosSwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// A gesture recognized from touch input (or from the platform's trackpad gestures).
+/// Positions are in window pixels, angles in radians with counterclockwise positive.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChaosGesture {
+    Tap {
+        x: f64,
+        y: f64,
+    },
+    DoubleTap {
+        x: f64,
+        y: f64,
+    },
+    LongPress {
+        x: f64,
+        y: f64,
+        duration: Duration,
+    },
+    Swipe {
+        direction: ChaosSwipeDirection,
+        // Where the swipe started.
+        x: f64,
+        y: f64,
+        distance: f64,
+        duration: Duration,
+    },
+    Pinch {
+        // Scale relative to the start of the pinch, and its change since the last update.
+        scale: f64,
+        delta: f64,
+        // Midpoint between the fingers; unknown for trackpad pinches.
+        center: Option<(f64, f64)>,
+    },
+    Rotation {
+        // Angle relative to the start of the rotation, and its change since the last update.
+        angle: f64,
+        delta: f64,
+        center: Option<(f64, f64)>,
+    },
+}
+
+/// Thresholds used by [`ChaosGestureRecognizer`]. Distances are in window pixels.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChaosGestureConfig {
+    pub tap_max_duration: Duration,
+    pub tap_max_distance: f64,
+    pub double_tap_max_interval: Duration,
+    pub double_tap_max_distance: f64,
+    pub long_press_duration: Duration,
+    pub swipe_min_distance: f64,
+    pub swipe_max_duration: Duration,
+    // Minimum change in finger distance (as a scale factor) before a pinch is recognized.
+    pub pinch_min_scale: f64,
+    // Minimum rotation in radians before a two-finger rotation is recognized.
+    pub rotation_min_angle: f64,
+}
+
+impl Default for ChaosGestureConfig {
+    fn default() -> Self {
+        Self {
+            tap_max_duration: Duration::from_millis(250),
+            tap_max_distance: 10.0,
+            double_tap_max_interval: Duration::from_millis(300),
+            double_tap_max_distance: 30.0,
+            long_press_duration: Duration::from_millis(500),
+            swipe_min_distance: 50.0,
+            swipe_max_duration: Duration::from_millis(500),
+            pinch_min_scale: 0.05,
+            rotation_min_angle: 0.1,
+        }
+    }
+}
+
+/// A finger currently touching the screen.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChaosTouchPoint {
+    pub start: (f64, f64),
+    pub position: (f64, f64),
+    pub started_at: Instant,
+}
+
+impl ChaosTouchPoint {
+    pub(crate) fn new(position: (f64, f64), now: Instant) -> Self {
+        Self {
+            start: position,
+            position,
+            started_at: now,
+        }
+    }
+
+    pub fn distance_moved(&self) -> f64 {
+        distance(self.start, self.position)
+    }
+}
+
+struct TwoFingerState {
+    ids: (u64, u64),
+    start_distance: f64,
+    start_angle: f64,
+    last_scale: f64,
+    last_angle: f64,
+    pinching: bool,
+    rotating: bool,
+}
+
+/// Turns the touch points tracked by `ChaosBindingContext` into [`ChaosGesture`]s.
+///
+/// Single-finger gestures (tap, double tap, long press, swipe) are only recognized when
+/// no other finger touched the screen since the first one went down, so lifting the
+/// fingers after a pinch doesn't also produce a tap.
+pub struct ChaosGestureRecognizer {
+    config: ChaosGestureConfig,
+    last_tap: Option<(Instant, (f64, f64))>,
+    two_finger: Option<TwoFingerState>,
+    multi_touch: bool,
+    long_pressed: Option<u64>,
+    trackpad_scale: f64,
+    trackpad_angle: f64,
+}
+
+impl Default for ChaosGestureRecognizer {
+    fn default() -> Self {
+        Self::new(ChaosGestureConfig::default())
+    }
+}
+
+impl ChaosGestureRecognizer {
+    pub fn new(config: ChaosGestureConfig) -> Self {
+        Self {
+            config,
+            last_tap: None,
+            two_finger: None,
+            multi_touch: false,
+            long_pressed: None,
+            trackpad_scale: 1.0,
+            trackpad_angle: 0.0,
+        }
+    }
+
+    pub fn config(&self) -> &ChaosGestureConfig {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: ChaosGestureConfig) {
+        self.config = config;
+    }
+
+    /// Feed a touch that was just started, moved, ended or cancelled. `touches` holds the
+    /// active touch points after the event was applied; `point` is the touch the event is
+    /// about (for ended/cancelled touches it is no longer in `touches`).
+    pub(crate) fn touch(
+        &mut self,
+        id: u64,
+        phase: ChaosTouchPhase,
+        point: &ChaosTouchPoint,
+        touches: &HashMap<u64, ChaosTouchPoint>,
+        now: Instant,
+    ) -> Vec<ChaosGesture> {
+        match phase {
+            ChaosTouchPhase::Started => {
+                self.touch_started(touches);
+                Vec::new()
+            }
+            ChaosTouchPhase::Moved => self.touches_moved(touches),
+            ChaosTouchPhase::Ended => self.touch_ended(id, point, touches, now),
+            ChaosTouchPhase::Cancelled => {
+                self.touch_lifted(id, touches);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Time based recognition (long press), called on every update even without input.
+    pub(crate) fn poll(
+        &mut self,
+        touches: &HashMap<u64, ChaosTouchPoint>,
+        now: Instant,
+    ) -> Vec<ChaosGesture> {
+        if self.multi_touch || touches.len() != 1 {
+            return Vec::new();
+        }
+        let Some((id, point)) = touches.iter().next() else {
+            return Vec::new();
+        };
+        if self.long_pressed == Some(*id) {
+            return Vec::new();
+        }
+
+        let duration = now.duration_since(point.started_at);
+        if duration < self.config.long_press_duration
+            || point.distance_moved() > self.config.tap_max_distance
+        {
+            return Vec::new();
+        }
+
+        self.long_pressed = Some(*id);
+        vec![ChaosGesture::LongPress {
+            x: point.position.0,
+            y: point.position.1,
+            duration,
+        }]
+    }
+
+    /// Platform pinch (trackpad magnification). `delta` is the change in scale.
+    pub(crate) fn trackpad_pinch(
+        &mut self,
+        delta: f64,
+        phase: ChaosTouchPhase,
+    ) -> Vec<ChaosGesture> {
+        if phase == ChaosTouchPhase::Started {
+            self.trackpad_scale = 1.0;
+        }
+        if delta == 0.0 || delta.is_nan() {
+            return Vec::new();
+        }
+        self.trackpad_scale += delta;
+        vec![ChaosGesture::Pinch {
+            scale: self.trackpad_scale,
+            delta,
+            center: None,
+        }]
+    }
+
+    /// Platform rotation (trackpad). `delta` is in degrees, counterclockwise positive.
+    pub(crate) fn trackpad_rotation(
+        &mut self,
+        delta: f32,
+        phase: ChaosTouchPhase,
+    ) -> Vec<ChaosGesture> {
+        if phase == ChaosTouchPhase::Started {
+            self.trackpad_angle = 0.0;
+        }
+        if delta == 0.0 || delta.is_nan() {
+            return Vec::new();
+        }
+        let delta = (delta as f64).to_radians();
+        self.trackpad_angle += delta;
+        vec![ChaosGesture::Rotation {
+            angle: self.trackpad_angle,
+            delta,
+            center: None,
+        }]
+    }
+
+    fn touch_started(&mut self, touches: &HashMap<u64, ChaosTouchPoint>) {
+        if touches.len() < 2 {
+            return;
+        }
+        self.multi_touch = true;
+        if self.two_finger.is_some() {
+            return;
+        }
+
+        let mut ids: Vec<u64> = touches.keys().copied().collect();
+        ids.sort_unstable();
+        let (a, b) = (&touches[&ids[0]], &touches[&ids[1]]);
+        self.two_finger = Some(TwoFingerState {
+            ids: (ids[0], ids[1]),
+            start_distance: distance(a.position, b.position),
+            start_angle: angle(a.position, b.position),
+            last_scale: 1.0,
+            last_angle: 0.0,
+            pinching: false,
+            rotating: false,
+        });
+    }
+
+    fn touches_moved(&mut self, touches: &HashMap<u64, ChaosTouchPoint>) -> Vec<ChaosGesture> {
+        let Some(state) = self.two_finger.as_mut() else {
+            return Vec::new();
+        };
+        let (Some(a), Some(b)) = (touches.get(&state.ids.0), touches.get(&state.ids.1)) else {
+            return Vec::new();
+        };
+
+        let center = Some((
+            (a.position.0 + b.position.0) / 2.0,
+            (a.position.1 + b.position.1) / 2.0,
+        ));
+        let mut gestures = Vec::new();
+
+        if state.start_distance > 0.0 {
+            let scale = distance(a.position, b.position) / state.start_distance;
+            state.pinching |= (scale - 1.0).abs() >= self.config.pinch_min_scale;
+            if state.pinching && scale != state.last_scale {
+                gestures.push(ChaosGesture::Pinch {
+                    scale,
+                    delta: scale - state.last_scale,
+                    center,
+                });
+                state.last_scale = scale;
+            }
+        }
+
+        // Window coordinates grow downwards, so the on-screen counterclockwise angle is
+        // the negated atan2 angle.
+        let rotation = wrap_angle(state.start_angle - angle(a.position, b.position));
+        state.rotating |= rotation.abs() >= self.config.rotation_min_angle;
+        if state.rotating && rotation != state.last_angle {
+            gestures.push(ChaosGesture::Rotation {
+                angle: rotation,
+                delta: wrap_angle(rotation - state.last_angle),
+                center,
+            });
+            state.last_angle = rotation;
+        }
+
+        gestures
+    }
+
+    fn touch_ended(
+        &mut self,
+        id: u64,
+        point: &ChaosTouchPoint,
+        touches: &HashMap<u64, ChaosTouchPoint>,
+        now: Instant,
+    ) -> Vec<ChaosGesture> {
+        let single_finger = !self.multi_touch && touches.is_empty();
+        let long_pressed = self.long_pressed == Some(id);
+        self.touch_lifted(id, touches);
+        if !single_finger || long_pressed {
+            return Vec::new();
+        }
+
+        let duration = now.duration_since(point.started_at);
+        let distance = point.distance_moved();
+        let (x, y) = point.position;
+
+        if distance >= self.config.swipe_min_distance && duration <= self.config.swipe_max_duration
+        {
+            let dx = point.position.0 - point.start.0;
+            let dy = point.position.1 - point.start.1;
+            let direction = if dx.abs() >= dy.abs() {
+                if dx > 0.0 {
+                    ChaosSwipeDirection::Right
+                } else {
+                    ChaosSwipeDirection::Left
+                }
+            } else if dy > 0.0 {
+                ChaosSwipeDirection::Down
+            } else {
+                ChaosSwipeDirection::Up
+            };
+            return vec![ChaosGesture::Swipe {
+                direction,
+                x: point.start.0,
+                y: point.start.1,
+                distance,
+                duration,
+            }];
+        }
+
+        if distance > self.config.tap_max_distance || duration > self.config.tap_max_duration {
+            return Vec::new();
+        }
+
+        let mut gestures = vec![ChaosGesture::Tap { x, y }];
+        let is_double_tap = self.last_tap.is_some_and(|(tapped_at, position)| {
+            now.duration_since(tapped_at) <= self.config.double_tap_max_interval
+                && self::distance(position, (x, y)) <= self.config.double_tap_max_distance
+        });
+        if is_double_tap {
+            gestures.push(ChaosGesture::DoubleTap { x, y });
+            self.last_tap = None;
+        } else {
+            self.last_tap = Some((now, (x, y)));
+        }
+        gestures
+    }
+
+    fn touch_lifted(&mut self, id: u64, touches: &HashMap<u64, ChaosTouchPoint>) {
+        if self
+            .two_finger
+            .as_ref()
+            .is_some_and(|state| state.ids.0 == id || state.ids.1 == id)
+        {
+            self.two_finger = None;
+        }
+        if self.long_pressed == Some(id) {
+            self.long_pressed = None;
+        }
+        if touches.is_empty() {
+            self.multi_touch = false;
+        }
+    }
+}
+
+impl ChaosGesture {
+    pub fn matcher(&self) -> ChaosGestureMatcher {
+        match self {
+            ChaosGesture::Tap { .. } => ChaosGestureMatcher::Tap,
+            ChaosGesture::DoubleTap { .. } => ChaosGestureMatcher::DoubleTap,
+            ChaosGesture::LongPress { .. } => ChaosGestureMatcher::LongPress,
+            ChaosGesture::Swipe { direction, .. } => ChaosGestureMatcher::Swipe(*direction),
+            ChaosGesture::Pinch { .. } => ChaosGestureMatcher::Pinch,
+            ChaosGesture::Rotation { .. } => ChaosGestureMatcher::Rotation,
+        }
+    }
+
+    /// Extend `builder` with the parameters of this gesture (e.g. `x`/`y` for a tap,
+    /// `scale`/`delta` for a pinch).
+    pub(crate) fn enrich_message(&self, builder: ChaosMessageBuilder) -> ChaosMessageBuilder {
+        let builder = builder.with_param("gesture", self.clone());
+        let with_center = |builder: ChaosMessageBuilder, center: &Option<(f64, f64)>| match center {
+            Some((x, y)) => builder.with_param("x", *x).with_param("y", *y),
+            None => builder,
+        };
+        match self {
+            ChaosGesture::Tap { x, y } | ChaosGesture::DoubleTap { x, y } => {
+                builder.with_param("x", *x).with_param("y", *y)
+            }
+            ChaosGesture::LongPress { x, y, duration } => builder
+                .with_param("x", *x)
+                .with_param("y", *y)
+                .with_param("duration", *duration),
+            ChaosGesture::Swipe {
+                direction,
+                x,
+                y,
+                distance,
+                duration,
+            } => builder
+                .with_param("direction", *direction)
+                .with_param("x", *x)
+                .with_param("y", *y)
+                .with_param("distance", *distance)
+                .with_param("duration", *duration),
+            ChaosGesture::Pinch {
+                scale,
+                delta,
+                center,
+            } => with_center(
+                builder
+                    .with_param("scale", *scale)
+                    .with_param("delta", *delta),
+                center,
+            ),
+            ChaosGesture::Rotation {
+                angle,
+                delta,
+                center,
+            } => with_center(
+                builder
+                    .with_param("angle", *angle)
+                    .with_param("delta", *delta),
+                center,
+            ),
+        }
+    }
+}
+
+impl From<ChaosGesture> for ChaosMessage {
+    fn from(gesture: ChaosGesture) -> Self {
+        let key = gesture.matcher();
+        gesture
+            .enrich_message(ChaosMessageBuilder::new())
+            .build_for_event(key)
+    }
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
+
+fn angle(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (b.1 - a.1).atan2(b.0 - a.0)
+}
+
+// Wraps an angle into [-PI, PI).
+fn wrap_angle(angle: f64) -> f64 {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TouchScript {
+        recognizer: ChaosGestureRecognizer,
+        touches: HashMap<u64, ChaosTouchPoint>,
+        start: Instant,
+    }
+
+    impl TouchScript {
+        fn new() -> Self {
+            Self {
+                recognizer: ChaosGestureRecognizer::default(),
+                touches: HashMap::new(),
+                start: Instant::now(),
+            }
+        }
+
+        fn at(&self, millis: u64) -> Instant {
+            self.start + Duration::from_millis(millis)
+        }
+
+        fn touch(
+            &mut self,
+            id: u64,
+            phase: ChaosTouchPhase,
+            position: (f64, f64),
+            millis: u64,
+        ) -> Vec<ChaosGesture> {
+            let now = self.at(millis);
+            let point = match phase {
+                ChaosTouchPhase::Started => {
+                    self.touches.insert(id, ChaosTouchPoint::new(position, now));
+                    self.touches[&id].clone()
+                }
+                ChaosTouchPhase::Moved => {
+                    let point = self.touches.get_mut(&id).unwrap();
+                    point.position = position;
+                    point.clone()
+                }
+                ChaosTouchPhase::Ended | ChaosTouchPhase::Cancelled => {
+                    let mut point = self.touches.remove(&id).unwrap();
+                    point.position = position;
+                    point
+                }
+            };
+            self.recognizer.touch(id, phase, &point, &self.touches, now)
+        }
+    }
+
+    #[test]
+    fn quick_touch_is_a_tap_and_second_quick_touch_a_double_tap() {
+        let mut script = TouchScript::new();
+
+        script.touch(0, ChaosTouchPhase::Started, (100.0, 100.0), 0);
+        let first = script.touch(0, ChaosTouchPhase::Ended, (101.0, 100.0), 100);
+        script.touch(1, ChaosTouchPhase::Started, (102.0, 101.0), 200);
+        let second = script.touch(1, ChaosTouchPhase::Ended, (102.0, 101.0), 280);
+
+        assert_eq!(first, vec![ChaosGesture::Tap { x: 101.0, y: 100.0 }]);
+        assert_eq!(
+            second,
+            vec![
+                ChaosGesture::Tap { x: 102.0, y: 101.0 },
+                ChaosGesture::DoubleTap { x: 102.0, y: 101.0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn held_touch_is_a_long_press_once_and_not_a_tap() {
+        let mut script = TouchScript::new();
+
+        script.touch(0, ChaosTouchPhase::Started, (50.0, 50.0), 0);
+        let early = script.recognizer.poll(&script.touches, script.at(100));
+        let long_press = script.recognizer.poll(&script.touches, script.at(600));
+        let repeated = script.recognizer.poll(&script.touches, script.at(700));
+        let released = script.touch(0, ChaosTouchPhase::Ended, (50.0, 50.0), 800);
+
+        assert!(early.is_empty());
+        assert_eq!(
+            long_press,
+            vec![ChaosGesture::LongPress {
+                x: 50.0,
+                y: 50.0,
+                duration: Duration::from_millis(600),
+            }]
+        );
+        assert!(repeated.is_empty());
+        assert!(released.is_empty());
+    }
+
+    #[test]
+    fn fast_drag_is_a_swipe_in_its_dominant_direction() {
+        let mut script = TouchScript::new();
+
+        script.touch(0, ChaosTouchPhase::Started, (200.0, 200.0), 0);
+        script.touch(0, ChaosTouchPhase::Moved, (190.0, 150.0), 50);
+        let gestures = script.touch(0, ChaosTouchPhase::Ended, (180.0, 80.0), 150);
+
+        assert!(matches!(
+            gestures.as_slice(),
+            [ChaosGesture::Swipe {
+                direction: ChaosSwipeDirection::Up,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn spreading_two_fingers_is_a_pinch_and_not_a_tap() {
+        let mut script = TouchScript::new();
+
+        script.touch(0, ChaosTouchPhase::Started, (100.0, 100.0), 0);
+        script.touch(1, ChaosTouchPhase::Started, (200.0, 100.0), 10);
+        let small = script.touch(1, ChaosTouchPhase::Moved, (202.0, 100.0), 20);
+        let spread = script.touch(1, ChaosTouchPhase::Moved, (300.0, 100.0), 30);
+        script.touch(1, ChaosTouchPhase::Ended, (300.0, 100.0), 40);
+        let lifted = script.touch(0, ChaosTouchPhase::Ended, (100.0, 100.0), 50);
+
+        assert!(small.is_empty());
+        assert_eq!(
+            spread,
+            vec![ChaosGesture::Pinch {
+                scale: 2.0,
+                delta: 1.0,
+                center: Some((200.0, 100.0)),
+            }]
+        );
+        assert!(lifted.is_empty());
+    }
+
+    #[test]
+    fn turning_two_fingers_is_a_counterclockwise_rotation() {
+        let mut script = TouchScript::new();
+
+        script.touch(0, ChaosTouchPhase::Started, (100.0, 100.0), 0);
+        script.touch(1, ChaosTouchPhase::Started, (200.0, 100.0), 10);
+        // Second finger moves up the screen around the first: counterclockwise on screen.
+        let gestures = script.touch(1, ChaosTouchPhase::Moved, (100.0, 0.0), 20);
+
+        let rotation = gestures
+            .iter()
+            .find_map(|gesture| match gesture {
+                ChaosGesture::Rotation { angle, .. } => Some(*angle),
+                _ => None,
+            })
+            .expect("rotation gesture");
+        assert!((rotation - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,151 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+use crate::{device::bindings::ChaosButton, triggers::trigger_event_key::TriggerEventKey};
+
+/// Pollable snapshot of the input, kept up to date by the engine as a world resource.
+///
+/// ```rust
+/// use chaos_engine::{device::input_state::InputState, ecs::world::ChaosWorld};
+/// use winit::keyboard::KeyCode;
+///
+/// let mut world = ChaosWorld::new();
+/// world.insert_resource(InputState::default());
+///
+/// let input = world.get_resource::<InputState>().unwrap();
+/// assert!(!input.just_pressed(KeyCode::Space));
+/// ```
+///
+/// "Just" pressed/released buttons, mouse motion, the wheel delta and fired actions cover
+/// everything that happened since the previous `ChaosWorld::update`; they are cleared at
+/// the end of every world update.
+#[derive(Clone, Debug, Default)]
+pub struct InputState {
+    pressed: HashSet<ChaosButton>,
+    held_since: HashMap<ChaosButton, Instant>,
+    just_pressed: HashSet<ChaosButton>,
+    just_released: HashSet<ChaosButton>,
+    cursor_position: Option<(f64, f64)>,
+    mouse_motion: (f64, f64),
+    wheel_delta: (f32, f32),
+    // Current value of every bound signal, see `InputState::action_value`.
+    action_values: HashMap<TriggerEventKey, f32>,
+    fired_actions: HashSet<TriggerEventKey>,
+    // When the state was last written by the `DeviceEventSystem`.
+    updated_at: Option<Instant>,
+}
+
+impl InputState {
+    pub fn is_pressed(&self, button: impl Into<ChaosButton>) -> bool {
+        self.pressed.contains(&button.into())
+    }
+
+    pub fn just_pressed(&self, button: impl Into<ChaosButton>) -> bool {
+        self.just_pressed.contains(&button.into())
+    }
+
+    pub fn just_released(&self, button: impl Into<ChaosButton>) -> bool {
+        self.just_released.contains(&button.into())
+    }
+
+    /// How long `button` has been held, as of the last input update. `None` if it is not
+    /// pressed.
+    pub fn held_duration(&self, button: impl Into<ChaosButton>) -> Option<Duration> {
+        let pressed_at = self.held_since.get(&button.into())?;
+        Some(
+            self.updated_at
+                .map(|now| now.duration_since(*pressed_at))
+                .unwrap_or_default(),
+        )
+    }
+
+    pub fn pressed_buttons(&self) -> impl Iterator<Item = &ChaosButton> {
+        self.pressed.iter()
+    }
+
+    /// Cursor position inside the window in physical pixels, `None` before the cursor
+    /// first moved over the window.
+    pub fn cursor_position(&self) -> Option<(f64, f64)> {
+        self.cursor_position
+    }
+
+    /// Relative mouse movement this frame, also while the cursor is grabbed.
+    pub fn mouse_motion(&self) -> (f64, f64) {
+        self.mouse_motion
+    }
+
+    /// Scrolled amount this frame, `(horizontal, vertical)`.
+    pub fn wheel_delta(&self) -> (f32, f32) {
+        self.wheel_delta
+    }
+
+    /// Value of the action bound to `signal` with `DeviceEventSystem::bind`: `1.0` while
+    /// the buttons of a button binding are held, the vertical wheel delta for a wheel
+    /// binding, and `1.0` in the frame other bindings fired. With several bindings for the
+    /// same signal the largest value wins. `0.0` for unknown signals.
+    pub fn action_value<T: Hash>(&self, signal: &T) -> f32 {
+        self.action_values
+            .get(&TriggerEventKey::new(signal))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    pub fn is_action_active<T: Hash>(&self, signal: &T) -> bool {
+        self.action_value(signal) != 0.0
+    }
+
+    /// Whether a binding for `signal` sent its signal this frame.
+    pub fn action_fired<T: Hash>(&self, signal: &T) -> bool {
+        self.fired_actions.contains(&TriggerEventKey::new(signal))
+    }
+
+    // Called by `DeviceEventSystem::write_input_state`. Level state (held buttons, cursor,
+    // action values) is replaced, while frame edges accumulate until `end_frame`.
+    pub(crate) fn update(
+        &mut self,
+        pressed: &HashSet<ChaosButton>,
+        held_since: &HashMap<ChaosButton, Instant>,
+        cursor_position: Option<(f64, f64)>,
+        frame: &mut ChaosFrameInput,
+        action_values: HashMap<TriggerEventKey, f32>,
+        now: Instant,
+    ) {
+        self.pressed.clone_from(pressed);
+        self.held_since.clone_from(held_since);
+        self.cursor_position = cursor_position;
+        self.action_values = action_values;
+        self.updated_at = Some(now);
+
+        self.just_pressed.extend(frame.just_pressed.drain());
+        self.just_released.extend(frame.just_released.drain());
+        self.fired_actions.extend(frame.fired_actions.drain());
+        self.mouse_motion.0 += frame.mouse_motion.0;
+        self.mouse_motion.1 += frame.mouse_motion.1;
+        self.wheel_delta.0 += frame.wheel_delta.0;
+        self.wheel_delta.1 += frame.wheel_delta.1;
+        frame.mouse_motion = (0.0, 0.0);
+        frame.wheel_delta = (0.0, 0.0);
+    }
+
+    pub(crate) fn end_frame(&mut self) {
+        self.just_pressed.clear();
+        self.just_released.clear();
+        self.fired_actions.clear();
+        self.mouse_motion = (0.0, 0.0);
+        self.wheel_delta = (0.0, 0.0);
+    }
+}
+
+/// Input edges collected by the `DeviceEventSystem` between two writes of the
+/// [`InputState`].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ChaosFrameInput {
+    pub(crate) just_pressed: HashSet<ChaosButton>,
+    pub(crate) just_released: HashSet<ChaosButton>,
+    pub(crate) mouse_motion: (f64, f64),
+    pub(crate) wheel_delta: (f32, f32),
+    pub(crate) fired_actions: HashSet<TriggerEventKey>,
+}
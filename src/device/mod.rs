@@ -7,5 +7,8 @@ The input strategy is this:
  */
 
 pub mod bindings;
+pub mod cursor;
 pub mod events;
+pub mod gestures;
+pub mod input_state;
 pub mod system;
@@ -6,17 +6,20 @@ use std::{
 };
 
 use chaos_communicator::message::{ChaosMessage, ChaosMessageBuilder};
-use winit::event::WindowEvent;
+use winit::event::{DeviceEvent, WindowEvent};
 
 use crate::{
     device::{
         bindings::{ChaosBindingEvent, ChaosButton, ChaosInputEventMatcher},
-        events::{ChaosDeviceEvent, ChaosInputEvent},
+        events::{ChaosDeviceEvent, ChaosInputEvent, ChaosModifiers, ChaosTouchPhase},
+        gestures::{ChaosGesture, ChaosGestureConfig, ChaosGestureRecognizer, ChaosTouchPoint},
+        input_state::{ChaosFrameInput, InputState},
     },
     triggers::trigger_event_key::TriggerEventKey,
 };
 
 const MAX_INPUT_HISTORY: usize = 64;
+const MAX_PRESS_HISTORY: usize = 16;
 
 /// Opaque handle to a registered binding, used with [`DeviceEventSystem::unbind`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -49,6 +52,21 @@ pub struct ChaosBindingContext {
     // binding are pushed here, so mouse motion and other high-frequency events never
     // evict older button presses.
     input_history: VecDeque<(Instant, ChaosInputEvent)>,
+    // Times of the most recent presses of each button, for multi-tap matching.
+    press_history: HashMap<ChaosButton, VecDeque<Instant>>,
+    // The button released by the current input event and how long it was held.
+    released_hold: Option<(ChaosButton, Duration)>,
+    // Modifier keys (Shift/Ctrl/Alt/Super) currently held, as last reported by the platform.
+    modifiers: ChaosModifiers,
+    // Last cursor position inside the window, in physical pixels.
+    cursor_position: Option<(f64, f64)>,
+    // Fingers currently touching the screen, by touch id.
+    touches: HashMap<u64, ChaosTouchPoint>,
+    gesture_recognizer: ChaosGestureRecognizer,
+    // Gestures recognized during the current update; cleared at the start of every update.
+    recognized_gestures: Vec<ChaosGesture>,
+    // Input edges since the `InputState` was last written.
+    frame: ChaosFrameInput,
 }
 
 impl Default for ChaosBindingContext {
@@ -65,9 +83,33 @@ impl ChaosBindingContext {
             fired_held_bindings: HashSet::new(),
             fired_chord_bindings: HashSet::new(),
             input_history: VecDeque::new(),
+            press_history: HashMap::new(),
+            released_hold: None,
+            modifiers: ChaosModifiers::empty(),
+            cursor_position: None,
+            touches: HashMap::new(),
+            gesture_recognizer: ChaosGestureRecognizer::default(),
+            recognized_gestures: Vec::new(),
+            frame: ChaosFrameInput::default(),
         }
     }
 
+    pub(crate) fn modifiers(&self) -> ChaosModifiers {
+        self.modifiers
+    }
+
+    pub fn cursor_position(&self) -> Option<(f64, f64)> {
+        self.cursor_position
+    }
+
+    pub fn touches(&self) -> &HashMap<u64, ChaosTouchPoint> {
+        &self.touches
+    }
+
+    pub(crate) fn recognized_gestures(&self) -> &[ChaosGesture] {
+        &self.recognized_gestures
+    }
+
     pub(crate) fn button_held_for_at(
         &self,
         key: &ChaosButton,
@@ -120,6 +162,8 @@ impl ChaosBindingContext {
             Some(ChaosInputEvent::KeyboardInput {
                 keycode,
                 pressed: true,
+                repeat: false,
+                ..
             }) => keys
                 .iter()
                 .any(|k| matches!(k, ChaosButton::Keyboard(kc) if kc == keycode)),
@@ -142,6 +186,121 @@ impl ChaosBindingContext {
         self.fired_chord_bindings.insert(keys.to_vec())
     }
 
+    // Number of presses of `button` in a row, ending with its most recent press, where each
+    // press followed the previous one within `max_interval`.
+    pub(crate) fn tap_count(&self, button: &ChaosButton, max_interval: Duration) -> u32 {
+        let Some(presses) = self.press_history.get(button) else {
+            return 0;
+        };
+
+        let mut count = 0;
+        let mut later: Option<&Instant> = None;
+        for pressed_at in presses.iter().rev() {
+            if later.is_some_and(|later| later.duration_since(*pressed_at) > max_interval) {
+                break;
+            }
+            count += 1;
+            later = Some(pressed_at);
+        }
+        count
+    }
+
+    // How long `button` was held, if `input_event` is its release.
+    pub(crate) fn released_hold_duration(
+        &self,
+        button: &ChaosButton,
+        input_event: &ChaosInputEvent,
+    ) -> Option<Duration> {
+        if input_event.button().as_ref() != Some(button)
+            || input_event.button_pressed() != Some(false)
+        {
+            return None;
+        }
+        self.released_hold
+            .filter(|(released, _)| released == button)
+            .map(|(_, duration)| duration)
+    }
+
+    pub(crate) fn tap_matches(
+        &self,
+        button: &ChaosButton,
+        max_duration: Duration,
+        input_event: &ChaosInputEvent,
+    ) -> bool {
+        self.released_hold_duration(button, input_event)
+            .is_some_and(|held| held <= max_duration)
+    }
+
+    pub(crate) fn released_after_held_matches(
+        &self,
+        button: &ChaosButton,
+        min_duration: Duration,
+        input_event: &ChaosInputEvent,
+    ) -> bool {
+        self.released_hold_duration(button, input_event)
+            .is_some_and(|held| held >= min_duration)
+    }
+
+    pub(crate) fn multi_tap_matches(
+        &self,
+        button: &ChaosButton,
+        count: u32,
+        max_interval: Duration,
+        input_event: &ChaosInputEvent,
+    ) -> bool {
+        input_event.button().as_ref() == Some(button)
+            && input_event.button_pressed() == Some(true)
+            && self.tap_count(button, max_interval) == count
+    }
+
+    // The level of a binding for `InputState::action_value`: 1.0 while its buttons are
+    // held, the wheel delta for wheel bindings, 0.0 for everything else.
+    fn action_value(&self, binding: &ChaosBindingEvent, now: Instant) -> f32 {
+        let held = |button: &ChaosButton| self.pressed_buttons.contains(button);
+        let active = match binding {
+            ChaosBindingEvent::Input(ChaosInputEventMatcher::MouseWheel) => {
+                return self.frame.wheel_delta.1;
+            }
+            ChaosBindingEvent::Input(ChaosInputEventMatcher::Pressed(button))
+            | ChaosBindingEvent::Tap { button, .. }
+            | ChaosBindingEvent::MultiTap { button, .. }
+            | ChaosBindingEvent::ReleasedAfterHeld { button, .. } => held(button),
+            ChaosBindingEvent::Held {
+                button, duration, ..
+            } => self.button_held_for_at(button, *duration, now),
+            ChaosBindingEvent::Chord { keys } => self.chord_matches(keys),
+            ChaosBindingEvent::Modified {
+                matcher: ChaosInputEventMatcher::Pressed(button),
+                modifiers,
+            } => self.modifiers == *modifiers && held(button),
+            _ => false,
+        };
+        if active { 1.0 } else { 0.0 }
+    }
+
+    // Binding specific parameters for the signal message, e.g. the measured hold duration
+    // of a `Tap` or the tap count of a `MultiTap`.
+    fn enrich_binding_message(
+        &self,
+        binding: &ChaosBindingEvent,
+        builder: ChaosMessageBuilder,
+    ) -> ChaosMessageBuilder {
+        match binding {
+            ChaosBindingEvent::Tap { .. } | ChaosBindingEvent::ReleasedAfterHeld { .. } => {
+                match self.released_hold {
+                    Some((_, duration)) => builder.with_param("duration", duration),
+                    None => builder,
+                }
+            }
+            ChaosBindingEvent::MultiTap {
+                button,
+                max_interval,
+                ..
+            } => builder.with_param("tap_count", self.tap_count(button, *max_interval)),
+            _ => builder,
+        }
+    }
+
     // Immutable peek: is the sequence the tail of recent input history? Kept for
     // `ChaosBindingEvent::matches` compatibility; the dispatch path uses
     // `sequence_binding_matches` which allows intervening unrelated events.
@@ -221,6 +380,43 @@ impl ChaosBindingContext {
             self.input_history.pop_front();
         }
     }
+
+    // Applies a touch event to the tracked touch points and feeds it to the gesture
+    // recognizer. Moves and ends of touches we never saw start are ignored.
+    fn update_touch(
+        &mut self,
+        id: u64,
+        phase: ChaosTouchPhase,
+        position: (f64, f64),
+        now: Instant,
+    ) {
+        let point = match phase {
+            ChaosTouchPhase::Started => {
+                let point = ChaosTouchPoint::new(position, now);
+                self.touches.insert(id, point.clone());
+                point
+            }
+            ChaosTouchPhase::Moved => {
+                let Some(point) = self.touches.get_mut(&id) else {
+                    return;
+                };
+                point.position = position;
+                point.clone()
+            }
+            ChaosTouchPhase::Ended | ChaosTouchPhase::Cancelled => {
+                let Some(mut point) = self.touches.remove(&id) else {
+                    return;
+                };
+                point.position = position;
+                point
+            }
+        };
+
+        let gestures = self
+            .gesture_recognizer
+            .touch(id, phase, &point, &self.touches, now);
+        self.recognized_gestures.extend(gestures);
+    }
 }
 
 pub struct DeviceEventSystem {
@@ -229,15 +425,25 @@ pub struct DeviceEventSystem {
     next_binding_id: u64,
 }
 
+type BuildMessage = Box<
+    dyn Fn(
+            ChaosMessageBuilder,
+            Option<&ChaosInputEvent>,
+            Option<&ChaosDeviceEvent>,
+            Option<&ChaosGesture>,
+        ) -> ChaosMessage
+        + Send
+        + Sync,
+>;
+
 struct BoundSignal {
     id: BindingId,
     binding: ChaosBindingEvent,
+    trigger_key: TriggerEventKey,
     // Constructs the signal message on demand. Parameters (including event-specific
     // ones like `width`/`height` for a resize) are materialized when the binding
     // matches, not when it was registered.
-    build_message: Box<
-        dyn Fn(Option<&ChaosInputEvent>, Option<&ChaosDeviceEvent>) -> ChaosMessage + Send + Sync,
-    >,
+    build_message: BuildMessage,
 }
 
 impl Default for DeviceEventSystem {
@@ -260,20 +466,20 @@ impl DeviceEventSystem {
         T: Any + Hash + Clone + Send + Sync + 'static,
     {
         let trigger_key = TriggerEventKey::new(&signal);
-        let build_message: Box<
-            dyn Fn(Option<&ChaosInputEvent>, Option<&ChaosDeviceEvent>) -> ChaosMessage
-                + Send
-                + Sync,
-        > = Box::new(move |input_event, device_event| {
-            let mut builder = ChaosMessageBuilder::new().with_param("signal", signal.clone());
-            if let Some(ie) = input_event {
-                builder = ie.enrich_message(builder);
-            }
-            if let Some(de) = device_event {
-                builder = de.enrich_message(builder);
-            }
-            builder.build_for_event(trigger_key)
-        });
+        let build_message: BuildMessage =
+            Box::new(move |builder, input_event, device_event, gesture| {
+                let mut builder = builder.with_param("signal", signal.clone());
+                if let Some(ie) = input_event {
+                    builder = ie.enrich_message(builder);
+                }
+                if let Some(de) = device_event {
+                    builder = de.enrich_message(builder);
+                }
+                if let Some(gesture) = gesture {
+                    builder = gesture.enrich_message(builder);
+                }
+                builder.build_for_event(trigger_key)
+            });
 
         let id = BindingId(self.next_binding_id);
         self.next_binding_id += 1;
@@ -281,12 +487,18 @@ impl DeviceEventSystem {
         self.bindings.push(BoundSignal {
             id,
             binding,
+            trigger_key,
             build_message,
         });
 
         BindingHandle { trigger_key, id }
     }
 
+    /// Replace the thresholds used to recognize touch gestures.
+    pub fn set_gesture_config(&mut self, config: ChaosGestureConfig) {
+        self.context.gesture_recognizer.set_config(config);
+    }
+
     /// Remove a previously registered binding. Returns `true` if the binding was found
     /// and removed.
     pub fn unbind(&mut self, id: BindingId) -> bool {
@@ -306,20 +518,103 @@ impl DeviceEventSystem {
         )
     }
 
+    /// Feed a raw device event (e.g. relative mouse motion) that is not tied to the window.
+    pub fn update_device_event(&mut self, event: &DeviceEvent) -> Vec<ChaosMessage> {
+        match ChaosInputEvent::try_from(event) {
+            Ok(input_event) => {
+                self.update_with_chaos_events(Some(input_event), None, Instant::now())
+            }
+            Err(()) => Vec::new(),
+        }
+    }
+
+    /// Last known cursor position inside the window, in physical pixels.
+    pub fn cursor_position(&self) -> Option<(f64, f64)> {
+        self.context.cursor_position
+    }
+
+    /// Copy the current input into `state` (usually the world's `InputState` resource).
+    /// Presses, releases and deltas since the previous call are added to the state's
+    /// frame edges, which `ChaosWorld::update` clears at its end.
+    pub fn write_input_state(&mut self, state: &mut InputState) {
+        self.write_input_state_at(state, Instant::now());
+    }
+
+    fn write_input_state_at(&mut self, state: &mut InputState, now: Instant) {
+        let mut action_values: HashMap<TriggerEventKey, f32> = HashMap::new();
+        for bound_signal in &self.bindings {
+            let value = self.context.action_value(&bound_signal.binding, now);
+            let entry = action_values.entry(bound_signal.trigger_key).or_default();
+            if value.abs() > entry.abs() {
+                *entry = value;
+            }
+        }
+        // Bindings that are not tied to held buttons count as active in the frame they fired.
+        for fired in &self.context.frame.fired_actions {
+            let entry = action_values.entry(*fired).or_default();
+            if *entry == 0.0 {
+                *entry = 1.0;
+            }
+        }
+
+        state.update(
+            &self.context.pressed_buttons,
+            &self.context.held_since,
+            self.context.cursor_position,
+            &mut self.context.frame,
+            action_values,
+            now,
+        );
+    }
+
     fn update_with_chaos_events(
         &mut self,
         input_event: Option<ChaosInputEvent>,
         device_event: Option<ChaosDeviceEvent>,
         now: Instant,
     ) -> Vec<ChaosMessage> {
+        self.context.recognized_gestures.clear();
+        self.context.released_hold = None;
         let input_event =
             input_event.and_then(|input_event| self.update_input_state(input_event, now));
+        let long_presses = self
+            .context
+            .gesture_recognizer
+            .poll(&self.context.touches, now);
+        self.context.recognized_gestures.extend(long_presses);
 
         let mut messages = Vec::new();
         let mut input_referenced = false;
         let mut device_referenced = false;
+        let mut gestures_referenced = vec![false; self.context.recognized_gestures.len()];
 
         for bound_signal in &self.bindings {
+            // A gesture binding fires once for every matching gesture recognized in this
+            // update, with that gesture's parameters.
+            if let ChaosBindingEvent::Gesture(matcher) = &bound_signal.binding {
+                for (gesture, referenced) in self
+                    .context
+                    .recognized_gestures
+                    .iter()
+                    .zip(gestures_referenced.iter_mut())
+                {
+                    if matcher.matches_gesture(gesture) {
+                        self.context
+                            .frame
+                            .fired_actions
+                            .insert(bound_signal.trigger_key);
+                        messages.push((bound_signal.build_message)(
+                            ChaosMessageBuilder::new(),
+                            None,
+                            None,
+                            Some(gesture),
+                        ));
+                        *referenced = true;
+                    }
+                }
+                continue;
+            }
+
             if !self.context.matches_binding(
                 &bound_signal.binding,
                 input_event.as_ref(),
@@ -329,9 +624,18 @@ impl DeviceEventSystem {
                 continue;
             }
 
+            self.context
+                .frame
+                .fired_actions
+                .insert(bound_signal.trigger_key);
+            let builder = self
+                .context
+                .enrich_binding_message(&bound_signal.binding, ChaosMessageBuilder::new());
             messages.push((bound_signal.build_message)(
+                builder,
                 input_event.as_ref(),
                 device_event.as_ref(),
+                None,
             ));
 
             if let Some(ie) = input_event.as_ref() {
@@ -360,6 +664,16 @@ impl DeviceEventSystem {
                 messages.push(de.into());
             }
         }
+        for (gesture, referenced) in self
+            .context
+            .recognized_gestures
+            .iter()
+            .zip(gestures_referenced)
+        {
+            if referenced {
+                messages.push(gesture.clone().into());
+            }
+        }
 
         messages
     }
@@ -369,20 +683,74 @@ impl DeviceEventSystem {
         input_event: ChaosInputEvent,
         now: Instant,
     ) -> Option<ChaosInputEvent> {
-        let state_changed = match &input_event {
-            ChaosInputEvent::KeyboardInput { keycode, pressed } => {
-                self.update_button_state(ChaosButton::Keyboard(*keycode), *pressed, now)
+        let input_event = match input_event {
+            ChaosInputEvent::KeyboardInput {
+                keycode,
+                logical_key,
+                text,
+                pressed,
+                ..
+            } => {
+                let state_changed =
+                    self.update_button_state(ChaosButton::Keyboard(keycode), pressed, now);
+                // A press of a key that is already held is an OS-level repeat. Repeats are
+                // forwarded (flagged) so text entry and `Repeated` bindings see them, while
+                // duplicate releases are dropped.
+                if !state_changed && !pressed {
+                    return None;
+                }
+                ChaosInputEvent::KeyboardInput {
+                    keycode,
+                    logical_key,
+                    text,
+                    pressed,
+                    repeat: !state_changed,
+                }
             }
             ChaosInputEvent::MouseButton { button, pressed } => {
-                self.update_button_state(ChaosButton::Mouse(*button), *pressed, now)
+                if !self.update_button_state(ChaosButton::Mouse(button), pressed, now) {
+                    return None;
+                }
+                ChaosInputEvent::MouseButton { button, pressed }
+            }
+            ChaosInputEvent::ModifiersChanged { modifiers } => {
+                self.context.modifiers = modifiers;
+                ChaosInputEvent::ModifiersChanged { modifiers }
+            }
+            ChaosInputEvent::MousePosition { x, y } => {
+                self.context.cursor_position = Some((x, y));
+                ChaosInputEvent::MousePosition { x, y }
+            }
+            ChaosInputEvent::MouseWheel { delta_x, delta_y } => {
+                self.context.frame.wheel_delta.0 += delta_x;
+                self.context.frame.wheel_delta.1 += delta_y;
+                ChaosInputEvent::MouseWheel { delta_x, delta_y }
+            }
+            ChaosInputEvent::MouseMotion { delta_x, delta_y } => {
+                self.context.frame.mouse_motion.0 += delta_x;
+                self.context.frame.mouse_motion.1 += delta_y;
+                ChaosInputEvent::MouseMotion { delta_x, delta_y }
+            }
+            ChaosInputEvent::Touch { id, phase, x, y } => {
+                self.context.update_touch(id, phase, (x, y), now);
+                ChaosInputEvent::Touch { id, phase, x, y }
             }
-            _ => true,
+            ChaosInputEvent::PinchGesture { delta, phase } => {
+                let gestures = self.context.gesture_recognizer.trackpad_pinch(delta, phase);
+                self.context.recognized_gestures.extend(gestures);
+                ChaosInputEvent::PinchGesture { delta, phase }
+            }
+            ChaosInputEvent::RotationGesture { delta, phase } => {
+                let gestures = self
+                    .context
+                    .gesture_recognizer
+                    .trackpad_rotation(delta, phase);
+                self.context.recognized_gestures.extend(gestures);
+                ChaosInputEvent::RotationGesture { delta, phase }
+            }
+            input_event => input_event,
         };
 
-        if !state_changed {
-            return None;
-        }
-
         if self.event_is_relevant_for_sequence_history(&input_event) {
             self.context.record_input_event(input_event.clone(), now);
         }
@@ -410,13 +778,22 @@ impl DeviceEventSystem {
                 return false;
             }
             self.context.held_since.insert(button.clone(), now);
+            let presses = self.context.press_history.entry(button).or_default();
+            presses.push_back(now);
+            while presses.len() > MAX_PRESS_HISTORY {
+                presses.pop_front();
+            }
+            self.context.frame.just_pressed.insert(button);
             self.context.pressed_buttons.insert(button);
             true
         } else {
             if !self.context.pressed_buttons.remove(&button) {
                 return false;
             }
-            self.context.held_since.remove(&button);
+            if let Some(pressed_at) = self.context.held_since.remove(&button) {
+                self.context.released_hold = Some((button, now.duration_since(pressed_at)));
+            }
+            self.context.frame.just_released.insert(button);
             self.context
                 .fired_held_bindings
                 .retain(|(held_button, _)| held_button != &button);
@@ -436,31 +813,17 @@ impl DeviceEventSystem {
             ChaosBindingEvent::Sequence { events, .. } => events
                 .iter()
                 .any(|matcher| matcher.matches_input_event(input_event)),
-            ChaosBindingEvent::Held { button, .. } => match (button, input_event) {
-                (
-                    ChaosButton::Keyboard(expected_key),
-                    ChaosInputEvent::KeyboardInput { keycode, .. },
-                ) => expected_key == keycode,
-                (
-                    ChaosButton::Mouse(expected_button),
-                    ChaosInputEvent::MouseButton { button, .. },
-                ) => expected_button == button,
-                _ => false,
-            },
-            ChaosBindingEvent::Chord { keys } => {
-                keys.iter().any(|button| match (button, input_event) {
-                    (
-                        ChaosButton::Keyboard(expected_key),
-                        ChaosInputEvent::KeyboardInput { keycode, .. },
-                    ) => expected_key == keycode,
-                    (
-                        ChaosButton::Mouse(expected_button),
-                        ChaosInputEvent::MouseButton { button, .. },
-                    ) => expected_button == button,
-                    _ => false,
-                })
+            ChaosBindingEvent::Held { button, .. }
+            | ChaosBindingEvent::Tap { button, .. }
+            | ChaosBindingEvent::MultiTap { button, .. }
+            | ChaosBindingEvent::ReleasedAfterHeld { button, .. } => {
+                input_event.button().as_ref() == Some(button)
             }
-            ChaosBindingEvent::Device(_) => false,
+            ChaosBindingEvent::Chord { keys } => input_event
+                .button()
+                .is_some_and(|button| keys.contains(&button)),
+            ChaosBindingEvent::Modified { matcher, .. } => matcher.matches_input_event(input_event),
+            ChaosBindingEvent::Device(_) | ChaosBindingEvent::Gesture(_) => false,
         }
     }
 
@@ -502,6 +865,34 @@ impl ChaosBindingContext {
                 continuous,
             } => self.held_binding_matches(button, *duration, now, *continuous),
             ChaosBindingEvent::Chord { keys } => self.chord_binding_matches(keys, input_event),
+            ChaosBindingEvent::Tap {
+                button,
+                max_duration,
+            } => input_event
+                .is_some_and(|input_event| self.tap_matches(button, *max_duration, input_event)),
+            ChaosBindingEvent::MultiTap {
+                button,
+                count,
+                max_interval,
+            } => input_event.is_some_and(|input_event| {
+                self.multi_tap_matches(button, *count, *max_interval, input_event)
+            }),
+            ChaosBindingEvent::ReleasedAfterHeld {
+                button,
+                min_duration,
+            } => input_event.is_some_and(|input_event| {
+                self.released_after_held_matches(button, *min_duration, input_event)
+            }),
+            ChaosBindingEvent::Modified { matcher, modifiers } => {
+                self.modifiers == *modifiers
+                    && input_event
+                        .map(|input_event| matcher.matches_input_event(input_event))
+                        .unwrap_or(false)
+            }
+            ChaosBindingEvent::Gesture(matcher) => self
+                .recognized_gestures
+                .iter()
+                .any(|gesture| matcher.matches_gesture(gesture)),
         }
     }
 }
@@ -509,8 +900,14 @@ impl ChaosBindingContext {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::device::bindings::ChaosDeviceEventMatcher;
-    use winit::keyboard::KeyCode;
+    use crate::{
+        device::{
+            bindings::{ChaosDeviceEventMatcher, ChaosGestureMatcher},
+            gestures::ChaosSwipeDirection,
+        },
+        ecs::{system::ChaosSystem, world::ChaosWorld},
+    };
+    use winit::keyboard::{Key, KeyCode, ModifiersState};
 
     #[derive(Clone, Debug, PartialEq, Eq, Hash)]
     enum TestSignal {
@@ -538,10 +935,7 @@ mod tests {
         // The handle is usable end-to-end: the signal message dispatched by the system
         // carries the same trigger key.
         let messages = system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::Space,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::Space, true)),
             None,
             Instant::now(),
         );
@@ -563,10 +957,7 @@ mod tests {
         );
 
         let messages = system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::Space,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::Space, true)),
             None,
             Instant::now(),
         );
@@ -581,10 +972,7 @@ mod tests {
         }));
         assert!(messages.iter().any(|message| {
             message.get::<ChaosInputEvent>("input_event")
-                == Some(ChaosInputEvent::KeyboardInput {
-                    keycode: KeyCode::Space,
-                    pressed: true,
-                })
+                == Some(ChaosInputEvent::key(KeyCode::Space, true))
                 && message.get::<bool>("pressed") == Some(true)
         }));
     }
@@ -601,34 +989,22 @@ mod tests {
         let now = Instant::now();
 
         let first_press = system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::Space,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::Space, true)),
             None,
             now,
         );
         let repeated_press = system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::Space,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::Space, true)),
             None,
             now + Duration::from_millis(16),
         );
         system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::Space,
-                pressed: false,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::Space, false)),
             None,
             now + Duration::from_millis(32),
         );
         let press_after_release = system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::Space,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::Space, true)),
             None,
             now + Duration::from_millis(48),
         );
@@ -652,10 +1028,7 @@ mod tests {
         let now = Instant::now();
 
         let initial_press = system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::Space,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::Space, true)),
             None,
             now,
         );
@@ -664,18 +1037,12 @@ mod tests {
         let repeated_held_match =
             system.update_with_chaos_events(None, None, now + Duration::from_millis(20));
         system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::Space,
-                pressed: false,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::Space, false)),
             None,
             now + Duration::from_millis(30),
         );
         system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::Space,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::Space, true)),
             None,
             now + Duration::from_millis(40),
         );
@@ -704,10 +1071,7 @@ mod tests {
         let now = Instant::now();
 
         let initial_press = system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::Space,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::Space, true)),
             None,
             now,
         );
@@ -716,18 +1080,12 @@ mod tests {
         let repeated_held_match =
             system.update_with_chaos_events(None, None, now + Duration::from_millis(20));
         system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::Space,
-                pressed: false,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::Space, false)),
             None,
             now + Duration::from_millis(30),
         );
         system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::Space,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::Space, true)),
             None,
             now + Duration::from_millis(40),
         );
@@ -825,10 +1183,7 @@ mod tests {
         let now = Instant::now();
 
         let after_a = system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::KeyA,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::KeyA, true)),
             None,
             now,
         );
@@ -839,10 +1194,7 @@ mod tests {
         );
 
         let after_b = system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::KeyB,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::KeyB, true)),
             None,
             now + Duration::from_millis(50),
         );
@@ -871,14 +1223,7 @@ mod tests {
         );
         let now = Instant::now();
 
-        system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::KeyA,
-                pressed: true,
-            }),
-            None,
-            now,
-        );
+        system.update_with_chaos_events(Some(ChaosInputEvent::key(KeyCode::KeyA, true)), None, now);
         // Mouse motion in the middle of the sequence.
         for i in 0..10 {
             system.update_with_chaos_events(
@@ -891,10 +1236,7 @@ mod tests {
             );
         }
         let after_b = system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::KeyB,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::KeyB, true)),
             None,
             now + Duration::from_millis(100),
         );
@@ -923,27 +1265,14 @@ mod tests {
         );
         let now = Instant::now();
 
+        system.update_with_chaos_events(Some(ChaosInputEvent::key(KeyCode::KeyA, true)), None, now);
         system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::KeyA,
-                pressed: true,
-            }),
-            None,
-            now,
-        );
-        system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::KeyA,
-                pressed: false,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::KeyA, false)),
             None,
             now + Duration::from_millis(50),
         );
         let after_second_press = system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::KeyA,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::KeyA, true)),
             None,
             now + Duration::from_millis(100),
         );
@@ -970,19 +1299,9 @@ mod tests {
         );
         let now = Instant::now();
 
-        system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::KeyA,
-                pressed: true,
-            }),
-            None,
-            now,
-        );
+        system.update_with_chaos_events(Some(ChaosInputEvent::key(KeyCode::KeyA, true)), None, now);
         let after_b = system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::KeyB,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::KeyB, true)),
             None,
             now + Duration::from_millis(500),
         );
@@ -1011,10 +1330,7 @@ mod tests {
         let now = Instant::now();
 
         let after_ctrl = system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::ControlLeft,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::ControlLeft, true)),
             None,
             now,
         );
@@ -1025,10 +1341,7 @@ mod tests {
         );
 
         let after_s = system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::KeyS,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::KeyS, true)),
             None,
             now + Duration::from_millis(10),
         );
@@ -1054,18 +1367,12 @@ mod tests {
 
         // Releasing and re-pressing a chord key should allow the chord to fire again.
         system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::KeyS,
-                pressed: false,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::KeyS, false)),
             None,
             now + Duration::from_millis(30),
         );
         let after_s_again = system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::KeyS,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::KeyS, true)),
             None,
             now + Duration::from_millis(40),
         );
@@ -1093,26 +1400,17 @@ mod tests {
         let now = Instant::now();
 
         system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::ControlLeft,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::ControlLeft, true)),
             None,
             now,
         );
         system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::KeyS,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::KeyS, true)),
             None,
             now + Duration::from_millis(10),
         );
         let after_release = system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::KeyS,
-                pressed: false,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::KeyS, false)),
             None,
             now + Duration::from_millis(20),
         );
@@ -1138,10 +1436,7 @@ mod tests {
         assert!(!system.unbind(handle.id));
 
         let messages = system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::Space,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::Space, true)),
             None,
             Instant::now(),
         );
@@ -1167,14 +1462,7 @@ mod tests {
         );
         let now = Instant::now();
 
-        system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::KeyA,
-                pressed: true,
-            }),
-            None,
-            now,
-        );
+        system.update_with_chaos_events(Some(ChaosInputEvent::key(KeyCode::KeyA, true)), None, now);
         for i in 0..(MAX_INPUT_HISTORY * 4) {
             system.update_with_chaos_events(
                 Some(ChaosInputEvent::MousePosition {
@@ -1186,10 +1474,7 @@ mod tests {
             );
         }
         let after_b = system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::KeyB,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::KeyB, true)),
             None,
             now + Duration::from_secs(1),
         );
@@ -1268,10 +1553,7 @@ mod tests {
         );
 
         let messages = system.update_with_chaos_events(
-            Some(ChaosInputEvent::KeyboardInput {
-                keycode: KeyCode::Space,
-                pressed: true,
-            }),
+            Some(ChaosInputEvent::key(KeyCode::Space, true)),
             None,
             Instant::now(),
         );
@@ -1286,4 +1568,553 @@ mod tests {
         );
         assert_eq!(signal_message.get::<bool>("pressed"), Some(true));
     }
+
+    #[test]
+    fn text_binding_receives_text_of_presses_and_repeats() {
+        let mut system = DeviceEventSystem::new();
+        let handle = system.bind(ChaosBindingEvent::text(), TestSignal::Fire);
+        let key_a = |repeat: bool| ChaosInputEvent::KeyboardInput {
+            keycode: KeyCode::KeyA,
+            logical_key: Key::Character("a".into()),
+            text: Some("a".to_string()),
+            pressed: true,
+            repeat,
+        };
+        let now = Instant::now();
+
+        let first_press = system.update_with_chaos_events(Some(key_a(false)), None, now);
+        // The platform repeat arrives while A is still held.
+        let repeat = system.update_with_chaos_events(
+            Some(key_a(true)),
+            None,
+            now + Duration::from_millis(500),
+        );
+
+        for messages in [&first_press, &repeat] {
+            let signal_message = messages
+                .iter()
+                .find(|m| m.get_event() == signal_event(handle.trigger_key))
+                .expect("signal message");
+            assert_eq!(signal_message.get::<String>("text"), Some("a".to_string()));
+        }
+        let repeat_message = repeat
+            .iter()
+            .find(|m| m.get_event() == signal_event(handle.trigger_key))
+            .unwrap();
+        assert_eq!(repeat_message.get::<bool>("repeat"), Some(true));
+    }
+
+    #[test]
+    fn repeated_binding_fires_on_key_repeat_but_pressed_does_not() {
+        let mut system = DeviceEventSystem::new();
+        let repeated = system.bind(
+            ChaosBindingEvent::Input(ChaosInputEventMatcher::Repeated(ChaosButton::Keyboard(
+                KeyCode::Backspace,
+            ))),
+            TestSignal::Fire,
+        );
+        let pressed = system.bind(
+            ChaosBindingEvent::pressed(ChaosButton::Keyboard(KeyCode::Backspace)),
+            TestSignal::Close,
+        );
+        let now = Instant::now();
+
+        let first_press = system.update_with_chaos_events(
+            Some(ChaosInputEvent::key(KeyCode::Backspace, true)),
+            None,
+            now,
+        );
+        let repeat = system.update_with_chaos_events(
+            Some(ChaosInputEvent::key(KeyCode::Backspace, true)),
+            None,
+            now + Duration::from_millis(500),
+        );
+
+        let count = |messages: &[ChaosMessage], key: TriggerEventKey| {
+            messages
+                .iter()
+                .filter(|m| m.get_event() == signal_event(key))
+                .count()
+        };
+        assert_eq!(count(&first_press, pressed.trigger_key), 1);
+        assert_eq!(count(&first_press, repeated.trigger_key), 0);
+        assert_eq!(count(&repeat, pressed.trigger_key), 0);
+        assert_eq!(count(&repeat, repeated.trigger_key), 1);
+    }
+
+    #[test]
+    fn shortcut_binding_requires_exactly_its_modifiers() {
+        let mut system = DeviceEventSystem::new();
+        let handle = system.bind(
+            ChaosBindingEvent::shortcut(ModifiersState::CONTROL, KeyCode::KeyS),
+            TestSignal::Fire,
+        );
+        let now = Instant::now();
+        let fired = |messages: &[ChaosMessage]| {
+            messages
+                .iter()
+                .any(|m| m.get_event() == signal_event(handle.trigger_key))
+        };
+        let press_s = |system: &mut DeviceEventSystem, modifiers: ModifiersState| {
+            system.update_with_chaos_events(
+                Some(ChaosInputEvent::ModifiersChanged { modifiers }),
+                None,
+                now,
+            );
+            let messages = system.update_with_chaos_events(
+                Some(ChaosInputEvent::key(KeyCode::KeyS, true)),
+                None,
+                now,
+            );
+            system.update_with_chaos_events(
+                Some(ChaosInputEvent::key(KeyCode::KeyS, false)),
+                None,
+                now,
+            );
+            messages
+        };
+
+        assert!(!fired(&press_s(&mut system, ModifiersState::empty())));
+        assert!(fired(&press_s(&mut system, ModifiersState::CONTROL)));
+        assert!(!fired(&press_s(
+            &mut system,
+            ModifiersState::CONTROL | ModifiersState::SHIFT
+        )));
+    }
+
+    fn touch(id: u64, phase: ChaosTouchPhase, x: f64, y: f64) -> Option<ChaosInputEvent> {
+        Some(ChaosInputEvent::Touch { id, phase, x, y })
+    }
+
+    #[test]
+    fn tap_binding_fires_with_tap_position_and_double_tap_on_second_tap() {
+        let mut system = DeviceEventSystem::new();
+        let tap = system.bind(
+            ChaosBindingEvent::gesture(ChaosGestureMatcher::Tap),
+            TestSignal::Fire,
+        );
+        let double_tap = system.bind(
+            ChaosBindingEvent::gesture(ChaosGestureMatcher::DoubleTap),
+            TestSignal::Close,
+        );
+        let now = Instant::now();
+
+        let press = system.update_with_chaos_events(
+            touch(0, ChaosTouchPhase::Started, 40.0, 60.0),
+            None,
+            now,
+        );
+        let first = system.update_with_chaos_events(
+            touch(0, ChaosTouchPhase::Ended, 40.0, 60.0),
+            None,
+            now + Duration::from_millis(80),
+        );
+        system.update_with_chaos_events(
+            touch(1, ChaosTouchPhase::Started, 42.0, 61.0),
+            None,
+            now + Duration::from_millis(200),
+        );
+        let second = system.update_with_chaos_events(
+            touch(1, ChaosTouchPhase::Ended, 42.0, 61.0),
+            None,
+            now + Duration::from_millis(260),
+        );
+
+        let signal = |messages: &[ChaosMessage], key: TriggerEventKey| {
+            messages
+                .iter()
+                .find(|m| m.get_event() == signal_event(key))
+                .cloned()
+        };
+        assert!(press.is_empty());
+        let tapped = signal(&first, tap.trigger_key).expect("tap signal");
+        assert_eq!(tapped.get::<f64>("x"), Some(40.0));
+        assert_eq!(tapped.get::<f64>("y"), Some(60.0));
+        assert!(signal(&first, double_tap.trigger_key).is_none());
+        assert!(signal(&second, tap.trigger_key).is_some());
+        assert!(signal(&second, double_tap.trigger_key).is_some());
+    }
+
+    #[test]
+    fn long_press_binding_fires_on_update_without_new_touch_events() {
+        let mut system = DeviceEventSystem::new();
+        let handle = system.bind(
+            ChaosBindingEvent::gesture(ChaosGestureMatcher::LongPress),
+            TestSignal::Fire,
+        );
+        let now = Instant::now();
+
+        system.update_with_chaos_events(touch(3, ChaosTouchPhase::Started, 10.0, 10.0), None, now);
+        let early = system.update_with_chaos_events(None, None, now + Duration::from_millis(200));
+        let held = system.update_with_chaos_events(None, None, now + Duration::from_millis(600));
+        let released = system.update_with_chaos_events(
+            touch(3, ChaosTouchPhase::Ended, 10.0, 10.0),
+            None,
+            now + Duration::from_millis(700),
+        );
+
+        assert!(early.is_empty());
+        let long_press = held
+            .iter()
+            .find(|m| m.get_event() == signal_event(handle.trigger_key))
+            .expect("long press signal");
+        assert_eq!(
+            long_press.get::<Duration>("duration"),
+            Some(Duration::from_millis(600))
+        );
+        assert!(released.is_empty());
+        assert!(system.context.touches().is_empty());
+    }
+
+    #[test]
+    fn swipe_binding_only_fires_for_its_direction() {
+        let mut system = DeviceEventSystem::new();
+        let left = system.bind(
+            ChaosBindingEvent::gesture(ChaosGestureMatcher::Swipe(ChaosSwipeDirection::Left)),
+            TestSignal::Fire,
+        );
+        let right = system.bind(
+            ChaosBindingEvent::gesture(ChaosGestureMatcher::Swipe(ChaosSwipeDirection::Right)),
+            TestSignal::Close,
+        );
+        let now = Instant::now();
+
+        system.update_with_chaos_events(
+            touch(0, ChaosTouchPhase::Started, 300.0, 100.0),
+            None,
+            now,
+        );
+        system.update_with_chaos_events(
+            touch(0, ChaosTouchPhase::Moved, 250.0, 105.0),
+            None,
+            now + Duration::from_millis(50),
+        );
+        let messages = system.update_with_chaos_events(
+            touch(0, ChaosTouchPhase::Ended, 150.0, 110.0),
+            None,
+            now + Duration::from_millis(120),
+        );
+
+        assert!(
+            messages
+                .iter()
+                .any(|m| m.get_event() == signal_event(left.trigger_key)
+                    && m.get::<ChaosSwipeDirection>("direction")
+                        == Some(ChaosSwipeDirection::Left))
+        );
+        assert!(
+            !messages
+                .iter()
+                .any(|m| m.get_event() == signal_event(right.trigger_key))
+        );
+    }
+
+    #[test]
+    fn pinch_binding_receives_scale_from_touches_and_trackpad() {
+        let mut system = DeviceEventSystem::new();
+        let handle = system.bind(
+            ChaosBindingEvent::gesture(ChaosGestureMatcher::Pinch),
+            TestSignal::Fire,
+        );
+        let now = Instant::now();
+        let scales = |messages: &[ChaosMessage]| {
+            messages
+                .iter()
+                .filter(|m| m.get_event() == signal_event(handle.trigger_key))
+                .filter_map(|m| m.get::<f64>("scale"))
+                .collect::<Vec<_>>()
+        };
+
+        system.update_with_chaos_events(
+            touch(0, ChaosTouchPhase::Started, 100.0, 100.0),
+            None,
+            now,
+        );
+        system.update_with_chaos_events(
+            touch(1, ChaosTouchPhase::Started, 200.0, 100.0),
+            None,
+            now,
+        );
+        let touch_pinch = system.update_with_chaos_events(
+            touch(1, ChaosTouchPhase::Moved, 150.0, 100.0),
+            None,
+            now + Duration::from_millis(16),
+        );
+        let trackpad_pinch = system.update_with_chaos_events(
+            Some(ChaosInputEvent::PinchGesture {
+                delta: 0.25,
+                phase: ChaosTouchPhase::Started,
+            }),
+            None,
+            now + Duration::from_millis(32),
+        );
+
+        assert_eq!(scales(&touch_pinch), vec![0.5]);
+        assert_eq!(scales(&trackpad_pinch), vec![1.25]);
+    }
+
+    #[test]
+    fn mouse_motion_binding_receives_relative_deltas_and_tracks_cursor_separately() {
+        let mut system = DeviceEventSystem::new();
+        let handle = system.bind(ChaosBindingEvent::mouse_motion(), TestSignal::Fire);
+        let now = Instant::now();
+
+        let moved = system.update_with_chaos_events(
+            Some(ChaosInputEvent::MousePosition { x: 12.0, y: 34.0 }),
+            None,
+            now,
+        );
+        let motion = system.update_with_chaos_events(
+            Some(ChaosInputEvent::MouseMotion {
+                delta_x: -3.0,
+                delta_y: 1.5,
+            }),
+            None,
+            now,
+        );
+
+        assert!(moved.is_empty());
+        assert_eq!(system.cursor_position(), Some((12.0, 34.0)));
+        let signal = motion
+            .iter()
+            .find(|m| m.get_event() == signal_event(handle.trigger_key))
+            .expect("mouse motion signal");
+        assert_eq!(signal.get::<f64>("delta_x"), Some(-3.0));
+        assert_eq!(signal.get::<f64>("delta_y"), Some(1.5));
+    }
+
+    fn click(system: &mut DeviceEventSystem, pressed: bool, at: Instant) -> Vec<ChaosMessage> {
+        system.update_with_chaos_events(
+            Some(ChaosInputEvent::MouseButton {
+                button: winit::event::MouseButton::Left,
+                pressed,
+            }),
+            None,
+            at,
+        )
+    }
+
+    #[test]
+    fn double_and_triple_click_bindings_fire_once_each_with_tap_count() {
+        let mut system = DeviceEventSystem::new();
+        let double = system.bind(
+            ChaosBindingEvent::double_click(winit::event::MouseButton::Left),
+            TestSignal::Fire,
+        );
+        let triple = system.bind(
+            ChaosBindingEvent::triple_click(winit::event::MouseButton::Left),
+            TestSignal::Close,
+        );
+        let now = Instant::now();
+        let tap_count = |messages: &[ChaosMessage], key: TriggerEventKey| {
+            messages
+                .iter()
+                .find(|m| m.get_event() == signal_event(key))
+                .and_then(|m| m.get::<u32>("tap_count"))
+        };
+
+        let mut presses = Vec::new();
+        for i in 0..4 {
+            let at = now + Duration::from_millis(200 * i);
+            presses.push(click(&mut system, true, at));
+            click(&mut system, false, at + Duration::from_millis(50));
+        }
+        let late_press = click(&mut system, true, now + Duration::from_millis(2000));
+
+        assert_eq!(tap_count(&presses[0], double.trigger_key), None);
+        assert_eq!(tap_count(&presses[1], double.trigger_key), Some(2));
+        assert_eq!(tap_count(&presses[1], triple.trigger_key), None);
+        assert_eq!(tap_count(&presses[2], double.trigger_key), None);
+        assert_eq!(tap_count(&presses[2], triple.trigger_key), Some(3));
+        assert_eq!(tap_count(&presses[3], double.trigger_key), None);
+        assert_eq!(tap_count(&presses[3], triple.trigger_key), None);
+        assert_eq!(tap_count(&late_press, double.trigger_key), None);
+    }
+
+    #[test]
+    fn tap_binding_fires_on_quick_release_with_hold_duration() {
+        let mut system = DeviceEventSystem::new();
+        let handle = system.bind(
+            ChaosBindingEvent::tap(
+                ChaosButton::Keyboard(KeyCode::KeyE),
+                Duration::from_millis(200),
+            ),
+            TestSignal::Fire,
+        );
+        let now = Instant::now();
+        let press_and_release = |system: &mut DeviceEventSystem, start: Instant, held: u64| {
+            let press = system.update_with_chaos_events(
+                Some(ChaosInputEvent::key(KeyCode::KeyE, true)),
+                None,
+                start,
+            );
+            let release = system.update_with_chaos_events(
+                Some(ChaosInputEvent::key(KeyCode::KeyE, false)),
+                None,
+                start + Duration::from_millis(held),
+            );
+            (press, release)
+        };
+
+        let (quick_press, quick_release) = press_and_release(&mut system, now, 120);
+        let (_, slow_release) = press_and_release(&mut system, now + Duration::from_secs(1), 400);
+
+        assert!(quick_press.is_empty());
+        let tap = quick_release
+            .iter()
+            .find(|m| m.get_event() == signal_event(handle.trigger_key))
+            .expect("tap signal");
+        assert_eq!(
+            tap.get::<Duration>("duration"),
+            Some(Duration::from_millis(120))
+        );
+        assert!(
+            !slow_release
+                .iter()
+                .any(|m| m.get_event() == signal_event(handle.trigger_key))
+        );
+    }
+
+    #[test]
+    fn released_after_held_binding_fires_on_release_after_charging() {
+        let mut system = DeviceEventSystem::new();
+        let handle = system.bind(
+            ChaosBindingEvent::released_after_held(
+                ChaosButton::Keyboard(KeyCode::Space),
+                Duration::from_millis(300),
+            ),
+            TestSignal::Fire,
+        );
+        let now = Instant::now();
+
+        system.update_with_chaos_events(
+            Some(ChaosInputEvent::key(KeyCode::Space, true)),
+            None,
+            now,
+        );
+        let charging =
+            system.update_with_chaos_events(None, None, now + Duration::from_millis(500));
+        let released = system.update_with_chaos_events(
+            Some(ChaosInputEvent::key(KeyCode::Space, false)),
+            None,
+            now + Duration::from_millis(750),
+        );
+
+        assert!(charging.is_empty());
+        let signal = released
+            .iter()
+            .find(|m| m.get_event() == signal_event(handle.trigger_key))
+            .expect("release signal");
+        assert_eq!(
+            signal.get::<Duration>("duration"),
+            Some(Duration::from_millis(750))
+        );
+    }
+
+    // Records whether W was just pressed in every update.
+    struct JustPressedRecorder;
+
+    impl ChaosSystem for JustPressedRecorder {
+        fn initialize(&mut self, _world: &mut ChaosWorld) -> Result<(), &'static str> {
+            Ok(())
+        }
+
+        fn update(&mut self, world: &mut ChaosWorld) -> Result<(), &'static str> {
+            let pressed = world
+                .get_resource::<InputState>()
+                .ok_or("Missing input state")?
+                .just_pressed(KeyCode::KeyW);
+            world
+                .get_resource_mut::<Vec<bool>>()
+                .ok_or("Missing log")?
+                .push(pressed);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn world_update_clears_input_edges() {
+        let mut world = ChaosWorld::new();
+        world.insert_resource(InputState::default());
+        world.insert_resource(Vec::<bool>::new());
+        world.add_system(JustPressedRecorder);
+        let mut system = DeviceEventSystem::new();
+        let now = Instant::now();
+
+        system.update_with_chaos_events(Some(ChaosInputEvent::key(KeyCode::KeyW, true)), None, now);
+        for _ in 0..3 {
+            system.write_input_state(world.get_resource_mut::<InputState>().unwrap());
+            world.update().unwrap();
+        }
+
+        assert_eq!(
+            world.get_resource::<Vec<bool>>().unwrap(),
+            &vec![true, false, false]
+        );
+        let state = world.get_resource::<InputState>().unwrap();
+        assert!(state.is_pressed(KeyCode::KeyW));
+        assert!(!state.just_pressed(KeyCode::KeyW));
+    }
+
+    #[test]
+    fn input_state_reports_edges_until_frame_ends_and_levels_until_release() {
+        let mut system = DeviceEventSystem::new();
+        system.bind(
+            ChaosBindingEvent::pressed(ChaosButton::Keyboard(KeyCode::KeyW)),
+            TestSignal::Fire,
+        );
+        let mut state = InputState::default();
+        let now = Instant::now();
+
+        system.update_with_chaos_events(Some(ChaosInputEvent::key(KeyCode::KeyW, true)), None, now);
+        system.update_with_chaos_events(
+            Some(ChaosInputEvent::MouseWheel {
+                delta_x: 0.0,
+                delta_y: 1.0,
+            }),
+            None,
+            now,
+        );
+        system.update_with_chaos_events(
+            Some(ChaosInputEvent::MouseWheel {
+                delta_x: 0.0,
+                delta_y: 2.0,
+            }),
+            None,
+            now,
+        );
+        system.write_input_state_at(&mut state, now + Duration::from_millis(100));
+
+        assert!(state.is_pressed(KeyCode::KeyW));
+        assert!(state.just_pressed(KeyCode::KeyW));
+        assert!(!state.just_released(KeyCode::KeyW));
+        assert_eq!(
+            state.held_duration(KeyCode::KeyW),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(state.wheel_delta(), (0.0, 3.0));
+        assert_eq!(state.action_value(&TestSignal::Fire), 1.0);
+        assert!(state.action_fired(&TestSignal::Fire));
+
+        state.end_frame();
+        system.write_input_state_at(&mut state, now + Duration::from_millis(200));
+
+        assert!(state.is_pressed(KeyCode::KeyW));
+        assert!(!state.just_pressed(KeyCode::KeyW));
+        assert_eq!(state.wheel_delta(), (0.0, 0.0));
+        assert_eq!(state.action_value(&TestSignal::Fire), 1.0);
+        assert!(!state.action_fired(&TestSignal::Fire));
+
+        system.update_with_chaos_events(
+            Some(ChaosInputEvent::key(KeyCode::KeyW, false)),
+            None,
+            now + Duration::from_millis(250),
+        );
+        system.write_input_state_at(&mut state, now + Duration::from_millis(300));
+
+        assert!(!state.is_pressed(KeyCode::KeyW));
+        assert!(state.just_released(KeyCode::KeyW));
+        assert_eq!(state.held_duration(KeyCode::KeyW), None);
+        assert_eq!(state.action_value(&TestSignal::Fire), 0.0);
+    }
 }
@@ -6,5 +6,8 @@ pub mod componentstore;
 pub mod entity;
 pub mod errors;
 pub mod query;
+pub mod state;
 pub mod system;
+pub mod task;
+pub mod timer;
 pub mod world;
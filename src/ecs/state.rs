@@ -0,0 +1,280 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{
+    ecs::{EntityID, system::ChaosSystem, world::ChaosWorld},
+    triggers::conditions::TriggerCondition,
+};
+
+/// A type usable as game state, usually a fieldless enum like `Menu`, `Playing`, `GameOver`.
+pub trait ChaosState: Clone + Eq + Hash + 'static {}
+impl<T: Clone + Eq + Hash + 'static> ChaosState for T {}
+
+pub type StateCallback = Box<dyn FnMut(&mut ChaosWorld)>;
+
+/// World resource holding the current state of type `S`, added with `ChaosWorld::add_state`.
+///
+/// Transitions requested with [`ChaosStateMachine::request`] (or `ChaosWorld::request_state`)
+/// are applied at the end of the world update: first the `on_exit` callbacks of the old
+/// state run and the entities tagged with [`StateScoped`] for it are despawned, then the
+/// `on_enter` callbacks of the new state run. The initial state is entered on the first
+/// update.
+pub struct ChaosStateMachine<S: ChaosState> {
+    current: S,
+    requested: Option<S>,
+    // The initial state hasn't been entered yet.
+    entering: bool,
+    on_enter: HashMap<S, Vec<StateCallback>>,
+    on_exit: HashMap<S, Vec<StateCallback>>,
+}
+
+impl<S: ChaosState> ChaosStateMachine<S> {
+    pub fn new(initial: S) -> Self {
+        Self {
+            current: initial,
+            requested: None,
+            entering: true,
+            on_enter: HashMap::new(),
+            on_exit: HashMap::new(),
+        }
+    }
+
+    pub fn current(&self) -> &S {
+        &self.current
+    }
+
+    /// Changes to `state` at the end of the current world update. The last request in an
+    /// update wins; requesting the current state does nothing.
+    pub fn request(&mut self, state: S) {
+        self.requested = Some(state);
+    }
+
+    pub fn on_enter(&mut self, state: S, callback: impl FnMut(&mut ChaosWorld) + 'static) {
+        self.on_enter
+            .entry(state)
+            .or_default()
+            .push(Box::new(callback));
+    }
+
+    pub fn on_exit(&mut self, state: S, callback: impl FnMut(&mut ChaosWorld) + 'static) {
+        self.on_exit
+            .entry(state)
+            .or_default()
+            .push(Box::new(callback));
+    }
+}
+
+/// Tags an entity to be despawned when the world leaves the given state.
+pub struct StateScoped<S: ChaosState>(pub S);
+
+/// Runs the wrapped system only while the world is in `state`. Initialization always runs.
+pub struct InState<S: ChaosState, T: ChaosSystem> {
+    state: S,
+    system: T,
+}
+
+impl<S: ChaosState, T: ChaosSystem> InState<S, T> {
+    pub fn new(state: S, system: T) -> Self {
+        Self { state, system }
+    }
+}
+
+impl<S: ChaosState, T: ChaosSystem> ChaosSystem for InState<S, T> {
+    fn initialize(&mut self, world: &mut ChaosWorld) -> Result<(), &'static str> {
+        self.system.initialize(world)
+    }
+
+    fn update(&mut self, world: &mut ChaosWorld) -> Result<(), &'static str> {
+        if world.state::<S>() != Some(&self.state) {
+            return Ok(());
+        }
+        self.system.update(world)
+    }
+}
+
+/// True while the world is in `state`, to limit triggers to certain states.
+pub struct InStateCondition<S: ChaosState> {
+    state: S,
+}
+
+impl<S: ChaosState> InStateCondition<S> {
+    pub fn new(state: S) -> Self {
+        Self { state }
+    }
+}
+
+impl<S: ChaosState> TriggerCondition for InStateCondition<S> {
+    fn check_condition(&mut self, world: &ChaosWorld, _delta_time: f32) -> bool {
+        world.state::<S>() == Some(&self.state)
+    }
+}
+
+// Applies a pending transition of the `S` state machine. Registered with the world by
+// `ChaosWorld::add_state`.
+pub(crate) fn apply_state_transition<S: ChaosState>(world: &mut ChaosWorld) {
+    let Some(machine) = world.get_resource_mut::<ChaosStateMachine<S>>() else {
+        return;
+    };
+    if machine.entering {
+        machine.entering = false;
+        let initial = machine.current.clone();
+        run_callbacks(world, &initial, |machine| &mut machine.on_enter);
+    }
+
+    let Some(machine) = world.get_resource_mut::<ChaosStateMachine<S>>() else {
+        return;
+    };
+    let next = match machine.requested.take() {
+        Some(next) if next != machine.current => next,
+        _ => return,
+    };
+    let previous = machine.current.clone();
+
+    run_callbacks(world, &previous, |machine| &mut machine.on_exit);
+    despawn_scoped(world, &previous);
+    if let Some(machine) = world.get_resource_mut::<ChaosStateMachine<S>>() {
+        machine.current = next.clone();
+    }
+    run_callbacks(world, &next, |machine| &mut machine.on_enter);
+}
+
+// The callbacks are taken out of the resource while they run, so they can use the world
+// and register further callbacks.
+fn run_callbacks<S: ChaosState>(
+    world: &mut ChaosWorld,
+    state: &S,
+    callbacks: fn(&mut ChaosStateMachine<S>) -> &mut HashMap<S, Vec<StateCallback>>,
+) {
+    let Some(mut running) = world
+        .get_resource_mut::<ChaosStateMachine<S>>()
+        .and_then(|machine| callbacks(machine).remove(state))
+    else {
+        return;
+    };
+
+    for callback in &mut running {
+        callback(world);
+    }
+
+    if let Some(machine) = world.get_resource_mut::<ChaosStateMachine<S>>() {
+        let added = callbacks(machine).entry(state.clone()).or_default();
+        running.append(added);
+        *added = running;
+    }
+}
+
+fn despawn_scoped<S: ChaosState>(world: &mut ChaosWorld, state: &S) {
+    let Ok(query) = world.query::<(&StateScoped<S>,)>() else {
+        return;
+    };
+    let entities: Vec<EntityID> = query
+        .filter(|(_, (scoped,))| scoped.0 == *state)
+        .map(|(entity, _)| entity)
+        .collect();
+    for entity in entities {
+        world.despawn(entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::RefCell, rc::Rc};
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    enum GameState {
+        Menu,
+        Playing,
+        GameOver,
+    }
+
+    struct Counter(u32);
+
+    struct CountingSystem;
+
+    impl ChaosSystem for CountingSystem {
+        fn initialize(&mut self, _world: &mut ChaosWorld) -> Result<(), &'static str> {
+            Ok(())
+        }
+
+        fn update(&mut self, world: &mut ChaosWorld) -> Result<(), &'static str> {
+            world.get_resource_mut::<Counter>().unwrap().0 += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn transitions_run_exit_then_enter_callbacks() {
+        let mut world = ChaosWorld::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        world.add_state(GameState::Menu);
+        let machine = world
+            .get_resource_mut::<ChaosStateMachine<GameState>>()
+            .unwrap();
+        for state in [GameState::Menu, GameState::Playing] {
+            let enter_log = log.clone();
+            machine.on_enter(state, move |_| {
+                enter_log.borrow_mut().push(("enter", state))
+            });
+            let exit_log = log.clone();
+            machine.on_exit(state, move |_| exit_log.borrow_mut().push(("exit", state)));
+        }
+
+        world.update().unwrap();
+        world.request_state(GameState::Playing);
+        assert_eq!(world.state::<GameState>(), Some(&GameState::Menu));
+        world.update().unwrap();
+        world.request_state(GameState::Playing);
+        world.update().unwrap();
+
+        assert_eq!(world.state::<GameState>(), Some(&GameState::Playing));
+        assert_eq!(
+            *log.borrow(),
+            vec![
+                ("enter", GameState::Menu),
+                ("exit", GameState::Menu),
+                ("enter", GameState::Playing),
+            ]
+        );
+    }
+
+    #[test]
+    fn state_scoped_entities_are_despawned_on_exit() {
+        let mut world = ChaosWorld::new();
+        world.add_state(GameState::Playing);
+        let ship = world.spawn().with(StateScoped(GameState::Playing)).build();
+        let banner = world.spawn().with(StateScoped(GameState::GameOver)).build();
+
+        world.request_state(GameState::GameOver);
+        world.update().unwrap();
+
+        assert!(
+            world
+                .get_component::<StateScoped<GameState>>(ship)
+                .is_none()
+        );
+        assert!(
+            world
+                .get_component::<StateScoped<GameState>>(banner)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn state_limited_systems_and_conditions_follow_the_state() {
+        let mut world = ChaosWorld::new();
+        world.add_state(GameState::Menu);
+        world.insert_resource(Counter(0));
+        world.add_system(InState::new(GameState::Playing, CountingSystem));
+        let mut condition = InStateCondition::new(GameState::Playing);
+
+        world.update().unwrap();
+        assert_eq!(world.get_resource::<Counter>().unwrap().0, 0);
+        assert!(!condition.check_condition(&world, 0.0));
+
+        world.request_state(GameState::Playing);
+        world.update().unwrap();
+        world.update().unwrap();
+        assert_eq!(world.get_resource::<Counter>().unwrap().0, 1);
+        assert!(condition.check_condition(&world, 0.0));
+    }
+}
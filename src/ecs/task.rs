@@ -0,0 +1,489 @@
+use std::{
+    cell::RefCell,
+    future::Future,
+    hash::Hash,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+use chaos_communicator::{communicator::ChaosReceiver, message::ChaosMessage};
+
+use crate::{ecs::world::ChaosWorld, triggers::trigger_event_key::TriggerEventKey};
+
+// Rounds of world accesses answered per task and update, so a task that keeps accessing the
+// world can't stall `ChaosWorld::update`.
+const MAX_REQUEST_ROUNDS: usize = 64;
+
+// Runs a future's world access and stores the result where that future finds it.
+type WorldRequest = Box<dyn FnOnce(&mut ChaosWorld)>;
+
+// State shared between a task's futures and the executor.
+#[derive(Default)]
+struct TaskShared {
+    // Number of the world update the task is polled in.
+    frame: u64,
+    // Seconds the task has been running, in scaled world time.
+    elapsed: f32,
+    // World accesses waiting to be run by the executor, in the order they were made. A
+    // task can have several at once, e.g. when it joins two futures.
+    requests: Vec<WorldRequest>,
+    cancelled: bool,
+    finished: bool,
+}
+
+/// Handed to every task spawned with `ChaosWorld::spawn_task`; its futures suspend the task
+/// until the next frame, for a while or until a signal arrives, and give it world access.
+#[derive(Clone)]
+pub struct TaskContext {
+    shared: Rc<RefCell<TaskShared>>,
+}
+
+impl TaskContext {
+    /// Resumes in the next world update.
+    pub fn next_frame(&self) -> NextFrame {
+        NextFrame {
+            shared: self.shared.clone(),
+            frame: None,
+        }
+    }
+
+    /// Resumes in the first world update after `seconds` of world time (following the time
+    /// scale) have passed.
+    pub fn seconds(&self, seconds: f32) -> Seconds {
+        Seconds {
+            shared: self.shared.clone(),
+            seconds,
+            until: None,
+        }
+    }
+
+    /// Resumes with the message of the next `signal`, e.g. an input signal bound with
+    /// `DeviceEventSystem::bind`. Only signals sent after the task started waiting count.
+    pub fn signal<T: Hash>(&self, signal: T) -> Signal {
+        Signal {
+            shared: self.shared.clone(),
+            key: TriggerEventKey::new(&signal),
+            registered: false,
+            receiver: Rc::default(),
+        }
+    }
+
+    /// Runs `access` with the world and resumes with its result, in the same update. A task
+    /// gets 64 rounds of accesses per update (accesses awaited together count as one round);
+    /// after that it resumes in the next update.
+    pub fn world<F, R>(&self, access: F) -> WorldAccess<F, R>
+    where
+        F: FnOnce(&mut ChaosWorld) -> R + 'static,
+        R: 'static,
+    {
+        WorldAccess {
+            shared: self.shared.clone(),
+            access: Some(access),
+            result: Rc::default(),
+        }
+    }
+}
+
+pub struct NextFrame {
+    shared: Rc<RefCell<TaskShared>>,
+    frame: Option<u64>,
+}
+
+impl Future for NextFrame {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        let current = self.shared.borrow().frame;
+        match self.frame {
+            Some(frame) if current > frame => Poll::Ready(()),
+            Some(_) => Poll::Pending,
+            None => {
+                self.frame = Some(current);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+pub struct Seconds {
+    shared: Rc<RefCell<TaskShared>>,
+    seconds: f32,
+    until: Option<f32>,
+}
+
+impl Future for Seconds {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        let elapsed = self.shared.borrow().elapsed;
+        match self.until {
+            Some(until) if elapsed >= until => Poll::Ready(()),
+            Some(_) => Poll::Pending,
+            None => {
+                // Like `next_frame`, never resumes in the update the wait started in.
+                self.until = Some(elapsed + self.seconds);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+pub struct Signal {
+    shared: Rc<RefCell<TaskShared>>,
+    key: TriggerEventKey,
+    registered: bool,
+    // Filled in by the executor once the registration request ran.
+    receiver: Rc<RefCell<Option<ChaosReceiver>>>,
+}
+
+impl Future for Signal {
+    type Output = ChaosMessage;
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<ChaosMessage> {
+        if !self.registered {
+            self.registered = true;
+            let (key, receiver) = (self.key, self.receiver.clone());
+            self.shared
+                .borrow_mut()
+                .requests
+                .push(Box::new(move |world| {
+                    *receiver.borrow_mut() = Some(world.register_for(key));
+                }));
+            return Poll::Pending;
+        }
+
+        match self
+            .receiver
+            .borrow_mut()
+            .as_mut()
+            .and_then(|receiver| receiver.receive())
+        {
+            Some(message) => Poll::Ready(message),
+            None => Poll::Pending,
+        }
+    }
+}
+
+pub struct WorldAccess<F, R> {
+    shared: Rc<RefCell<TaskShared>>,
+    access: Option<F>,
+    // Filled in by the executor once the access ran.
+    result: Rc<RefCell<Option<R>>>,
+}
+
+// The future never pins its fields.
+impl<F, R> Unpin for WorldAccess<F, R> {}
+
+impl<F, R> Future for WorldAccess<F, R>
+where
+    F: FnOnce(&mut ChaosWorld) -> R + 'static,
+    R: 'static,
+{
+    type Output = R;
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<R> {
+        if let Some(access) = self.access.take() {
+            let result = self.result.clone();
+            self.shared
+                .borrow_mut()
+                .requests
+                .push(Box::new(move |world| {
+                    *result.borrow_mut() = Some(access(world));
+                }));
+            return Poll::Pending;
+        }
+        match self.result.borrow_mut().take() {
+            Some(result) => Poll::Ready(result),
+            None => Poll::Pending,
+        }
+    }
+}
+
+/// Cancels or inspects a task spawned with `ChaosWorld::spawn_task`.
+#[derive(Clone)]
+pub struct TaskHandle {
+    shared: Rc<RefCell<TaskShared>>,
+}
+
+impl TaskHandle {
+    /// Stops the task before its next poll.
+    pub fn cancel(&self) {
+        self.shared.borrow_mut().cancelled = true;
+    }
+
+    /// Whether the task completed or was cancelled.
+    pub fn is_finished(&self) -> bool {
+        let shared = self.shared.borrow();
+        shared.finished || shared.cancelled
+    }
+}
+
+struct Task {
+    future: Pin<Box<dyn Future<Output = ()>>>,
+    shared: Rc<RefCell<TaskShared>>,
+}
+
+/// Polls the tasks of a world once per `ChaosWorld::update`.
+#[derive(Default)]
+pub(crate) struct TaskExecutor {
+    tasks: Vec<Task>,
+    frame: u64,
+}
+
+impl TaskExecutor {
+    pub(crate) fn spawn<F, Fut>(&mut self, task: F) -> TaskHandle
+    where
+        F: FnOnce(TaskContext) -> Fut,
+        Fut: Future<Output = ()> + 'static,
+    {
+        let shared = Rc::new(RefCell::new(TaskShared::default()));
+        let context = TaskContext {
+            shared: shared.clone(),
+        };
+        self.tasks.push(Task {
+            future: Box::pin(task(context)),
+            shared: shared.clone(),
+        });
+        TaskHandle { shared }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    // Tasks spawned while polling are added to the world's (emptied) executor, so they are
+    // merged in after this one was put back.
+    pub(crate) fn append(&mut self, other: &mut TaskExecutor) {
+        self.tasks.append(&mut other.tasks);
+    }
+
+    pub(crate) fn poll(&mut self, world: &mut ChaosWorld, delta_time: f32) {
+        self.frame += 1;
+        let mut context = Context::from_waker(Waker::noop());
+
+        self.tasks.retain_mut(|task| {
+            {
+                let mut shared = task.shared.borrow_mut();
+                if shared.cancelled {
+                    return false;
+                }
+                shared.frame = self.frame;
+                shared.elapsed += delta_time;
+            }
+
+            for _ in 0..MAX_REQUEST_ROUNDS {
+                if task.future.as_mut().poll(&mut context).is_ready() {
+                    task.shared.borrow_mut().finished = true;
+                    return false;
+                }
+                // Requests for world access are answered right away, so the task usually
+                // continues in the same update.
+                let requests = std::mem::take(&mut task.shared.borrow_mut().requests);
+                if requests.is_empty() {
+                    return true;
+                }
+                for request in requests {
+                    request(world);
+                }
+            }
+            // The results of the last round are picked up by the next update's poll.
+            true
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chaos_communicator::message::ChaosMessageBuilder;
+
+    struct Step(u32);
+
+    fn step(world: &ChaosWorld) -> u32 {
+        world.get_resource::<Step>().map_or(0, |step| step.0)
+    }
+
+    fn set_step(world: &mut ChaosWorld, value: u32) {
+        world.insert_resource(Step(value));
+    }
+
+    #[test]
+    fn tasks_wait_for_frames_and_seconds() {
+        let mut world = ChaosWorld::new();
+        let mut executor = TaskExecutor::default();
+        let handle = executor.spawn(|context| async move {
+            context.world(|world| set_step(world, 1)).await;
+            context.next_frame().await;
+            context.world(|world| set_step(world, 2)).await;
+            context.seconds(1.0).await;
+            context.world(|world| set_step(world, 3)).await;
+        });
+
+        executor.poll(&mut world, 0.5);
+        assert_eq!(step(&world), 1);
+        executor.poll(&mut world, 0.5);
+        assert_eq!(step(&world), 2);
+        executor.poll(&mut world, 0.5);
+        assert_eq!(step(&world), 2);
+        executor.poll(&mut world, 0.5);
+        assert_eq!(step(&world), 3);
+        assert!(handle.is_finished());
+        assert!(executor.is_empty());
+    }
+
+    #[test]
+    fn tasks_resume_on_signals() {
+        let mut world = ChaosWorld::new();
+        let mut executor = TaskExecutor::default();
+        executor.spawn(|context| async move {
+            let message = context.signal("fire").await;
+            let value = message.get::<u32>("value").unwrap();
+            context.world(move |world| set_step(world, value)).await;
+        });
+
+        executor.poll(&mut world, 0.1);
+        executor.poll(&mut world, 0.1);
+        assert_eq!(step(&world), 0);
+
+        world.send_message(
+            ChaosMessageBuilder::new()
+                .with_param("value", 7u32)
+                .build_for_event(TriggerEventKey::new(&"fire")),
+        );
+        executor.poll(&mut world, 0.1);
+        assert_eq!(step(&world), 7);
+    }
+
+    enum Either<A, B> {
+        Left(A),
+        Right(B),
+    }
+
+    fn join<A: Future, B: Future>(a: A, b: B) -> impl Future<Output = (A::Output, B::Output)> {
+        let (mut a, mut b) = (Box::pin(a), Box::pin(b));
+        let (mut a_output, mut b_output) = (None, None);
+        std::future::poll_fn(move |cx| {
+            if a_output.is_none()
+                && let Poll::Ready(output) = a.as_mut().poll(cx)
+            {
+                a_output = Some(output);
+            }
+            if b_output.is_none()
+                && let Poll::Ready(output) = b.as_mut().poll(cx)
+            {
+                b_output = Some(output);
+            }
+            if a_output.is_none() || b_output.is_none() {
+                return Poll::Pending;
+            }
+            Poll::Ready((a_output.take().unwrap(), b_output.take().unwrap()))
+        })
+    }
+
+    fn race<A: Future, B: Future>(
+        a: A,
+        b: B,
+    ) -> impl Future<Output = Either<A::Output, B::Output>> {
+        let (mut a, mut b) = (Box::pin(a), Box::pin(b));
+        std::future::poll_fn(move |cx| {
+            if let Poll::Ready(output) = a.as_mut().poll(cx) {
+                return Poll::Ready(Either::Left(output));
+            }
+            b.as_mut().poll(cx).map(Either::Right)
+        })
+    }
+
+    #[test]
+    fn joined_world_accesses_get_their_own_results() {
+        let mut world = ChaosWorld::new();
+        let mut executor = TaskExecutor::default();
+        executor.spawn(|context| async move {
+            let (number, text) = join(
+                context.world(|world| step(world) + 4),
+                context.world(|_| "three"),
+            )
+            .await;
+            context
+                .world(move |world| set_step(world, number + text.len() as u32))
+                .await;
+        });
+
+        executor.poll(&mut world, 0.1);
+        assert_eq!(step(&world), 9);
+        assert!(executor.is_empty());
+    }
+
+    #[test]
+    fn signals_race_world_accesses() {
+        let mut world = ChaosWorld::new();
+        let mut executor = TaskExecutor::default();
+        executor.spawn(|context| async move {
+            let Either::Right(first) = race(context.signal("fire"), context.world(|_| 5)).await
+            else {
+                panic!("the signal was not sent");
+            };
+            context.world(move |world| set_step(world, first)).await;
+
+            let (message, second) = join(context.signal("fire"), context.world(|_| 2)).await;
+            let value = message.get::<u32>("value").unwrap();
+            context
+                .world(move |world| set_step(world, value + second))
+                .await;
+        });
+
+        executor.poll(&mut world, 0.1);
+        assert_eq!(step(&world), 5);
+
+        world.send_message(
+            ChaosMessageBuilder::new()
+                .with_param("value", 7u32)
+                .build_for_event(TriggerEventKey::new(&"fire")),
+        );
+        executor.poll(&mut world, 0.1);
+        assert_eq!(step(&world), 9);
+        assert!(executor.is_empty());
+    }
+
+    #[test]
+    fn world_update_polls_spawned_tasks_until_cancelled() {
+        let mut world = ChaosWorld::new();
+        let handle = world.spawn_task(|context| async move {
+            loop {
+                context
+                    .world(|world| {
+                        let step = world.get_resource::<Step>().map_or(0, |step| step.0);
+                        world.insert_resource(Step(step + 1));
+                    })
+                    .await;
+                context.next_frame().await;
+            }
+        });
+
+        world.update().unwrap();
+        world.update().unwrap();
+        assert_eq!(step(&world), 2);
+
+        handle.cancel();
+        world.update().unwrap();
+        assert_eq!(step(&world), 2);
+        assert!(handle.is_finished());
+    }
+
+    #[test]
+    fn world_accesses_are_capped_per_update() {
+        let mut world = ChaosWorld::new();
+        world.spawn_task(|context| async move {
+            loop {
+                context
+                    .world(|world| set_step(world, step(world) + 1))
+                    .await;
+            }
+        });
+
+        world.update().unwrap();
+        assert_eq!(step(&world), MAX_REQUEST_ROUNDS as u32);
+        world.update().unwrap();
+        assert_eq!(step(&world), 2 * MAX_REQUEST_ROUNDS as u32);
+    }
+}
@@ -0,0 +1,290 @@
+use std::hash::Hash;
+
+use chaos_communicator::message::ChaosMessageBuilder;
+
+use crate::{
+    ecs::{EntityID, system::ChaosSystem, world::ChaosWorld},
+    triggers::trigger_event_key::TriggerEventKey,
+};
+
+pub type TimerCallback = Box<dyn FnMut(&mut ChaosWorld, EntityID)>;
+
+/// What a [`Timer`] does when it finishes.
+pub enum TimerAction {
+    /// Sends a message for the key, with the timer's entity as the `entity_id` param.
+    /// Receive it with `ChaosWorld::register_for_trigger(signal)`.
+    Signal(TriggerEventKey),
+    Callback(TimerCallback),
+}
+
+/// Counts down `WorldTime::delta_time`, so it follows the world's time scale, and runs its
+/// action when done. Needs a [`TimerSystem`] in the world. Timers that aren't about a
+/// specific entity (e.g. spawning a wave every 10 seconds) can live on an entity of their
+/// own.
+///
+/// ```rust
+/// use chaos_engine::ecs::{timer::Timer, world::ChaosWorld};
+///
+/// let mut world = ChaosWorld::new();
+/// let bullet = world.spawn().build();
+/// world
+///     .add_component(
+///         bullet,
+///         Timer::once(2.0).with_callback(|world, bullet| world.despawn(bullet)),
+///     )
+///     .unwrap();
+/// ```
+pub struct Timer {
+    duration: f32,
+    elapsed: f32,
+    repeating: bool,
+    paused: bool,
+    finished: bool,
+    // Taken out while it runs, so the action can borrow the world.
+    action: Option<TimerAction>,
+}
+
+impl Timer {
+    pub fn once(duration: f32) -> Self {
+        Self::new(duration, false)
+    }
+
+    /// Finishes every `interval` seconds until paused or removed.
+    pub fn repeating(interval: f32) -> Self {
+        Self::new(interval, true)
+    }
+
+    fn new(duration: f32, repeating: bool) -> Self {
+        Self {
+            duration: duration.max(0.0),
+            elapsed: 0.0,
+            repeating,
+            paused: false,
+            finished: false,
+            action: None,
+        }
+    }
+
+    pub fn with_signal<T: Hash>(mut self, signal: &T) -> Self {
+        self.action = Some(TimerAction::Signal(TriggerEventKey::new(signal)));
+        self
+    }
+
+    pub fn with_callback(
+        mut self,
+        callback: impl FnMut(&mut ChaosWorld, EntityID) + 'static,
+    ) -> Self {
+        self.action = Some(TimerAction::Callback(Box::new(callback)));
+        self
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Starts counting from zero again, also for finished one-shot timers.
+    pub fn restart(&mut self) {
+        self.elapsed = 0.0;
+        self.finished = false;
+    }
+
+    /// Whether a one-shot timer has run out. Repeating timers never finish.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    pub fn remaining(&self) -> f32 {
+        (self.duration - self.elapsed).max(0.0)
+    }
+
+    // Advances the timer and returns how often it finished in this step.
+    fn tick(&mut self, delta_time: f32) -> u32 {
+        if self.paused || self.finished {
+            return 0;
+        }
+
+        self.elapsed += delta_time;
+        if self.elapsed < self.duration {
+            return 0;
+        }
+
+        if !self.repeating {
+            self.elapsed = self.duration;
+            self.finished = true;
+            return 1;
+        }
+        if self.duration <= 0.0 {
+            self.elapsed = 0.0;
+            return 1;
+        }
+        // A long frame can cover several intervals; each of them counts.
+        let count = (self.elapsed / self.duration) as u32;
+        self.elapsed -= count as f32 * self.duration;
+        count
+    }
+}
+
+/// Advances every [`Timer`] in the world and runs the actions of the finished ones.
+pub struct TimerSystem;
+
+impl TimerSystem {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub(crate) fn advance(world: &mut ChaosWorld, delta_time: f32) -> Result<(), &'static str> {
+        let mut finished = Vec::new();
+        for (entity, (timer,)) in world
+            .query::<(&mut Timer,)>()
+            .map_err(|_| "Failed to query timers")?
+        {
+            let count = timer.tick(delta_time);
+            if count > 0 {
+                finished.push((entity, count));
+            }
+        }
+
+        for (entity, count) in finished {
+            let Some(mut action) = world
+                .get_component_mut::<Timer>(entity)
+                .and_then(|timer| timer.action.take())
+            else {
+                continue;
+            };
+
+            for _ in 0..count {
+                match &mut action {
+                    TimerAction::Signal(key) => {
+                        let message = ChaosMessageBuilder::new()
+                            .with_param("entity_id", entity)
+                            .build_for_event(*key);
+                        if let Err(error) = world.try_send_message(message) {
+                            log::debug!("Timer signal was not delivered: {}", error);
+                        }
+                    }
+                    TimerAction::Callback(callback) => callback(world, entity),
+                }
+            }
+
+            // The callback may have despawned the entity or replaced its timer, in which
+            // case the old action is dropped.
+            if let Some(timer) = world
+                .get_component_mut::<Timer>(entity)
+                .filter(|timer| timer.action.is_none())
+            {
+                timer.action = Some(action);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for TimerSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChaosSystem for TimerSystem {
+    fn initialize(&mut self, _world: &mut ChaosWorld) -> Result<(), &'static str> {
+        Ok(())
+    }
+
+    fn update(&mut self, world: &mut ChaosWorld) -> Result<(), &'static str> {
+        let delta_time = world.get_time().delta_time();
+        Self::advance(world, delta_time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Spawned;
+
+    fn spawned(world: &ChaosWorld) -> usize {
+        world
+            .get_all_components_of_type::<Spawned>()
+            .map_or(0, |components| components.len())
+    }
+
+    #[test]
+    fn one_shot_timer_runs_its_callback_once() {
+        let mut world = ChaosWorld::new();
+        let bullet = world
+            .spawn()
+            .with(Timer::once(2.0).with_callback(|world, bullet| world.despawn(bullet)))
+            .build();
+
+        TimerSystem::advance(&mut world, 1.5).unwrap();
+        assert!(world.get_component::<Timer>(bullet).is_some());
+
+        TimerSystem::advance(&mut world, 1.0).unwrap();
+        assert!(world.get_component::<Timer>(bullet).is_none());
+    }
+
+    #[test]
+    fn repeating_timer_fires_for_every_interval() {
+        let mut world = ChaosWorld::new();
+        let spawner = world
+            .spawn()
+            .with(Timer::repeating(1.0).with_callback(|world, _| {
+                world.spawn().with(Spawned).build();
+            }))
+            .build();
+
+        TimerSystem::advance(&mut world, 0.5).unwrap();
+        TimerSystem::advance(&mut world, 0.5).unwrap();
+        assert_eq!(spawned(&world), 1);
+
+        TimerSystem::advance(&mut world, 2.25).unwrap();
+        assert_eq!(spawned(&world), 3);
+        let timer = world.get_component::<Timer>(spawner).unwrap();
+        assert!((timer.elapsed() - 0.25).abs() < 1e-6);
+        assert!(!timer.is_finished());
+    }
+
+    #[test]
+    fn paused_timer_does_not_advance() {
+        let mut world = ChaosWorld::new();
+        let entity = world.spawn().with(Timer::once(1.0)).build();
+
+        world.get_component_mut::<Timer>(entity).unwrap().pause();
+        TimerSystem::advance(&mut world, 5.0).unwrap();
+        assert_eq!(world.get_component::<Timer>(entity).unwrap().elapsed(), 0.0);
+
+        world.get_component_mut::<Timer>(entity).unwrap().resume();
+        TimerSystem::advance(&mut world, 5.0).unwrap();
+        let timer = world.get_component::<Timer>(entity).unwrap();
+        assert!(timer.is_finished());
+        assert_eq!(timer.remaining(), 0.0);
+    }
+
+    #[test]
+    fn finished_timer_sends_its_signal() {
+        let mut world = ChaosWorld::new();
+        let mut receiver = world.register_for_trigger("wave");
+        let entity = world
+            .spawn()
+            .with(Timer::once(1.0).with_signal(&"wave"))
+            .build();
+
+        TimerSystem::advance(&mut world, 1.0).unwrap();
+
+        let message = receiver.receive().expect("timer should send its signal");
+        assert_eq!(message.get::<EntityID>("entity_id"), Some(entity));
+        assert!(receiver.receive().is_none());
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    any::{TypeId, type_name},
+    any::{Any, TypeId, type_name},
     collections::HashMap,
     hash::{Hash, Hasher},
     sync::{Arc, Mutex},
@@ -12,13 +12,16 @@ use chaos_communicator::{
 };
 
 use crate::{
+    device::input_state::InputState,
     ecs::{
         EntityID,
         component::{ChaosComponentManager, Component},
         entity::EntityBuilder,
         errors::ComponentErrors,
         query::{QueryError, QueryIter, QueryTuple},
+        state::{ChaosState, ChaosStateMachine, apply_state_transition},
         system::ChaosSystem,
+        task::{TaskContext, TaskExecutor, TaskHandle},
     },
     triggers::trigger_event_key::TriggerEventKey,
 };
@@ -26,23 +29,41 @@ use crate::{
 pub struct WorldTime {
     current_time: Instant,
     last_time: Instant,
+    time_scale: f32,
 }
 
 impl WorldTime {
+    /// Seconds since the previous update, multiplied by the time scale.
     pub fn delta_time(&self) -> f32 {
-        return self
-            .current_time
+        self.unscaled_delta_time() * self.time_scale
+    }
+
+    /// Seconds since the previous update in real time, e.g. for menus while the game is paused.
+    pub fn unscaled_delta_time(&self) -> f32 {
+        self.current_time
             .duration_since(self.last_time)
-            .as_secs_f32();
+            .as_secs_f32()
+    }
+
+    pub fn time_scale(&self) -> f32 {
+        self.time_scale
     }
 }
 
 pub struct ChaosWorld {
     component_manager: ChaosComponentManager,
-    systems: HashMap<TypeId, Box<dyn ChaosSystem>>,
+    // Run in the order they were added; `system_indices` finds a type's slot. A slot is
+    // empty while its system runs.
+    systems: Vec<Option<Box<dyn ChaosSystem>>>,
+    system_indices: HashMap<TypeId, usize>,
     specialized_entities: HashMap<SpecializedEntityKey, EntityID>,
     communicator: Arc<Mutex<ChaosCommunicator>>,
     time: WorldTime,
+    // World-global singletons (e.g. `InputState`), one per type.
+    resources: HashMap<TypeId, Box<dyn Any>>,
+    // One per state type added with `add_state`, run after the systems.
+    state_transitions: Vec<fn(&mut ChaosWorld)>,
+    tasks: TaskExecutor,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -73,13 +94,18 @@ impl ChaosWorld {
         let communicator = Arc::new(Mutex::new(ChaosCommunicator::new()));
         ChaosWorld {
             component_manager: ChaosComponentManager::new(communicator.clone()),
-            systems: HashMap::new(),
+            systems: Vec::new(),
+            system_indices: HashMap::new(),
             specialized_entities: HashMap::new(),
             communicator,
             time: WorldTime {
                 current_time: Instant::now(),
                 last_time: Instant::now(),
+                time_scale: 1.0,
             },
+            resources: HashMap::new(),
+            state_transitions: Vec::new(),
+            tasks: TaskExecutor::default(),
         }
     }
 
@@ -87,16 +113,34 @@ impl ChaosWorld {
         &self.time
     }
 
+    /// Speeds up (> 1), slows down (< 1) or pauses (0) everything driven by
+    /// `WorldTime::delta_time`. Negative scales are clamped to 0.
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        self.time.time_scale = time_scale.max(0.0);
+    }
+
     pub fn initialize_systems(&mut self) -> Result<(), &'static str> {
-        // slightly hacky way to avoid borrowing self.systems while iterating over it
-        let mut systems = std::mem::take(&mut self.systems);
-        for system in systems.values_mut() {
-            if let Err(error) = system.initialize(self) {
-                self.systems = systems;
-                return Err(error);
+        self.run_systems(|system, world| system.initialize(world))
+    }
+
+    // Runs `run` on every system in order, stopping at the first error. Each system is taken
+    // out of its slot while it runs, so it can borrow the world; systems it adds run from
+    // the next call on.
+    fn run_systems(
+        &mut self,
+        run: impl Fn(&mut dyn ChaosSystem, &mut ChaosWorld) -> Result<(), &'static str>,
+    ) -> Result<(), &'static str> {
+        for index in 0..self.systems.len() {
+            let Some(mut system) = self.systems[index].take() else {
+                continue;
+            };
+            let result = run(system.as_mut(), self);
+            // A system replaced while it ran keeps the replacement.
+            if self.systems[index].is_none() {
+                self.systems[index] = Some(system);
             }
+            result?;
         }
-        self.systems = systems;
         Ok(())
     }
 
@@ -136,10 +180,19 @@ impl ChaosWorld {
         self.register_for(TriggerEventKey::new(&event))
     }
 
+    /// Systems update in the order they were added. Adding a system of a type that was
+    /// already added replaces it, keeping its place in the order.
     pub fn add_system<T: ChaosSystem>(&mut self, system: T) -> &mut Self {
         log::info!("Adding system: {}", type_name::<T>());
 
-        self.systems.insert(TypeId::of::<T>(), Box::new(system));
+        match self.system_indices.get(&TypeId::of::<T>()) {
+            Some(index) => self.systems[*index] = Some(Box::new(system)),
+            None => {
+                self.system_indices
+                    .insert(TypeId::of::<T>(), self.systems.len());
+                self.systems.push(Some(Box::new(system)));
+            }
+        }
         self
     }
 
@@ -147,17 +200,103 @@ impl ChaosWorld {
         self.time = WorldTime {
             current_time: Instant::now(),
             last_time: self.time.current_time,
+            time_scale: self.time.time_scale,
         };
-        // slightly hacky way to avoid borrowing self.systems while iterating over it
-        let mut systems = std::mem::take(&mut self.systems);
-        for system in systems.values_mut() {
-            if let Err(error) = system.update(self) {
-                self.systems = systems;
-                return Err(error);
-            }
+        let result = self.run_systems(|system, world| system.update(world));
+
+        if !self.tasks.is_empty() {
+            let mut tasks = std::mem::take(&mut self.tasks);
+            tasks.poll(self, self.time.delta_time());
+            tasks.append(&mut self.tasks);
+            self.tasks = tasks;
+        }
+
+        for apply_state_transition in self.state_transitions.clone() {
+            apply_state_transition(self);
+        }
+
+        // Systems and tasks have seen this frame's input edges (just pressed/released, wheel
+        // delta), so they are cleared before the next update.
+        if let Some(input_state) = self.get_resource_mut::<InputState>() {
+            input_state.end_frame();
+        }
+        result
+    }
+
+    /// Insert a world-global resource, replacing (and returning) the previous resource of
+    /// the same type.
+    pub fn insert_resource<T: Any>(&mut self, resource: T) -> Option<T> {
+        self.resources
+            .insert(TypeId::of::<T>(), Box::new(resource))
+            .and_then(|previous| previous.downcast::<T>().ok())
+            .map(|previous| *previous)
+    }
+
+    pub fn remove_resource<T: Any>(&mut self) -> Option<T> {
+        self.resources
+            .remove(&TypeId::of::<T>())
+            .and_then(|resource| resource.downcast::<T>().ok())
+            .map(|resource| *resource)
+    }
+
+    pub fn get_resource<T: Any>(&self) -> Option<&T> {
+        self.resources
+            .get(&TypeId::of::<T>())
+            .and_then(|resource| resource.downcast_ref::<T>())
+    }
+
+    pub fn get_resource_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.resources
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|resource| resource.downcast_mut::<T>())
+    }
+
+    // tasks
+    /// Starts a coroutine-style task, polled once per update after the systems ran.
+    ///
+    /// ```rust
+    /// use chaos_engine::ecs::world::ChaosWorld;
+    ///
+    /// let mut world = ChaosWorld::new();
+    /// world.spawn_task(|context| async move {
+    ///     context.seconds(1.5).await;
+    ///     let ship = context.world(|world| world.spawn().build()).await;
+    ///     context.signal("fire").await;
+    ///     context.world(move |world| world.despawn(ship)).await;
+    /// });
+    /// ```
+    pub fn spawn_task<F, Fut>(&mut self, task: F) -> TaskHandle
+    where
+        F: FnOnce(TaskContext) -> Fut,
+        Fut: Future<Output = ()> + 'static,
+    {
+        self.tasks.spawn(task)
+    }
+
+    // states
+    /// Adds a state machine for states of type `S`, see [`ChaosStateMachine`]. Adding the
+    /// same state type again replaces the machine, including its callbacks.
+    pub fn add_state<S: ChaosState>(&mut self, initial: S) {
+        if self
+            .insert_resource(ChaosStateMachine::new(initial))
+            .is_none()
+        {
+            self.state_transitions.push(apply_state_transition::<S>);
+        }
+    }
+
+    pub fn state<S: ChaosState>(&self) -> Option<&S> {
+        self.get_resource::<ChaosStateMachine<S>>()
+            .map(|machine| machine.current())
+    }
+
+    /// Changes to `state` at the end of the current update. Only logs a warning if no state
+    /// machine for `S` was added.
+    pub fn request_state<S: ChaosState>(&mut self, state: S) {
+        match self.get_resource_mut::<ChaosStateMachine<S>>() {
+            Some(machine) => machine.request(state),
+            None => log::warn!("No state machine for {}", type_name::<S>()),
         }
-        self.systems = systems;
-        Ok(())
     }
 
     // creation methods
@@ -271,3 +410,42 @@ impl ChaosWorld {
         self.component_manager.subscribe_to_remove::<T>()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Records its name in the `Vec<&str>` resource on every update.
+    struct NamedSystem<const N: usize>(&'static str);
+
+    impl<const N: usize> ChaosSystem for NamedSystem<N> {
+        fn initialize(&mut self, _world: &mut ChaosWorld) -> Result<(), &'static str> {
+            Ok(())
+        }
+
+        fn update(&mut self, world: &mut ChaosWorld) -> Result<(), &'static str> {
+            let log = world
+                .get_resource_mut::<Vec<&'static str>>()
+                .ok_or("Missing log")?;
+            log.push(self.0);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn systems_update_in_the_order_they_were_added() {
+        let mut world = ChaosWorld::new();
+        world.insert_resource(Vec::<&'static str>::new());
+        world
+            .add_system(NamedSystem::<3>("third"))
+            .add_system(NamedSystem::<1>("first"))
+            .add_system(NamedSystem::<2>("second"))
+            .add_system(NamedSystem::<1>("first again"));
+
+        world.update().unwrap();
+        assert_eq!(
+            world.get_resource::<Vec<&'static str>>().unwrap(),
+            &vec!["third", "first again", "second"]
+        );
+    }
+}
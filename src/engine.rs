@@ -1,8 +1,15 @@
 use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
 use crate::{
-    device::system::DeviceEventSystem,
+    device::{
+        cursor,
+        events::{ChaosCursorGrabMode, ChaosCursorIcon},
+        input_state::InputState,
+        system::DeviceEventSystem,
+    },
     ecs::{errors::ComponentErrors, world::ChaosWorld},
+    math::{Vec3, color::Color, matrix::Mat4},
+    random::resource::ChaosRandom,
     rendering::{
         effect_factory::EffectFactory,
         rendering_system::{ChaosRenderContext, ChaosRenderSystem, ChaosRenderableContainer},
@@ -12,7 +19,7 @@ use crate::{
 use winit::{
     application::ApplicationHandler,
     dpi::PhysicalSize,
-    event::WindowEvent,
+    event::{DeviceEvent, DeviceId, WindowEvent},
     event_loop::{ActiveEventLoop, EventLoop},
     raw_window_handle::HasDisplayHandle,
     window::{WindowAttributes, WindowId},
@@ -27,14 +34,22 @@ pub struct ChaosEngine {
     width: u32,
     height: u32,
     directories: HashMap<PathBuf, PathBuf>,
+    ime_allowed: bool,
+    cursor_grab: ChaosCursorGrabMode,
+    cursor_visible: bool,
+    cursor_icon: ChaosCursorIcon,
+    clear_color: Color,
 }
 
 impl ChaosEngine {
     pub fn new(title: &str, width: u32, height: u32) -> Result<ChaosEngine, &'static str> {
         let device_event_system = DeviceEventSystem::new();
+        let mut world = ChaosWorld::new();
+        world.insert_resource(InputState::default());
+        world.insert_resource(ChaosRandom::from_time());
 
         Ok(ChaosEngine {
-            world: ChaosWorld::new(),
+            world,
             device_event_system,
             window: None,
             rendering_system: None,
@@ -42,6 +57,11 @@ impl ChaosEngine {
             width,
             height,
             directories: HashMap::new(),
+            ime_allowed: false,
+            cursor_grab: ChaosCursorGrabMode::None,
+            cursor_visible: true,
+            cursor_icon: ChaosCursorIcon::Default,
+            clear_color: Color::BLACK,
         })
     }
 
@@ -54,15 +74,23 @@ impl ChaosEngine {
         self.window = Some(Arc::new(
             event_loop.create_window(window_attributes).unwrap(),
         ));
+        let window = self.window.as_ref().unwrap();
+        window.set_ime_allowed(self.ime_allowed);
+        window.set_cursor_visible(self.cursor_visible);
+        window.set_cursor(self.cursor_icon);
+        if let Err(err) = Self::apply_cursor_grab(window, self.cursor_grab) {
+            log::warn!("Failed to grab cursor: {}", err);
+        }
 
         let add_subscription = self.world.subscribe_to_add::<ChaosRenderableContainer>();
 
-        let rendering_system = ChaosRenderSystem::new(
+        let mut rendering_system = ChaosRenderSystem::new(
             &event_loop.display_handle().unwrap(),
             self.window.clone().unwrap(),
             add_subscription,
             &self.directories,
         );
+        rendering_system.set_clear_color(self.clear_color);
         self.rendering_system = Some(rendering_system);
 
         // push the directories to the effect factory so we can use shaders
@@ -73,6 +101,84 @@ impl ChaosEngine {
         self.directories.insert(root, path);
     }
 
+    /// Enable or disable IME composition for the window. While allowed, text input arrives
+    /// as `ChaosInputEvent::Ime` events instead of plain key presses on platforms with an
+    /// input method editor. Can be called before the window is created.
+    pub fn set_ime_allowed(&mut self, allowed: bool) {
+        self.ime_allowed = allowed;
+        if let Some(window) = &self.window {
+            window.set_ime_allowed(allowed);
+        }
+    }
+
+    /// Grab the cursor: `Confined` keeps it inside the window, `Locked` pins it in place
+    /// (use `ChaosBindingEvent::mouse_motion` bindings for relative movement). Platforms
+    /// support only one of the two, so the other mode is used as a fallback. Can be called
+    /// before the window is created.
+    pub fn set_cursor_grab(&mut self, mode: ChaosCursorGrabMode) -> Result<(), &'static str> {
+        self.cursor_grab = mode;
+        match &self.window {
+            Some(window) => Self::apply_cursor_grab(window, mode),
+            None => Ok(()),
+        }
+    }
+
+    pub fn set_cursor_visible(&mut self, visible: bool) {
+        self.cursor_visible = visible;
+        if let Some(window) = &self.window {
+            window.set_cursor_visible(visible);
+        }
+    }
+
+    pub fn set_cursor_icon(&mut self, icon: ChaosCursorIcon) {
+        self.cursor_icon = icon;
+        if let Some(window) = &self.window {
+            window.set_cursor(icon);
+        }
+    }
+
+    /// The color every frame is cleared to before rendering. Can be called before the
+    /// window is created.
+    pub fn set_clear_color(&mut self, color: impl Into<Color>) {
+        self.clear_color = color.into();
+        if let Some(rendering_system) = &mut self.rendering_system {
+            rendering_system.set_clear_color(self.clear_color);
+        }
+    }
+
+    /// The point on the plane `z = plane_z` under the cursor, for a camera rendering with
+    /// `view` and `projection`. `None` until the cursor has entered the window.
+    pub fn cursor_world_position(
+        &self,
+        view: &Mat4,
+        projection: &Mat4,
+        plane_z: f32,
+    ) -> Option<Vec3> {
+        let window_size = self.window.as_ref()?.inner_size();
+        cursor::cursor_to_world(
+            self.device_event_system.cursor_position()?,
+            (window_size.width, window_size.height),
+            view,
+            projection,
+            plane_z,
+        )
+    }
+
+    fn apply_cursor_grab(
+        window: &winit::window::Window,
+        mode: ChaosCursorGrabMode,
+    ) -> Result<(), &'static str> {
+        let fallback = match mode {
+            ChaosCursorGrabMode::None => ChaosCursorGrabMode::None,
+            ChaosCursorGrabMode::Confined => ChaosCursorGrabMode::Locked,
+            ChaosCursorGrabMode::Locked => ChaosCursorGrabMode::Confined,
+        };
+        window
+            .set_cursor_grab(mode)
+            .or_else(|_| window.set_cursor_grab(fallback))
+            .map_err(|_| "Cursor grab is not supported on this platform")
+    }
+
     pub fn device_event_system(&mut self) -> &mut DeviceEventSystem {
         &mut self.device_event_system
     }
@@ -101,9 +207,22 @@ impl ChaosEngine {
                 log::debug!("Input signal was not delivered: {} {:?}", error, event);
             }
         }
+        if let Some(input_state) = self.world.get_resource_mut::<InputState>() {
+            self.device_event_system.write_input_state(input_state);
+        }
         self.world.update()
     }
 
+    // Raw device events arrive at a much higher rate than window events, so their signals
+    // are only queued here and handled on the next world update.
+    fn send_device_event(&mut self, event: &DeviceEvent) {
+        for message in self.device_event_system.update_device_event(event) {
+            if let Err(error) = self.world.try_send_message(message) {
+                log::debug!("Input signal was not delivered: {} {:?}", error, event);
+            }
+        }
+    }
+
     fn render(&mut self) -> Result<(), &'static str> {
         let rendering_system = self
             .rendering_system
@@ -163,4 +282,13 @@ impl ApplicationHandler for ChaosEngine {
             _ => (),
         }
     }
+
+    fn device_event(
+        &mut self,
+        _event_loop: &ActiveEventLoop,
+        _device_id: DeviceId,
+        event: DeviceEvent,
+    ) {
+        self.send_device_event(&event);
+    }
 }
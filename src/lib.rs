@@ -3,13 +3,20 @@ extern crate spirv_reflect;
 pub extern crate vulkano;
 extern crate winit;
 
+pub mod ai;
 pub mod device;
 pub mod ecs;
 pub mod engine;
 pub mod logger;
 pub mod math;
+pub mod physics;
+pub mod procgen;
+pub mod random;
 pub mod rendering;
+pub mod spatial;
+pub mod transform;
 pub mod triggers;
+pub mod tween;
 pub use vulkano_macros::{BufferContents, Vertex};
 
 pub use chaos_communicator::{
@@ -0,0 +1,639 @@
+//! Colors. [`Srgba`] is what hex codes and color pickers use, [`LinearRgba`] is what
+//! shaders, blending and interpolation expect, and [`Hsva`]/[`Hsla`] make hue and
+//! brightness easy to adjust. [`Color`] holds any of them; convert it to `LinearRgba`
+//! before passing it to a shader.
+//!
+//! ```rust
+//! use chaos_engine::math::color::{Color, Hsva, LinearRgba, Srgba};
+//!
+//! let orange = Color::hex("#ff8000").unwrap();
+//! assert_eq!(orange.to_srgba().to_hex(), "#ff8000");
+//! // Half-bright sRGB is much darker than half in linear light.
+//! assert!((Srgba::rgb(0.5, 0.5, 0.5).to_linear().red - 0.214).abs() < 1e-3);
+//! let hsv: Hsva = orange.to_hsva();
+//! assert!((hsv.hue - 30.0).abs() < 0.5);
+//! let linear: LinearRgba = orange.into();
+//! assert_eq!(linear.blue, 0.0);
+//! ```
+
+use std::fmt::Display;
+
+use vulkano_macros::BufferContents;
+
+use crate::math::Vec4;
+
+#[derive(Debug, PartialEq)]
+pub enum ColorError {
+    /// Hex colors have 3, 4, 6 or 8 digits, after an optional `#`.
+    InvalidHexLength(usize),
+    InvalidHexDigit(char),
+}
+
+impl Display for ColorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColorError::InvalidHexLength(length) => {
+                write!(f, "Hex color needs 3, 4, 6 or 8 digits, got {length}")
+            }
+            ColorError::InvalidHexDigit(digit) => {
+                write!(f, "Invalid hex digit '{digit}' in color")
+            }
+        }
+    }
+}
+
+/// Red, green and blue in linear light, with straight (not premultiplied) alpha unless
+/// [`premultiplied`](Self::premultiplied) was called.
+#[derive(Debug, Clone, Copy, PartialEq, BufferContents)]
+#[repr(C)]
+pub struct LinearRgba {
+    pub red: f32,
+    pub green: f32,
+    pub blue: f32,
+    pub alpha: f32,
+}
+
+/// Red, green and blue encoded with the sRGB transfer function, as in hex codes and
+/// images.
+#[derive(Debug, Clone, Copy, PartialEq, BufferContents)]
+#[repr(C)]
+pub struct Srgba {
+    pub red: f32,
+    pub green: f32,
+    pub blue: f32,
+    pub alpha: f32,
+}
+
+/// Hue in degrees, saturation and value in `0..=1`, over sRGB.
+#[derive(Debug, Clone, Copy, PartialEq, BufferContents)]
+#[repr(C)]
+pub struct Hsva {
+    pub hue: f32,
+    pub saturation: f32,
+    pub value: f32,
+    pub alpha: f32,
+}
+
+/// Hue in degrees, saturation and lightness in `0..=1`, over sRGB.
+#[derive(Debug, Clone, Copy, PartialEq, BufferContents)]
+#[repr(C)]
+pub struct Hsla {
+    pub hue: f32,
+    pub saturation: f32,
+    pub lightness: f32,
+    pub alpha: f32,
+}
+
+/// A color in any of the supported spaces.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Color {
+    LinearRgba(LinearRgba),
+    Srgba(Srgba),
+    Hsva(Hsva),
+    Hsla(Hsla),
+}
+
+// The sRGB transfer functions, per channel.
+fn srgb_to_linear(channel: f32) -> f32 {
+    if channel <= 0.04045 {
+        channel / 12.92
+    } else {
+        ((channel + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(channel: f32) -> f32 {
+    if channel <= 0.0031308 {
+        channel * 12.92
+    } else {
+        1.055 * channel.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+// The hue shared by HSV and HSL, in degrees.
+fn hue(red: f32, green: f32, blue: f32, max: f32, delta: f32) -> f32 {
+    if delta == 0.0 {
+        0.0
+    } else if max == red {
+        60.0 * ((green - blue) / delta).rem_euclid(6.0)
+    } else if max == green {
+        60.0 * ((blue - red) / delta + 2.0)
+    } else {
+        60.0 * ((red - green) / delta + 4.0)
+    }
+}
+
+// RGB from a hue, the chroma and the amount added to every channel.
+fn from_hue(hue: f32, chroma: f32, offset: f32) -> (f32, f32, f32) {
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let second = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (red, green, blue) = match sector as u32 {
+        0 => (chroma, second, 0.0),
+        1 => (second, chroma, 0.0),
+        2 => (0.0, chroma, second),
+        3 => (0.0, second, chroma),
+        4 => (second, 0.0, chroma),
+        _ => (chroma, 0.0, second),
+    };
+    (red + offset, green + offset, blue + offset)
+}
+
+impl LinearRgba {
+    pub const BLACK: Self = Self::rgb(0.0, 0.0, 0.0);
+    pub const WHITE: Self = Self::rgb(1.0, 1.0, 1.0);
+    pub const TRANSPARENT: Self = Self::new(0.0, 0.0, 0.0, 0.0);
+
+    pub const fn new(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
+    pub const fn rgb(red: f32, green: f32, blue: f32) -> Self {
+        Self::new(red, green, blue, 1.0)
+    }
+
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    /// Multiplies the color channels by alpha, for blending with `One, OneMinusSrcAlpha`.
+    pub fn premultiplied(&self) -> Self {
+        Self::new(
+            self.red * self.alpha,
+            self.green * self.alpha,
+            self.blue * self.alpha,
+            self.alpha,
+        )
+    }
+
+    /// Undoes [`premultiplied`](Self::premultiplied). Fully transparent colors become
+    /// transparent black.
+    pub fn unpremultiplied(&self) -> Self {
+        if self.alpha == 0.0 {
+            return Self::TRANSPARENT;
+        }
+        Self::new(
+            self.red / self.alpha,
+            self.green / self.alpha,
+            self.blue / self.alpha,
+            self.alpha,
+        )
+    }
+
+    pub fn lerp(from: &Self, to: &Self, t: f32) -> Self {
+        Self::new(
+            from.red + (to.red - from.red) * t,
+            from.green + (to.green - from.green) * t,
+            from.blue + (to.blue - from.blue) * t,
+            from.alpha + (to.alpha - from.alpha) * t,
+        )
+    }
+
+    pub fn to_srgba(&self) -> Srgba {
+        Srgba::new(
+            linear_to_srgb(self.red),
+            linear_to_srgb(self.green),
+            linear_to_srgb(self.blue),
+            self.alpha,
+        )
+    }
+}
+
+impl Srgba {
+    pub const BLACK: Self = Self::rgb(0.0, 0.0, 0.0);
+    pub const WHITE: Self = Self::rgb(1.0, 1.0, 1.0);
+    pub const TRANSPARENT: Self = Self::new(0.0, 0.0, 0.0, 0.0);
+
+    pub const fn new(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
+    pub const fn rgb(red: f32, green: f32, blue: f32) -> Self {
+        Self::new(red, green, blue, 1.0)
+    }
+
+    pub fn rgba_u8(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Self::new(
+            red as f32 / 255.0,
+            green as f32 / 255.0,
+            blue as f32 / 255.0,
+            alpha as f32 / 255.0,
+        )
+    }
+
+    /// Parses `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`; the `#` is optional.
+    pub fn hex(hex: &str) -> Result<Self, ColorError> {
+        let digits = hex
+            .strip_prefix('#')
+            .unwrap_or(hex)
+            .chars()
+            .map(|digit| {
+                digit
+                    .to_digit(16)
+                    .map(|value| value as u8)
+                    .ok_or(ColorError::InvalidHexDigit(digit))
+            })
+            .collect::<Result<Vec<u8>, ColorError>>()?;
+
+        let channels: Vec<u8> = match digits.len() {
+            3 | 4 => digits.iter().map(|digit| digit * 17).collect(),
+            6 | 8 => digits
+                .chunks(2)
+                .map(|pair| pair[0] * 16 + pair[1])
+                .collect(),
+            length => return Err(ColorError::InvalidHexLength(length)),
+        };
+        let alpha = channels.get(3).copied().unwrap_or(255);
+        Ok(Self::rgba_u8(channels[0], channels[1], channels[2], alpha))
+    }
+
+    /// The channels clamped to `0..=1` and rounded to bytes.
+    pub fn to_u8(&self) -> [u8; 4] {
+        [self.red, self.green, self.blue, self.alpha]
+            .map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8)
+    }
+
+    /// `#rrggbb`, or `#rrggbbaa` when the color isn't opaque.
+    pub fn to_hex(&self) -> String {
+        let [red, green, blue, alpha] = self.to_u8();
+        if alpha == 255 {
+            format!("#{red:02x}{green:02x}{blue:02x}")
+        } else {
+            format!("#{red:02x}{green:02x}{blue:02x}{alpha:02x}")
+        }
+    }
+
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    pub fn to_linear(&self) -> LinearRgba {
+        LinearRgba::new(
+            srgb_to_linear(self.red),
+            srgb_to_linear(self.green),
+            srgb_to_linear(self.blue),
+            self.alpha,
+        )
+    }
+
+    pub fn to_hsva(&self) -> Hsva {
+        let max = self.red.max(self.green).max(self.blue);
+        let delta = max - self.red.min(self.green).min(self.blue);
+        let saturation = if max == 0.0 { 0.0 } else { delta / max };
+        Hsva::new(
+            hue(self.red, self.green, self.blue, max, delta),
+            saturation,
+            max,
+            self.alpha,
+        )
+    }
+
+    pub fn to_hsla(&self) -> Hsla {
+        let max = self.red.max(self.green).max(self.blue);
+        let min = self.red.min(self.green).min(self.blue);
+        let delta = max - min;
+        let lightness = (max + min) / 2.0;
+        let saturation = if delta == 0.0 {
+            0.0
+        } else {
+            delta / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+        Hsla::new(
+            hue(self.red, self.green, self.blue, max, delta),
+            saturation,
+            lightness,
+            self.alpha,
+        )
+    }
+}
+
+impl Hsva {
+    pub const fn new(hue: f32, saturation: f32, value: f32, alpha: f32) -> Self {
+        Self {
+            hue,
+            saturation,
+            value,
+            alpha,
+        }
+    }
+
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    pub fn to_srgba(&self) -> Srgba {
+        let chroma = self.value * self.saturation;
+        let (red, green, blue) = from_hue(self.hue, chroma, self.value - chroma);
+        Srgba::new(red, green, blue, self.alpha)
+    }
+}
+
+impl Hsla {
+    pub const fn new(hue: f32, saturation: f32, lightness: f32, alpha: f32) -> Self {
+        Self {
+            hue,
+            saturation,
+            lightness,
+            alpha,
+        }
+    }
+
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    pub fn to_srgba(&self) -> Srgba {
+        let chroma = (1.0 - (2.0 * self.lightness - 1.0).abs()) * self.saturation;
+        let (red, green, blue) = from_hue(self.hue, chroma, self.lightness - chroma / 2.0);
+        Srgba::new(red, green, blue, self.alpha)
+    }
+}
+
+impl Color {
+    pub const BLACK: Self = Self::Srgba(Srgba::BLACK);
+    pub const WHITE: Self = Self::Srgba(Srgba::WHITE);
+    pub const TRANSPARENT: Self = Self::Srgba(Srgba::TRANSPARENT);
+
+    pub const fn srgb(red: f32, green: f32, blue: f32) -> Self {
+        Self::Srgba(Srgba::rgb(red, green, blue))
+    }
+
+    pub const fn srgba(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+        Self::Srgba(Srgba::new(red, green, blue, alpha))
+    }
+
+    pub const fn linear_rgb(red: f32, green: f32, blue: f32) -> Self {
+        Self::LinearRgba(LinearRgba::rgb(red, green, blue))
+    }
+
+    pub const fn hsv(hue: f32, saturation: f32, value: f32) -> Self {
+        Self::Hsva(Hsva::new(hue, saturation, value, 1.0))
+    }
+
+    pub const fn hsl(hue: f32, saturation: f32, lightness: f32) -> Self {
+        Self::Hsla(Hsla::new(hue, saturation, lightness, 1.0))
+    }
+
+    /// See [`Srgba::hex`].
+    pub fn hex(hex: &str) -> Result<Self, ColorError> {
+        Srgba::hex(hex).map(Self::Srgba)
+    }
+
+    pub fn alpha(&self) -> f32 {
+        match self {
+            Color::LinearRgba(color) => color.alpha,
+            Color::Srgba(color) => color.alpha,
+            Color::Hsva(color) => color.alpha,
+            Color::Hsla(color) => color.alpha,
+        }
+    }
+
+    pub fn with_alpha(self, alpha: f32) -> Self {
+        match self {
+            Color::LinearRgba(color) => Color::LinearRgba(color.with_alpha(alpha)),
+            Color::Srgba(color) => Color::Srgba(color.with_alpha(alpha)),
+            Color::Hsva(color) => Color::Hsva(color.with_alpha(alpha)),
+            Color::Hsla(color) => Color::Hsla(color.with_alpha(alpha)),
+        }
+    }
+
+    pub fn to_srgba(&self) -> Srgba {
+        match self {
+            Color::LinearRgba(color) => color.to_srgba(),
+            Color::Srgba(color) => *color,
+            Color::Hsva(color) => color.to_srgba(),
+            Color::Hsla(color) => color.to_srgba(),
+        }
+    }
+
+    pub fn to_linear(&self) -> LinearRgba {
+        match self {
+            Color::LinearRgba(color) => *color,
+            other => other.to_srgba().to_linear(),
+        }
+    }
+
+    pub fn to_hsva(&self) -> Hsva {
+        match self {
+            Color::Hsva(color) => *color,
+            other => other.to_srgba().to_hsva(),
+        }
+    }
+
+    pub fn to_hsla(&self) -> Hsla {
+        match self {
+            Color::Hsla(color) => *color,
+            other => other.to_srgba().to_hsla(),
+        }
+    }
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Self::WHITE
+    }
+}
+
+impl From<LinearRgba> for Color {
+    fn from(color: LinearRgba) -> Self {
+        Self::LinearRgba(color)
+    }
+}
+
+impl From<Srgba> for Color {
+    fn from(color: Srgba) -> Self {
+        Self::Srgba(color)
+    }
+}
+
+impl From<Hsva> for Color {
+    fn from(color: Hsva) -> Self {
+        Self::Hsva(color)
+    }
+}
+
+impl From<Hsla> for Color {
+    fn from(color: Hsla) -> Self {
+        Self::Hsla(color)
+    }
+}
+
+impl From<Color> for LinearRgba {
+    fn from(color: Color) -> Self {
+        color.to_linear()
+    }
+}
+
+impl From<Color> for Srgba {
+    fn from(color: Color) -> Self {
+        color.to_srgba()
+    }
+}
+
+impl From<Srgba> for LinearRgba {
+    fn from(color: Srgba) -> Self {
+        color.to_linear()
+    }
+}
+
+impl From<LinearRgba> for Srgba {
+    fn from(color: LinearRgba) -> Self {
+        color.to_srgba()
+    }
+}
+
+impl From<LinearRgba> for Vec4 {
+    fn from(color: LinearRgba) -> Self {
+        Vec4::new(color.red, color.green, color.blue, color.alpha)
+    }
+}
+
+impl From<Vec4> for LinearRgba {
+    fn from(vec: Vec4) -> Self {
+        Self::new(vec.x, vec.y, vec.z, vec.w)
+    }
+}
+
+impl From<LinearRgba> for [f32; 4] {
+    fn from(color: LinearRgba) -> Self {
+        [color.red, color.green, color.blue, color.alpha]
+    }
+}
+
+/// Colors at positions along a line, blended in linear light between them.
+///
+/// ```rust
+/// use chaos_engine::math::color::{Color, Gradient, LinearRgba};
+///
+/// let fire = Gradient::new()
+///     .with_stop(0.0, Color::hex("#ffff00").unwrap())
+///     .with_stop(1.0, Color::hex("#ff0000").unwrap());
+/// assert_eq!(fire.sample(0.5), LinearRgba::rgb(1.0, 0.5, 0.0));
+/// assert_eq!(fire.sample(2.0), LinearRgba::rgb(1.0, 0.0, 0.0));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Gradient {
+    stops: Vec<(f32, LinearRgba)>,
+}
+
+impl Gradient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a stop, keeping the stops sorted by position.
+    pub fn with_stop(mut self, position: f32, color: impl Into<Color>) -> Self {
+        let index = self.stops.partition_point(|(stop, _)| *stop <= position);
+        self.stops
+            .insert(index, (position, color.into().to_linear()));
+        self
+    }
+
+    pub fn stops(&self) -> &[(f32, LinearRgba)] {
+        &self.stops
+    }
+
+    /// The color at `position`; positions outside the stops get the nearest end's color,
+    /// and an empty gradient is transparent.
+    pub fn sample(&self, position: f32) -> LinearRgba {
+        let index = self.stops.partition_point(|(stop, _)| *stop <= position);
+        match (index.checked_sub(1), self.stops.get(index)) {
+            (None, Some((_, first))) => *first,
+            (Some(before), None) => self.stops[before].1,
+            (Some(before), Some((end, to))) => {
+                let (start, from) = self.stops[before];
+                LinearRgba::lerp(&from, to, (position - start) / (end - start))
+            }
+            (None, None) => LinearRgba::TRANSPARENT,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Srgba, b: Srgba) -> bool {
+        [
+            a.red - b.red,
+            a.green - b.green,
+            a.blue - b.blue,
+            a.alpha - b.alpha,
+        ]
+        .iter()
+        .all(|difference| difference.abs() < 1e-5)
+    }
+
+    #[test]
+    fn srgb_round_trips_through_linear() {
+        for value in [0.0, 0.02, 0.04045, 0.2, 0.5, 0.9, 1.0] {
+            let color = Srgba::new(value, 1.0 - value, value / 2.0, 0.5);
+            assert!(close(color.to_linear().to_srgba(), color), "{color:?}");
+        }
+        assert_eq!(Srgba::WHITE.to_linear(), LinearRgba::WHITE);
+        assert_eq!(Vec4::from(LinearRgba::WHITE), Vec4::one());
+    }
+
+    #[test]
+    fn hsv_and_hsl_conversions() {
+        let orange = Srgba::rgb(1.0, 0.5, 0.0);
+        let hsv = orange.to_hsva();
+        assert_eq!(hsv, Hsva::new(30.0, 1.0, 1.0, 1.0));
+        assert!(close(hsv.to_srgba(), orange));
+        let hsl = orange.to_hsla();
+        assert_eq!(hsl, Hsla::new(30.0, 1.0, 0.5, 1.0));
+        assert!(close(hsl.to_srgba(), orange));
+
+        for hue in [0.0, 75.0, 150.0, 200.0, 290.0, 350.0, 360.0, -60.0] {
+            let color = Color::hsv(hue, 0.6, 0.8).to_srgba();
+            assert!(close(color.to_hsva().to_srgba(), color), "{hue}");
+            let color = Color::hsl(hue, 0.6, 0.3).to_srgba();
+            assert!(close(color.to_hsla().to_srgba(), color), "{hue}");
+        }
+        assert_eq!(Srgba::rgb(0.4, 0.4, 0.4).to_hsla().saturation, 0.0);
+    }
+
+    #[test]
+    fn hex_parsing() {
+        assert_eq!(Srgba::hex("#ff0000"), Ok(Srgba::rgb(1.0, 0.0, 0.0)));
+        assert_eq!(Srgba::hex("0f08"), Srgba::hex("#00ff0088"));
+        assert_eq!(Srgba::hex("#fff").unwrap().to_hex(), "#ffffff");
+        assert_eq!(Srgba::hex("#12345678").unwrap().to_hex(), "#12345678");
+        assert_eq!(Srgba::hex("#12345"), Err(ColorError::InvalidHexLength(5)));
+        assert_eq!(Srgba::hex("#ggg"), Err(ColorError::InvalidHexDigit('g')));
+    }
+
+    #[test]
+    fn premultiplied_alpha_and_gradients() {
+        let color = LinearRgba::new(0.8, 0.4, 0.2, 0.5);
+        assert_eq!(color.premultiplied(), LinearRgba::new(0.4, 0.2, 0.1, 0.5));
+        assert_eq!(color.premultiplied().unpremultiplied(), color);
+        assert_eq!(
+            LinearRgba::WHITE.with_alpha(0.0).unpremultiplied(),
+            LinearRgba::TRANSPARENT
+        );
+
+        let gradient = Gradient::new()
+            .with_stop(1.0, LinearRgba::WHITE)
+            .with_stop(0.0, LinearRgba::BLACK)
+            .with_stop(0.5, LinearRgba::rgb(1.0, 0.0, 0.0));
+        assert_eq!(gradient.stops()[1].0, 0.5);
+        assert_eq!(gradient.sample(0.25), LinearRgba::rgb(0.5, 0.0, 0.0));
+        assert_eq!(gradient.sample(0.75), LinearRgba::rgb(1.0, 0.5, 0.5));
+        assert_eq!(gradient.sample(-1.0), LinearRgba::BLACK);
+        assert_eq!(Gradient::new().sample(0.5), LinearRgba::TRANSPARENT);
+    }
+}
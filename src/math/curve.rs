@@ -0,0 +1,647 @@
+//! Parametric curves over `Vec2` and `Vec3`, for flight paths, camera rails and beams.
+//! Every curve runs from `t = 0` to `t = 1`; [`ArcLength`] walks one at constant speed.
+//!
+//! ```rust
+//! use chaos_engine::math::{
+//!     Vec2,
+//!     curve::{ArcLength, CatmullRom, Curve},
+//! };
+//!
+//! let path = CatmullRom::new(vec![
+//!     Vec2::new(0.0, 0.0),
+//!     Vec2::new(4.0, 2.0),
+//!     Vec2::new(8.0, 0.0),
+//! ])
+//! .unwrap();
+//! // Catmull-Rom splines pass through their points.
+//! assert_eq!(path.point(0.5), Vec2::new(4.0, 2.0));
+//! let lines = path.flatten(0.01);
+//! assert_eq!(lines.first(), Some(&Vec2::new(0.0, 0.0)));
+//!
+//! let walk = ArcLength::new(&path, 64);
+//! let halfway = walk.point_at_distance(walk.length() / 2.0);
+//! assert!((halfway.x - 4.0).abs() < 0.05);
+//! ```
+
+use std::ops::{Add, Mul, Sub};
+
+use crate::math::{Vec2, Vec3};
+
+/// A point type curves can be built from.
+pub trait CurvePoint:
+    Copy + Add<Output = Self> + Sub<Output = Self> + Mul<f32, Output = Self>
+{
+    fn dot(one: &Self, other: &Self) -> f32;
+
+    fn distance(one: &Self, other: &Self) -> f32 {
+        let difference = *one - *other;
+        Self::dot(&difference, &difference).sqrt()
+    }
+}
+
+impl CurvePoint for Vec2 {
+    fn dot(one: &Self, other: &Self) -> f32 {
+        Vec2::dot(one, other)
+    }
+}
+
+impl CurvePoint for Vec3 {
+    fn dot(one: &Self, other: &Self) -> f32 {
+        Vec3::dot(one, other)
+    }
+}
+
+// Subdivision depth at which flattening stops refining, whatever the tolerance.
+const MAX_FLATTEN_DEPTH: u32 = 16;
+// Samples per segment when searching for the closest point.
+const CLOSEST_POINT_SAMPLES: usize = 16;
+
+/// A curve from `t = 0` to `t = 1`. Parameters outside that range are clamped.
+pub trait Curve {
+    type Point: CurvePoint;
+
+    fn point(&self, t: f32) -> Self::Point;
+
+    /// The velocity along the curve at `t`, i.e. the change of the point per unit of `t`.
+    fn derivative(&self, t: f32) -> Self::Point;
+
+    /// Pieces the curve is made of; searches sample each piece separately.
+    fn segment_count(&self) -> usize {
+        1
+    }
+
+    /// The unit direction of travel at `t`; zero where the curve stops.
+    fn tangent(&self, t: f32) -> Self::Point {
+        let derivative = self.derivative(t);
+        let length = Self::Point::dot(&derivative, &derivative).sqrt();
+        if length == 0.0 {
+            derivative
+        } else {
+            derivative * (1.0 / length)
+        }
+    }
+
+    /// Points along the curve, close enough together that the lines between them stay
+    /// within `tolerance` of the curve. Includes both ends.
+    fn flatten(&self, tolerance: f32) -> Vec<Self::Point> {
+        let mut points = vec![self.point(0.0)];
+        let segments = self.segment_count().max(1);
+        for segment in 0..segments {
+            let start = segment as f32 / segments as f32;
+            let end = (segment + 1) as f32 / segments as f32;
+            flatten_range(self, start, end, tolerance, 0, &mut points);
+        }
+        points
+    }
+
+    /// The length of the lines [`flatten`](Self::flatten) returns for `tolerance`.
+    fn length(&self, tolerance: f32) -> f32 {
+        self.flatten(tolerance)
+            .windows(2)
+            .map(|pair| Self::Point::distance(&pair[0], &pair[1]))
+            .sum()
+    }
+
+    /// The parameter of the point on the curve closest to `target`.
+    fn closest_parameter(&self, target: Self::Point) -> f32 {
+        let samples = self.segment_count().max(1) * CLOSEST_POINT_SAMPLES;
+        let distance = |t: f32| {
+            let difference = self.point(t) - target;
+            Self::Point::dot(&difference, &difference)
+        };
+        let nearest = (0..=samples)
+            .map(|sample| sample as f32 / samples as f32)
+            .min_by(|a, b| distance(*a).total_cmp(&distance(*b)))
+            .unwrap_or(0.0);
+
+        // Narrows the sampled neighbourhood down with a ternary search.
+        let step = 1.0 / samples as f32;
+        let (mut low, mut high) = ((nearest - step).max(0.0), (nearest + step).min(1.0));
+        for _ in 0..24 {
+            let third = (high - low) / 3.0;
+            if distance(low + third) < distance(high - third) {
+                high -= third;
+            } else {
+                low += third;
+            }
+        }
+        (low + high) / 2.0
+    }
+
+    fn closest_point(&self, target: Self::Point) -> Self::Point {
+        self.point(self.closest_parameter(target))
+    }
+}
+
+fn flatten_range<C: Curve + ?Sized>(
+    curve: &C,
+    start: f32,
+    end: f32,
+    tolerance: f32,
+    depth: u32,
+    points: &mut Vec<C::Point>,
+) {
+    // Splits until the curve's midpoint is close to the chord's.
+    let middle = (start + end) / 2.0;
+    let chord_middle = (curve.point(start) + curve.point(end)) * 0.5;
+    let flat = C::Point::distance(&curve.point(middle), &chord_middle) <= tolerance;
+    // A quarter point catches S-bends whose midpoint happens to lie on the chord.
+    let quarter = curve.point((start + middle) / 2.0);
+    let quarter_chord = curve.point(start) * 0.75 + curve.point(end) * 0.25;
+    let flat = flat && C::Point::distance(&quarter, &quarter_chord) <= tolerance;
+
+    if (flat && depth > 0) || depth >= MAX_FLATTEN_DEPTH {
+        points.push(curve.point(end));
+    } else {
+        flatten_range(curve, start, middle, tolerance, depth + 1, points);
+        flatten_range(curve, middle, end, tolerance, depth + 1, points);
+    }
+}
+
+/// A Bezier curve with one control point. Passes through `start` and `end` only.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QuadraticBezier<P: CurvePoint> {
+    pub start: P,
+    pub control: P,
+    pub end: P,
+}
+
+impl<P: CurvePoint> QuadraticBezier<P> {
+    pub fn new(start: P, control: P, end: P) -> Self {
+        Self {
+            start,
+            control,
+            end,
+        }
+    }
+
+    /// The same curve as a cubic Bezier.
+    pub fn to_cubic(&self) -> CubicBezier<P> {
+        CubicBezier::new(
+            self.start,
+            self.start + (self.control - self.start) * (2.0 / 3.0),
+            self.end + (self.control - self.end) * (2.0 / 3.0),
+            self.end,
+        )
+    }
+}
+
+impl<P: CurvePoint> Curve for QuadraticBezier<P> {
+    type Point = P;
+
+    fn point(&self, t: f32) -> P {
+        let t = t.clamp(0.0, 1.0);
+        let u = 1.0 - t;
+        self.start * (u * u) + self.control * (2.0 * u * t) + self.end * (t * t)
+    }
+
+    fn derivative(&self, t: f32) -> P {
+        let t = t.clamp(0.0, 1.0);
+        (self.control - self.start) * (2.0 * (1.0 - t)) + (self.end - self.control) * (2.0 * t)
+    }
+}
+
+/// A Bezier curve with two control points: it leaves `start` towards `control1` and
+/// arrives at `end` from `control2`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CubicBezier<P: CurvePoint> {
+    pub start: P,
+    pub control1: P,
+    pub control2: P,
+    pub end: P,
+}
+
+impl<P: CurvePoint> CubicBezier<P> {
+    pub fn new(start: P, control1: P, control2: P, end: P) -> Self {
+        Self {
+            start,
+            control1,
+            control2,
+            end,
+        }
+    }
+}
+
+impl<P: CurvePoint> Curve for CubicBezier<P> {
+    type Point = P;
+
+    fn point(&self, t: f32) -> P {
+        let t = t.clamp(0.0, 1.0);
+        let u = 1.0 - t;
+        self.start * (u * u * u)
+            + self.control1 * (3.0 * u * u * t)
+            + self.control2 * (3.0 * u * t * t)
+            + self.end * (t * t * t)
+    }
+
+    fn derivative(&self, t: f32) -> P {
+        let t = t.clamp(0.0, 1.0);
+        let u = 1.0 - t;
+        (self.control1 - self.start) * (3.0 * u * u)
+            + (self.control2 - self.control1) * (6.0 * u * t)
+            + (self.end - self.control2) * (3.0 * t * t)
+    }
+}
+
+// Splines are evaluated one cubic Bezier segment at a time. A spline of one point has no
+// segments and stays on that point.
+fn spline_point<P: CurvePoint>(points: &[P], segments: &[CubicBezier<P>], t: f32) -> P {
+    if segments.is_empty() {
+        return points[0];
+    }
+    let (segment, local) = spline_segment(segments.len(), t);
+    segments[segment].point(local)
+}
+
+fn spline_derivative<P: CurvePoint>(points: &[P], segments: &[CubicBezier<P>], t: f32) -> P {
+    if segments.is_empty() {
+        return points[0] * 0.0;
+    }
+    let (segment, local) = spline_segment(segments.len(), t);
+    // Each segment covers 1 / len of `t`, so it is traversed len times as fast.
+    segments[segment].derivative(local) * segments.len() as f32
+}
+
+// Only called with at least one segment.
+fn spline_segment(count: usize, t: f32) -> (usize, f32) {
+    let scaled = t.clamp(0.0, 1.0) * count as f32;
+    let segment = (scaled as usize).min(count - 1);
+    (segment, scaled - segment as f32)
+}
+
+/// A smooth curve through all of its points, e.g. waypoints. Open splines run from the
+/// first point to the last; closed ones loop back to the first. A spline of one point
+/// stays on it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CatmullRom<P: CurvePoint> {
+    points: Vec<P>,
+    closed: bool,
+    segments: Vec<CubicBezier<P>>,
+}
+
+impl<P: CurvePoint> CatmullRom<P> {
+    /// `None` without points.
+    pub fn new(points: Vec<P>) -> Option<Self> {
+        (!points.is_empty()).then(|| Self::build(points, false))
+    }
+
+    /// `None` without points.
+    pub fn closed(points: Vec<P>) -> Option<Self> {
+        (!points.is_empty()).then(|| Self::build(points, true))
+    }
+
+    fn build(points: Vec<P>, closed: bool) -> Self {
+        let count = points.len();
+        let at = |index: isize| {
+            if closed {
+                points[index.rem_euclid(count as isize) as usize]
+            } else {
+                // Open ends repeat the end points.
+                points[index.clamp(0, count as isize - 1) as usize]
+            }
+        };
+        let segment_count = match count {
+            0 | 1 => 0,
+            _ if closed => count,
+            _ => count - 1,
+        };
+        let segments = (0..segment_count as isize)
+            .map(|i| {
+                let (p0, p1, p2, p3) = (at(i - 1), at(i), at(i + 1), at(i + 2));
+                CubicBezier::new(
+                    p1,
+                    p1 + (p2 - p0) * (1.0 / 6.0),
+                    p2 - (p3 - p1) * (1.0 / 6.0),
+                    p2,
+                )
+            })
+            .collect();
+        Self {
+            points,
+            closed,
+            segments,
+        }
+    }
+
+    pub fn points(&self) -> &[P] {
+        &self.points
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// The spline as cubic Bezier segments, one between each pair of neighbouring points.
+    pub fn segments(&self) -> &[CubicBezier<P>] {
+        &self.segments
+    }
+}
+
+impl<P: CurvePoint> Curve for CatmullRom<P> {
+    type Point = P;
+
+    fn point(&self, t: f32) -> P {
+        spline_point(&self.points, &self.segments, t)
+    }
+
+    fn derivative(&self, t: f32) -> P {
+        spline_derivative(&self.points, &self.segments, t)
+    }
+
+    fn segment_count(&self) -> usize {
+        self.segments.len()
+    }
+}
+
+/// A uniform cubic B-spline: smoother than a Catmull-Rom spline (its curvature is
+/// continuous too), but it only passes near its points. Open splines repeat their end
+/// points three times, so they start and end exactly on them. A spline of one point stays
+/// on it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BSpline<P: CurvePoint> {
+    points: Vec<P>,
+    closed: bool,
+    segments: Vec<CubicBezier<P>>,
+}
+
+impl<P: CurvePoint> BSpline<P> {
+    /// `None` without points.
+    pub fn new(points: Vec<P>) -> Option<Self> {
+        (!points.is_empty()).then(|| Self::build(points, false))
+    }
+
+    /// `None` without points.
+    pub fn closed(points: Vec<P>) -> Option<Self> {
+        (!points.is_empty()).then(|| Self::build(points, true))
+    }
+
+    fn build(points: Vec<P>, closed: bool) -> Self {
+        let count = points.len() as isize;
+        let at = |index: isize| {
+            if closed {
+                points[index.rem_euclid(count) as usize]
+            } else {
+                points[index.clamp(0, count - 1) as usize]
+            }
+        };
+        let range = match count {
+            0 | 1 => 0..0,
+            _ if closed => 0..count,
+            _ => -2..count - 1,
+        };
+        let segments = range
+            .map(|i| {
+                let (p0, p1, p2, p3) = (at(i), at(i + 1), at(i + 2), at(i + 3));
+                CubicBezier::new(
+                    (p0 + p1 * 4.0 + p2) * (1.0 / 6.0),
+                    (p1 * 2.0 + p2) * (1.0 / 3.0),
+                    (p1 + p2 * 2.0) * (1.0 / 3.0),
+                    (p1 + p2 * 4.0 + p3) * (1.0 / 6.0),
+                )
+            })
+            .collect();
+        Self {
+            points,
+            closed,
+            segments,
+        }
+    }
+
+    pub fn points(&self) -> &[P] {
+        &self.points
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    pub fn segments(&self) -> &[CubicBezier<P>] {
+        &self.segments
+    }
+}
+
+impl<P: CurvePoint> Curve for BSpline<P> {
+    type Point = P;
+
+    fn point(&self, t: f32) -> P {
+        spline_point(&self.points, &self.segments, t)
+    }
+
+    fn derivative(&self, t: f32) -> P {
+        spline_derivative(&self.points, &self.segments, t)
+    }
+
+    fn segment_count(&self) -> usize {
+        self.segments.len()
+    }
+}
+
+/// Maps distances along a curve to its parameter, so a point can move along the curve at
+/// constant speed, which `t` alone doesn't give. Built from a table of sampled lengths.
+pub struct ArcLength<'a, C: Curve> {
+    curve: &'a C,
+    // Distance along the curve at evenly spaced parameters.
+    distances: Vec<f32>,
+}
+
+impl<'a, C: Curve> ArcLength<'a, C> {
+    /// Samples every segment of the curve `samples_per_segment` times; more samples give
+    /// more even steps.
+    pub fn new(curve: &'a C, samples_per_segment: usize) -> Self {
+        let samples = curve.segment_count().max(1) * samples_per_segment.max(1);
+        let mut distances = Vec::with_capacity(samples + 1);
+        let mut total = 0.0;
+        let mut previous = curve.point(0.0);
+        distances.push(0.0);
+        for sample in 1..=samples {
+            let point = curve.point(sample as f32 / samples as f32);
+            total += C::Point::distance(&previous, &point);
+            distances.push(total);
+            previous = point;
+        }
+        Self { curve, distances }
+    }
+
+    pub fn length(&self) -> f32 {
+        self.distances.last().copied().unwrap_or(0.0)
+    }
+
+    /// The parameter `distance` along the curve, clamped to its ends.
+    pub fn parameter_at_distance(&self, distance: f32) -> f32 {
+        let samples = self.distances.len() - 1;
+        let distance = distance.clamp(0.0, self.length());
+        let index = self
+            .distances
+            .partition_point(|sampled| *sampled < distance)
+            .clamp(1, samples);
+        let (before, after) = (self.distances[index - 1], self.distances[index]);
+        let fraction = if after > before {
+            (distance - before) / (after - before)
+        } else {
+            0.0
+        };
+        (index as f32 - 1.0 + fraction) / samples as f32
+    }
+
+    pub fn point_at_distance(&self, distance: f32) -> C::Point {
+        self.curve.point(self.parameter_at_distance(distance))
+    }
+
+    /// The point a fraction `u` of the length along the curve, from `0.0` to `1.0`.
+    pub fn point_at_fraction(&self, u: f32) -> C::Point {
+        self.point_at_distance(u * self.length())
+    }
+
+    /// The distance along the curve to the parameter `t`.
+    pub fn distance_at_parameter(&self, t: f32) -> f32 {
+        let samples = self.distances.len() - 1;
+        let scaled = t.clamp(0.0, 1.0) * samples as f32;
+        let index = (scaled as usize).min(samples - 1);
+        let fraction = scaled - index as f32;
+        self.distances[index] + (self.distances[index + 1] - self.distances[index]) * fraction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close2(a: Vec2, b: Vec2, tolerance: f32) -> bool {
+        Vec2::distance(&a, &b) < tolerance
+    }
+
+    #[test]
+    fn bezier_curves_evaluate_and_differentiate() {
+        let quadratic =
+            QuadraticBezier::new(Vec2::zero(), Vec2::new(1.0, 2.0), Vec2::new(2.0, 0.0));
+        assert_eq!(quadratic.point(0.5), Vec2::new(1.0, 1.0));
+        assert_eq!(quadratic.derivative(0.5), Vec2::new(2.0, 0.0));
+        assert_eq!(
+            quadratic.tangent(0.0),
+            Vec2::normalized(&Vec2::new(1.0, 2.0))
+        );
+        let cubic = quadratic.to_cubic();
+        for t in [0.0, 0.2, 0.5, 0.9, 1.0] {
+            assert!(close2(cubic.point(t), quadratic.point(t), 1e-5));
+            assert!(close2(cubic.derivative(t), quadratic.derivative(t), 1e-4));
+        }
+
+        // The derivative matches a finite difference.
+        let cubic = CubicBezier::new(
+            Vec3::zero(),
+            Vec3::new(1.0, 3.0, 0.0),
+            Vec3::new(3.0, -1.0, 2.0),
+            Vec3::new(4.0, 0.0, 0.0),
+        );
+        let h = 1e-3;
+        let difference = (cubic.point(0.4 + h) - cubic.point(0.4 - h)) * (0.5 / h);
+        assert!(Vec3::distance(&difference, &cubic.derivative(0.4)) < 1e-2);
+        assert_eq!(cubic.point(2.0), cubic.end);
+    }
+
+    #[test]
+    fn splines_pass_through_or_near_their_points() {
+        let points = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 3.0),
+            Vec2::new(5.0, 1.0),
+            Vec2::new(6.0, 4.0),
+        ];
+        let catmull_rom = CatmullRom::new(points.clone()).unwrap();
+        assert_eq!(catmull_rom.segment_count(), 3);
+        for (i, point) in points.iter().enumerate() {
+            assert!(close2(catmull_rom.point(i as f32 / 3.0), *point, 1e-5));
+        }
+        // Neighbouring segments meet with the same velocity.
+        let [first, second, _] = catmull_rom.segments() else {
+            panic!("Expected three segments");
+        };
+        assert!(close2(first.derivative(1.0), second.derivative(0.0), 1e-5));
+
+        let closed = CatmullRom::closed(points.clone()).unwrap();
+        assert_eq!(closed.segment_count(), 4);
+        assert!(close2(closed.point(1.0), points[0], 1e-5));
+
+        let b_spline = BSpline::new(points.clone()).unwrap();
+        assert!(close2(b_spline.point(0.0), points[0], 1e-5));
+        assert!(close2(b_spline.point(1.0), points[3], 1e-5));
+        let inner = b_spline.point(b_spline.closest_parameter(points[1]));
+        assert!(Vec2::distance(&inner, &points[1]) < 1.5);
+        let [.., last] = b_spline.segments() else {
+            panic!("Expected segments");
+        };
+        assert!(close2(last.end, points[3], 1e-5));
+        assert_eq!(BSpline::closed(points).unwrap().segment_count(), 4);
+    }
+
+    #[test]
+    fn splines_need_a_point_and_stay_on_a_single_one() {
+        assert!(CatmullRom::<Vec2>::new(Vec::new()).is_none());
+        assert!(BSpline::<Vec3>::closed(Vec::new()).is_none());
+
+        let point = Vec2::new(0.1, -2.7);
+        let catmull_rom = CatmullRom::closed(vec![point]).unwrap();
+        let b_spline = BSpline::new(vec![point]).unwrap();
+        for curve in [&catmull_rom as &dyn Curve<Point = Vec2>, &b_spline] {
+            for t in [0.0, 0.3, 1.0] {
+                assert_eq!(curve.point(t), point);
+                assert_eq!(curve.derivative(t), Vec2::zero());
+            }
+            assert!(curve.flatten(0.01).iter().all(|flat| *flat == point));
+            assert_eq!(curve.closest_point(Vec2::new(5.0, 5.0)), point);
+        }
+        let walk = ArcLength::new(&b_spline, 8);
+        assert_eq!(walk.length(), 0.0);
+        assert_eq!(walk.point_at_fraction(0.5), point);
+        assert_eq!(walk.distance_at_parameter(0.7), 0.0);
+    }
+
+    #[test]
+    fn flattening_stays_within_tolerance() {
+        let curve = CubicBezier::new(
+            Vec2::zero(),
+            Vec2::new(0.0, 4.0),
+            Vec2::new(4.0, -4.0),
+            Vec2::new(4.0, 0.0),
+        );
+        let coarse = curve.flatten(0.1);
+        let fine = curve.flatten(0.001);
+        assert!(coarse.len() < fine.len());
+        assert_eq!(coarse.first(), Some(&curve.start));
+        assert_eq!(coarse.last(), Some(&curve.end));
+
+        for pair in coarse.windows(2) {
+            let middle = (pair[0] + pair[1]) * 0.5;
+            let on_curve = curve.closest_point(middle);
+            assert!(Vec2::distance(&middle, &on_curve) < 0.1 + 1e-3);
+        }
+        // A straight line only needs its ends.
+        let line = QuadraticBezier::new(Vec2::zero(), Vec2::new(1.0, 0.0), Vec2::new(2.0, 0.0));
+        assert_eq!(line.flatten(0.01).len(), 3);
+    }
+
+    #[test]
+    fn closest_points_and_arc_length() {
+        let curve = QuadraticBezier::new(Vec2::zero(), Vec2::new(2.0, 0.0), Vec2::new(4.0, 0.0));
+        assert!((curve.length(0.001) - 4.0).abs() < 1e-4);
+        assert!((curve.closest_parameter(Vec2::new(1.0, 5.0)) - 0.25).abs() < 1e-3);
+        assert!(close2(
+            curve.closest_point(Vec2::new(9.0, 1.0)),
+            curve.end,
+            1e-5
+        ));
+
+        // The control point near the start makes `t` run unevenly; distances don't.
+        let uneven = QuadraticBezier::new(Vec2::zero(), Vec2::new(0.2, 0.0), Vec2::new(4.0, 0.0));
+        assert!(uneven.point(0.5).x < 1.5);
+        let walk = ArcLength::new(&uneven, 128);
+        assert!((walk.length() - 4.0).abs() < 1e-3);
+        for distance in [0.0, 0.5, 1.0, 2.5, 4.0] {
+            let point = walk.point_at_distance(distance);
+            assert!((point.x - distance).abs() < 0.02, "{distance} {point:?}");
+            let t = walk.parameter_at_distance(distance);
+            assert!((walk.distance_at_parameter(t) - distance).abs() < 0.02);
+        }
+        assert!(close2(walk.point_at_fraction(1.0), uneven.end, 1e-4));
+    }
+}
@@ -2,7 +2,11 @@ use std::ops::{Mul, MulAssign};
 
 use vulkano::buffer::BufferContents;
 
-use crate::math::{Vec2, Vec3, quaternion::Quaternion};
+use crate::math::{
+    DVec2, DVec3, Vec2, Vec3,
+    quaternion::{DQuat, Quaternion},
+    simd,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, BufferContents)]
 #[repr(C)]
@@ -126,17 +130,9 @@ impl Mul<Mat3> for Mat3 {
     type Output = Self;
 
     fn mul(self, other: Mat3) -> Self::Output {
-        let mut result = Mat3::zero();
-
-        for i in 0..Self::ROWS {
-            for j in 0..Self::COLS {
-                for k in 0..Self::COLS {
-                    result.data[i][j] += self.data[i][k] * other.data[k][j];
-                }
-            }
+        Self {
+            data: simd::mat3_mul(&self.data, &other.data),
         }
-
-        result
     }
 }
 
@@ -341,33 +337,31 @@ impl Mat4 {
         }
     }
 
+    // Gauss-Jordan elimination with partial pivoting, in f64 to keep projection matrices
+    // (which mix very large and very small entries) accurate.
     pub fn inverse(&self) -> Option<Self> {
-        let mut inv = [[0.0; Self::COLS]; Self::ROWS];
-        let mut det: f64 = 0.0;
+        simd::mat4_inverse(&self.data).map(|data| Self { data })
+    }
 
-        for i in 0..Self::ROWS {
-            det += self.data[0][i] as f64
-                * (self.data[1][(i + 1) % Self::COLS] as f64
-                    * self.data[2][(i + 2) % Self::COLS] as f64
-                    - self.data[1][(i + 2) % Self::COLS] as f64
-                        * self.data[2][(i + 1) % Self::COLS] as f64);
+    // Transforms a point (w = 1) using the same row-vector convention as the shaders and
+    // divides by the resulting w. Returns `None` for points that end up at infinity.
+    pub fn transform_point(&self, point: &Vec3) -> Option<Vec3> {
+        let p = [point.x, point.y, point.z, 1.0];
+        let mut result = [0.0; 4];
+        for (column, value) in result.iter_mut().enumerate() {
+            for (row, component) in p.iter().enumerate() {
+                *value += component * self.data[row][column];
+            }
         }
 
-        if det == 0.0 {
+        if result[3].abs() <= f32::EPSILON {
             return None;
         }
-
-        for i in 0..Self::ROWS {
-            for j in 0..Self::COLS {
-                inv[j][i] = ((self.data[(i + 1) % Self::ROWS][(j + 1) % Self::COLS] as f64
-                    * self.data[(i + 2) % Self::ROWS][(j + 2) % Self::COLS] as f64
-                    - self.data[(i + 1) % Self::ROWS][(j + 2) % Self::COLS] as f64
-                        * self.data[(i + 2) % Self::ROWS][(j + 1) % Self::COLS] as f64)
-                    / det) as f32;
-            }
-        }
-
-        Some(Self { data: inv })
+        Some(Vec3::new(
+            result[0] / result[3],
+            result[1] / result[3],
+            result[2] / result[3],
+        ))
     }
 }
 
@@ -389,6 +383,8 @@ impl From<[[f32; 4]; 4]> for Mat4 {
     }
 }
 
+// Rows are the turned axes, so points (row vectors) turn the way `Quaternion::rotate`
+// turns them.
 impl From<Quaternion> for Mat4 {
     fn from(quat: Quaternion) -> Self {
         let mut mat = Mat4::identity();
@@ -400,17 +396,23 @@ impl From<Quaternion> for Mat4 {
         let xx2 = quat.a * x2;
         let yy2 = quat.b * y2;
         let zz2 = quat.c * z2;
+        let xy2 = quat.a * y2;
+        let xz2 = quat.a * z2;
+        let yz2 = quat.b * z2;
+        let wx2 = quat.d * x2;
+        let wy2 = quat.d * y2;
+        let wz2 = quat.d * z2;
 
         mat.data[0][0] = 1.0 - yy2 - zz2;
-        mat.data[0][1] = (quat.b * z2) - (quat.d * y2);
-        mat.data[0][2] = (quat.c * x2) + (quat.d * x2);
+        mat.data[0][1] = xy2 + wz2;
+        mat.data[0][2] = xz2 - wy2;
 
-        mat.data[1][0] = (quat.b * z2) + (quat.d * y2);
+        mat.data[1][0] = xy2 - wz2;
         mat.data[1][1] = 1.0 - xx2 - zz2;
-        mat.data[1][2] = (quat.c * y2) - (quat.d * x2);
+        mat.data[1][2] = yz2 + wx2;
 
-        mat.data[2][0] = (quat.c * x2) - (quat.d * y2);
-        mat.data[2][1] = (quat.c * y2) + (quat.d * x2);
+        mat.data[2][0] = xz2 + wy2;
+        mat.data[2][1] = yz2 - wx2;
         mat.data[2][2] = 1.0 - xx2 - yy2;
 
         mat
@@ -421,17 +423,9 @@ impl Mul<Mat4> for Mat4 {
     type Output = Self;
 
     fn mul(self, other: Mat4) -> Self::Output {
-        let mut result = Mat4::zero();
-
-        for i in 0..Self::ROWS {
-            for j in 0..Self::COLS {
-                for k in 0..Self::COLS {
-                    result.data[i][j] += self.data[i][k] * other.data[k][j];
-                }
-            }
+        Self {
+            data: simd::mat4_mul(&self.data, &other.data),
         }
-
-        result
     }
 }
 
@@ -441,6 +435,242 @@ impl MulAssign<Mat4> for Mat4 {
     }
 }
 
+/// Double precision `Mat3`, e.g. for 2D poses in a large world.
+#[derive(Debug, Clone, Copy, PartialEq, BufferContents)]
+#[repr(C)]
+pub struct DMat3 {
+    pub data: [[f64; 3]; 3],
+}
+
+/// Double precision `Mat4`, e.g. for model matrices in a large world. Bring them close to
+/// the camera with a translation before converting to `Mat4` for rendering.
+#[derive(Debug, Clone, Copy, PartialEq, BufferContents)]
+#[repr(C)]
+pub struct DMat4 {
+    pub data: [[f64; 4]; 4],
+}
+
+impl DMat3 {
+    pub fn zero() -> Self {
+        Self {
+            data: [[0.0; 3]; 3],
+        }
+    }
+
+    pub fn identity() -> Self {
+        Self::scale(1.0, 1.0)
+    }
+
+    pub fn scale(x: f64, y: f64) -> Self {
+        let mut mat = Self::zero();
+        mat.data[0][0] = x;
+        mat.data[1][1] = y;
+        mat.data[2][2] = 1.0;
+        mat
+    }
+
+    pub fn translation(x: f64, y: f64) -> Self {
+        let mut mat = Self::identity();
+        mat.data[2][0] = x;
+        mat.data[2][1] = y;
+        mat
+    }
+
+    /// Turns the same way as `Mat3::rotation`.
+    pub fn rotation(angle: f64) -> Self {
+        let mut mat = Self::identity();
+        let (sin_angle, cos_angle) = angle.sin_cos();
+        mat.data[0][0] = cos_angle;
+        mat.data[0][1] = -sin_angle;
+        mat.data[1][0] = sin_angle;
+        mat.data[1][1] = cos_angle;
+        mat
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self {
+            data: [0, 1, 2].map(|i| self.data.map(|row| row[i])),
+        }
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        let m = |i: usize, j: usize| self.data[i % 3][j % 3];
+        let det = (0..3)
+            .map(|i| m(0, i) * (m(1, i + 1) * m(2, i + 2) - m(1, i + 2) * m(2, i + 1)))
+            .sum::<f64>();
+        if det == 0.0 {
+            return None;
+        }
+
+        // The transposed cofactors over the determinant.
+        let data = [0, 1, 2].map(|j| {
+            [0, 1, 2].map(|i| {
+                (m(i + 1, j + 1) * m(i + 2, j + 2) - m(i + 1, j + 2) * m(i + 2, j + 1)) / det
+            })
+        });
+        Some(Self { data })
+    }
+
+    pub fn as_mat3(&self) -> Mat3 {
+        Mat3 {
+            data: self.data.map(|row| row.map(|value| value as f32)),
+        }
+    }
+}
+
+impl DMat4 {
+    pub fn zero() -> Self {
+        Self {
+            data: [[0.0; 4]; 4],
+        }
+    }
+
+    pub fn identity() -> Self {
+        Self::scale(1.0, 1.0, 1.0)
+    }
+
+    pub fn scale(x: f64, y: f64, z: f64) -> Self {
+        let mut mat = Self::zero();
+        mat.data[0][0] = x;
+        mat.data[1][1] = y;
+        mat.data[2][2] = z;
+        mat.data[3][3] = 1.0;
+        mat
+    }
+
+    pub fn translation(x: f64, y: f64, z: f64) -> Self {
+        let mut mat = Self::identity();
+        mat.data[3][0] = x;
+        mat.data[3][1] = y;
+        mat.data[3][2] = z;
+        mat
+    }
+
+    /// Rows are the turned axes, so points turn the way `DQuat::rotate` turns them.
+    pub fn rotation(quat: &DQuat) -> Self {
+        let mut mat = Self::identity();
+        for (row, axis) in [DVec3::x_axis(), DVec3::y_axis(), DVec3::z_axis()]
+            .iter()
+            .enumerate()
+        {
+            let turned = quat.rotate(axis);
+            mat.data[row][..3].copy_from_slice(&[turned.x, turned.y, turned.z]);
+        }
+        mat
+    }
+
+    /// Scales, then rotates, then translates.
+    pub fn transform(translation: &DVec3, rotation: &DQuat, scale: &DVec3) -> Self {
+        Self::scale(scale.x, scale.y, scale.z)
+            * Self::rotation(rotation)
+            * Self::translation(translation.x, translation.y, translation.z)
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self {
+            data: [0, 1, 2, 3].map(|i| self.data.map(|row| row[i])),
+        }
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        simd::mat4_inverse_f64(self.data).map(|data| Self { data })
+    }
+
+    /// Same convention as `Mat4::transform_point`.
+    pub fn transform_point(&self, point: &DVec3) -> Option<DVec3> {
+        let p = [point.x, point.y, point.z, 1.0];
+        let [x, y, z, w] = [0, 1, 2, 3].map(|column| {
+            (0..4)
+                .map(|row| p[row] * self.data[row][column])
+                .sum::<f64>()
+        });
+        if w.abs() <= f64::EPSILON {
+            return None;
+        }
+        Some(DVec3::new(x / w, y / w, z / w))
+    }
+
+    pub fn as_mat4(&self) -> Mat4 {
+        Mat4 {
+            data: self.data.map(|row| row.map(|value| value as f32)),
+        }
+    }
+}
+
+impl From<Mat3> for DMat3 {
+    fn from(mat: Mat3) -> Self {
+        Self {
+            data: mat.data.map(|row| row.map(|value| value as f64)),
+        }
+    }
+}
+
+impl From<Mat4> for DMat4 {
+    fn from(mat: Mat4) -> Self {
+        Self {
+            data: mat.data.map(|row| row.map(|value| value as f64)),
+        }
+    }
+}
+
+impl From<[[f64; 3]; 3]> for DMat3 {
+    fn from(data: [[f64; 3]; 3]) -> Self {
+        Self { data }
+    }
+}
+
+impl From<[[f64; 4]; 4]> for DMat4 {
+    fn from(data: [[f64; 4]; 4]) -> Self {
+        Self { data }
+    }
+}
+
+impl Mul<DMat3> for DMat3 {
+    type Output = Self;
+
+    fn mul(self, other: DMat3) -> Self::Output {
+        Self {
+            data: self
+                .data
+                .map(|row| [0, 1, 2].map(|j| (0..3).map(|k| row[k] * other.data[k][j]).sum())),
+        }
+    }
+}
+
+impl MulAssign<DMat3> for DMat3 {
+    fn mul_assign(&mut self, other: DMat3) {
+        *self = *self * other;
+    }
+}
+
+impl Mul<DVec2> for DMat3 {
+    type Output = DVec2;
+
+    fn mul(self, vec: DVec2) -> Self::Output {
+        let x = self.data[0][0] * vec.x + self.data[1][0] * vec.y + self.data[2][0];
+        let y = self.data[0][1] * vec.x + self.data[1][1] * vec.y + self.data[2][1];
+        DVec2 { x, y }
+    }
+}
+
+impl Mul<DMat4> for DMat4 {
+    type Output = Self;
+
+    fn mul(self, other: DMat4) -> Self::Output {
+        Self {
+            data: self
+                .data
+                .map(|row| [0, 1, 2, 3].map(|j| (0..4).map(|k| row[k] * other.data[k][j]).sum())),
+        }
+    }
+}
+
+impl MulAssign<DMat4> for DMat4 {
+    fn mul_assign(&mut self, other: DMat4) {
+        *self = *self * other;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -497,4 +727,55 @@ mod tests {
             assert_inside_clip_space(clip_space);
         }
     }
+
+    #[test]
+    fn inverse_of_view_projection_round_trips_points() {
+        let projection = Mat4::perspective_projection(std::f32::consts::PI / 2.0, 1.5, 0.1, 20.0);
+        let view = Mat4::look_at(
+            &[1.0, 2.0, 5.0].into(),
+            &[0.0, 0.0, 0.0].into(),
+            &[0.0, 1.0, 0.0].into(),
+        );
+        let view_projection = view * projection;
+        let inverse = view_projection.inverse().expect("invertible");
+
+        let point = Vec3::new(0.5, -0.25, 1.0);
+        let ndc = view_projection.transform_point(&point).unwrap();
+        let back = inverse.transform_point(&ndc).unwrap();
+
+        assert!(Vec3::distance(&point, &back) < 1e-4, "{back:?}");
+        assert!(Mat4::zero().inverse().is_none());
+    }
+
+    #[test]
+    fn double_precision_transforms_far_from_the_origin() {
+        let transform = DMat4::transform(
+            &DVec3::new(1.0e8, 0.0, 0.0),
+            &DQuat::from_axis_angle(&DVec3::z_axis(), std::f64::consts::FRAC_PI_2),
+            &DVec3::splat(2.0),
+        );
+        let point = transform.transform_point(&DVec3::x_axis()).unwrap();
+        assert!(DVec3::distance(&point, &DVec3::new(1.0e8, 2.0, 0.0)) < 1e-9);
+
+        let back = transform
+            .inverse()
+            .unwrap()
+            .transform_point(&point)
+            .unwrap();
+        assert!(DVec3::distance(&back, &DVec3::x_axis()) < 1e-7, "{back:?}");
+        assert_eq!(
+            DMat4::from(Mat4::translation(1.0, 2.0, 3.0)).as_mat4(),
+            Mat4::translation(1.0, 2.0, 3.0)
+        );
+
+        let pose = DMat3::rotation(0.5) * DMat3::translation(3.0, -1.0);
+        assert_eq!(
+            pose.as_mat3(),
+            Mat3::rotation(0.5) * Mat3::translation(3.0, -1.0)
+        );
+        let moved = pose * DVec2::new(1.0, 1.0);
+        let back = pose.inverse().unwrap() * moved;
+        assert!(DVec2::distance(&back, &DVec2::one()) < 1e-12);
+        assert!(DMat3::zero().inverse().is_none());
+    }
 }
@@ -1,9 +1,19 @@
 #[macro_use]
 mod swizzle;
 
+pub mod color;
+pub mod curve;
 pub mod matrix;
 pub mod quaternion;
 pub mod shape;
+mod simd;
+pub mod transform;
 mod vector;
 
-pub use vector::{vec2::Vec2, vec3::Vec3, vec4::Vec4};
+pub use vector::{
+    dvec::{DVec2, DVec3, DVec4},
+    ivec::{IVec2, IVec3, UVec2, UVec3},
+    vec2::Vec2,
+    vec3::Vec3,
+    vec4::Vec4,
+};
@@ -2,9 +2,12 @@ use std::ops::{Mul, MulAssign};
 
 use vulkano_macros::BufferContents;
 
-use crate::math::vector::{vec3::Vec3, vec4::Vec4};
+use crate::math::{
+    simd,
+    vector::{dvec::DVec3, vec3::Vec3, vec4::Vec4},
+};
 
-#[derive(Debug, Clone, Copy, BufferContents)]
+#[derive(Debug, Clone, Copy, PartialEq, BufferContents)]
 #[repr(C)]
 pub struct Quaternion {
     pub a: f32,
@@ -138,6 +141,22 @@ impl Quaternion {
         }
     }
 
+    /// Turns `vector` by this (unit) quaternion: counter-clockwise around the axis, looking
+    /// down the axis towards the origin.
+    ///
+    /// ```rust
+    /// use chaos_engine::math::{Vec3, quaternion::Quaternion};
+    ///
+    /// let quarter = Quaternion::from_axis_angle(&Vec3::z_axis(), std::f32::consts::FRAC_PI_2);
+    /// let turned = quarter.rotate(&Vec3::x_axis());
+    /// assert!((turned - Vec3::y_axis()).length() < 1e-6);
+    /// ```
+    pub fn rotate(&self, vector: &Vec3) -> Vec3 {
+        let axis = Vec3::new(self.a, self.b, self.c);
+        let t = Vec3::cross(&axis, vector) * 2.0;
+        *vector + t * self.d + Vec3::cross(&axis, &t)
+    }
+
     pub fn normalize(&mut self) {
         let length = (self.a * self.a + self.b * self.b + self.c * self.c + self.d * self.d).sqrt();
         if length != 0.0 {
@@ -175,6 +194,110 @@ impl Mul<Quaternion> for Quaternion {
     type Output = Self;
 
     fn mul(self, other: Quaternion) -> Self::Output {
+        let [a, b, c, d] = simd::quat_mul(
+            [self.a, self.b, self.c, self.d],
+            [other.a, other.b, other.c, other.d],
+        );
+        Self { a, b, c, d }
+    }
+}
+
+impl MulAssign<Quaternion> for Quaternion {
+    fn mul_assign(&mut self, other: Quaternion) {
+        *self = *self * other;
+    }
+}
+
+/// Double precision `Quaternion`, to turn `DVec3`s without losing precision far from the
+/// origin.
+#[derive(Debug, Clone, Copy, PartialEq, BufferContents)]
+#[repr(C)]
+pub struct DQuat {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+}
+
+impl DQuat {
+    pub fn identity() -> Self {
+        Self {
+            a: 0.0,
+            b: 0.0,
+            c: 0.0,
+            d: 1.0,
+        }
+    }
+
+    pub fn from_axis_angle(axis: &DVec3, angle: f64) -> Self {
+        let (sin_half_angle, cos_half_angle) = (angle / 2.0).sin_cos();
+        Self {
+            a: axis.x * sin_half_angle,
+            b: axis.y * sin_half_angle,
+            c: axis.z * sin_half_angle,
+            d: cos_half_angle,
+        }
+    }
+
+    pub fn normalized(quaternion: Self) -> Self {
+        let length = (quaternion.a * quaternion.a
+            + quaternion.b * quaternion.b
+            + quaternion.c * quaternion.c
+            + quaternion.d * quaternion.d)
+            .sqrt();
+        if length != 0.0 {
+            Self {
+                a: quaternion.a / length,
+                b: quaternion.b / length,
+                c: quaternion.c / length,
+                d: quaternion.d / length,
+            }
+        } else {
+            Self::identity()
+        }
+    }
+
+    pub fn conjugate(&self) -> Self {
+        Self {
+            a: -self.a,
+            b: -self.b,
+            c: -self.c,
+            d: self.d,
+        }
+    }
+
+    /// Turns `vector` the same way as `Quaternion::rotate`.
+    pub fn rotate(&self, vector: &DVec3) -> DVec3 {
+        let axis = DVec3::new(self.a, self.b, self.c);
+        let t = DVec3::cross(&axis, vector) * 2.0;
+        *vector + t * self.d + DVec3::cross(&axis, &t)
+    }
+
+    pub fn as_quaternion(&self) -> Quaternion {
+        Quaternion {
+            a: self.a as f32,
+            b: self.b as f32,
+            c: self.c as f32,
+            d: self.d as f32,
+        }
+    }
+}
+
+impl From<Quaternion> for DQuat {
+    fn from(quat: Quaternion) -> Self {
+        Self {
+            a: quat.a as f64,
+            b: quat.b as f64,
+            c: quat.c as f64,
+            d: quat.d as f64,
+        }
+    }
+}
+
+impl Mul<DQuat> for DQuat {
+    type Output = Self;
+
+    fn mul(self, other: DQuat) -> Self::Output {
         Self {
             a: self.d * other.a + self.a * other.d + self.b * other.c - self.c * other.b,
             b: self.d * other.b - self.a * other.c + self.b * other.d + self.c * other.a,
@@ -184,8 +307,8 @@ impl Mul<Quaternion> for Quaternion {
     }
 }
 
-impl MulAssign<Quaternion> for Quaternion {
-    fn mul_assign(&mut self, other: Quaternion) {
+impl MulAssign<DQuat> for DQuat {
+    fn mul_assign(&mut self, other: DQuat) {
         *self = *self * other;
     }
 }
@@ -0,0 +1,67 @@
+use crate::math::Vec2;
+
+/// Axis aligned box.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb2 {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Aabb2 {
+    /// The box spanned by two opposite corners, in any order.
+    pub fn new(a: Vec2, b: Vec2) -> Self {
+        Self {
+            min: Vec2::min(&a, &b),
+            max: Vec2::max(&a, &b),
+        }
+    }
+
+    pub fn from_center(center: Vec2, half_extents: Vec2) -> Self {
+        Self::new(center - half_extents, center + half_extents)
+    }
+
+    /// The smallest box containing all `points`, `None` without points.
+    pub fn from_points(points: &[Vec2]) -> Option<Self> {
+        let first = *points.first()?;
+        Some(points.iter().fold(Self::new(first, first), |aabb, point| {
+            Self::new(Vec2::min(&aabb.min, point), Vec2::max(&aabb.max, point))
+        }))
+    }
+
+    pub fn center(&self) -> Vec2 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn half_extents(&self) -> Vec2 {
+        (self.max - self.min) * 0.5
+    }
+
+    pub fn area(&self) -> f32 {
+        let size = self.max - self.min;
+        size.x * size.y
+    }
+
+    pub fn overlaps(&self, other: &Aabb2) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+    }
+
+    pub fn union(&self, other: &Aabb2) -> Self {
+        Self::new(
+            Vec2::min(&self.min, &other.min),
+            Vec2::max(&self.max, &other.max),
+        )
+    }
+
+    /// Corners in counter-clockwise order, starting at `min`.
+    pub fn corners(&self) -> [Vec2; 4] {
+        [
+            self.min,
+            Vec2::new(self.max.x, self.min.y),
+            self.max,
+            Vec2::new(self.min.x, self.max.y),
+        ]
+    }
+}
@@ -0,0 +1,167 @@
+use crate::math::{
+    Vec3,
+    matrix::Mat4,
+    shape::ray::{Ray3, RayHit3D, components, slabs},
+};
+
+/// Axis aligned box in 3D.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb3 {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb3 {
+    /// The box spanned by two opposite corners, in any order.
+    pub fn new(a: Vec3, b: Vec3) -> Self {
+        Self {
+            min: Vec3::min(&a, &b),
+            max: Vec3::max(&a, &b),
+        }
+    }
+
+    pub fn from_center(center: Vec3, half_extents: Vec3) -> Self {
+        Self::new(center - half_extents, center + half_extents)
+    }
+
+    /// The smallest box containing all `points`, `None` without points.
+    pub fn from_points(points: &[Vec3]) -> Option<Self> {
+        let first = *points.first()?;
+        Some(points.iter().fold(Self::new(first, first), |aabb, point| {
+            Self::new(Vec3::min(&aabb.min, point), Vec3::max(&aabb.max, point))
+        }))
+    }
+
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn half_extents(&self) -> Vec3 {
+        (self.max - self.min) * 0.5
+    }
+
+    pub fn corners(&self) -> [Vec3; 8] {
+        let (min, max) = (self.min, self.max);
+        [
+            Vec3::new(min.x, min.y, min.z),
+            Vec3::new(max.x, min.y, min.z),
+            Vec3::new(min.x, max.y, min.z),
+            Vec3::new(max.x, max.y, min.z),
+            Vec3::new(min.x, min.y, max.z),
+            Vec3::new(max.x, min.y, max.z),
+            Vec3::new(min.x, max.y, max.z),
+            Vec3::new(max.x, max.y, max.z),
+        ]
+    }
+
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        Vec3::clamp(&point, &self.min, &self.max) == point
+    }
+
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        Vec3::clamp(&point, &self.min, &self.max)
+    }
+
+    pub fn overlaps(&self, other: &Aabb3) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+            && self.min.z <= other.max.z
+            && other.min.z <= self.max.z
+    }
+
+    pub fn union(&self, other: &Aabb3) -> Self {
+        Self::new(
+            Vec3::min(&self.min, &other.min),
+            Vec3::max(&self.max, &other.max),
+        )
+    }
+
+    /// The box around this one after `transform`, e.g. a model matrix built with
+    /// `Mat4::transform`.
+    pub fn transformed(&self, transform: &Mat4) -> Self {
+        let corners: Vec<Vec3> = self
+            .corners()
+            .iter()
+            .filter_map(|corner| transform.transform_point(corner))
+            .collect();
+        Self::from_points(&corners).unwrap_or(*self)
+    }
+
+    /// A ray starting inside hits at distance zero.
+    pub fn raycast(&self, ray: &Ray3, max_distance: f32) -> Option<RayHit3D> {
+        if self.contains_point(ray.origin) {
+            return Some(RayHit3D::new(ray, 0.0, -ray.direction));
+        }
+        let (enter, _, axis, side) = slabs(
+            components(ray.origin),
+            components(ray.direction),
+            components(self.min),
+            components(self.max),
+        )?;
+        let mut normal = [0.0; 3];
+        normal[axis] = side;
+        (0.0..=max_distance)
+            .contains(&enter)
+            .then(|| RayHit3D::new(ray, enter, Vec3::from(normal)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_and_overlaps() {
+        let aabb = Aabb3::from_center(Vec3::zero(), Vec3::one());
+
+        assert!(aabb.contains_point(Vec3::new(1.0, -1.0, 0.5)));
+        assert!(!aabb.contains_point(Vec3::new(1.1, 0.0, 0.0)));
+        assert_eq!(
+            aabb.closest_point(Vec3::new(3.0, 0.5, -3.0)),
+            Vec3::new(1.0, 0.5, -1.0)
+        );
+        assert!(aabb.overlaps(&Aabb3::new(Vec3::one(), Vec3::one() * 2.0)));
+        assert!(!aabb.overlaps(&Aabb3::new(Vec3::new(1.0, 1.0, 1.1), Vec3::one() * 2.0)));
+
+        let moved = aabb.transformed(
+            &(Mat4::rotation_z(std::f32::consts::FRAC_PI_4) * Mat4::translation(5.0, 0.0, 0.0)),
+        );
+        let reach = 2f32.sqrt();
+        assert!(Vec3::distance(&moved.min, &Vec3::new(5.0 - reach, -reach, -1.0)) < 1e-5);
+        assert!(Vec3::distance(&moved.max, &Vec3::new(5.0 + reach, reach, 1.0)) < 1e-5);
+    }
+
+    #[test]
+    fn raycasts_hit_the_entry_face() {
+        let aabb = Aabb3::from_center(Vec3::zero(), Vec3::one());
+
+        let hit = aabb
+            .raycast(&Ray3::new(Vec3::new(0.5, 5.0, 0.0), -Vec3::y_axis()), 10.0)
+            .unwrap();
+        assert_eq!(hit.distance, 4.0);
+        assert_eq!(hit.point, Vec3::new(0.5, 1.0, 0.0));
+        assert_eq!(hit.normal, Vec3::y_axis());
+
+        let diagonal = Ray3::new(Vec3::new(-3.0, -2.5, 0.0), Vec3::new(1.0, 1.0, 0.0));
+        assert_eq!(
+            aabb.raycast(&diagonal, 10.0).unwrap().normal,
+            -Vec3::x_axis()
+        );
+        assert!(
+            aabb.raycast(&Ray3::new(Vec3::new(0.0, 5.0, 0.0), Vec3::y_axis()), 10.0)
+                .is_none()
+        );
+        assert!(
+            aabb.raycast(&Ray3::new(Vec3::new(2.0, 5.0, 0.0), -Vec3::y_axis()), 10.0)
+                .is_none()
+        );
+        assert_eq!(
+            aabb.raycast(&Ray3::new(Vec3::zero(), Vec3::x_axis()), 1.0)
+                .unwrap()
+                .distance,
+            0.0
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use crate::math::Vec2;
+
+/// All points within `radius` of the segment from `start` to `end`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Capsule2 {
+    pub start: Vec2,
+    pub end: Vec2,
+    pub radius: f32,
+}
+
+impl Capsule2 {
+    pub fn new(start: Vec2, end: Vec2, radius: f32) -> Self {
+        Self {
+            start,
+            end,
+            radius: radius.max(0.0),
+        }
+    }
+
+    pub fn area(&self) -> f32 {
+        let length = Vec2::distance(&self.start, &self.end);
+        2.0 * self.radius * length + std::f32::consts::PI * self.radius * self.radius
+    }
+}
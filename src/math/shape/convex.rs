@@ -0,0 +1,355 @@
+//! Shared algorithms for the 2D shapes. Every shape is handled as a convex core (one point,
+//! a segment or a counter-clockwise polygon) inflated by a radius: a circle is a point with a
+//! radius, a capsule a segment with a radius, boxes and polygons have a radius of zero.
+
+use crate::math::Vec2;
+
+pub(crate) struct Core<'a> {
+    pub points: &'a [Vec2],
+    pub radius: f32,
+}
+
+/// Where two shapes touch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Contact2D {
+    /// Points from the first shape to the second.
+    pub normal: Vec2,
+    /// Largest overlap along the normal.
+    pub depth: f32,
+    /// One or two points, halfway between the two surfaces, with their own depth.
+    pub points: Vec<ContactPoint>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContactPoint {
+    pub point: Vec2,
+    pub depth: f32,
+}
+
+impl Contact2D {
+    pub fn flipped(mut self) -> Self {
+        self.normal = -self.normal;
+        self
+    }
+}
+
+/// Where a ray or segment first hits a shape.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit2D {
+    pub distance: f32,
+    pub point: Vec2,
+    /// Surface normal at the hit point, facing the ray.
+    pub normal: Vec2,
+}
+
+// Edges of the core, each with its outward normal on the left of travel being the outside.
+// A segment has two edges (one per side), a point none.
+pub(crate) fn edges(points: &[Vec2]) -> Vec<(Vec2, Vec2)> {
+    match points.len() {
+        0 | 1 => Vec::new(),
+        2 => vec![(points[0], points[1]), (points[1], points[0])],
+        len => (0..len)
+            .map(|i| (points[i], points[(i + 1) % len]))
+            .collect(),
+    }
+}
+
+pub(crate) fn outward_normal(start: Vec2, end: Vec2) -> Vec2 {
+    let edge = end - start;
+    let length = edge.length();
+    if length <= f32::EPSILON {
+        return Vec2::y_axis();
+    }
+    Vec2::new(edge.y, -edge.x) / length
+}
+
+pub(crate) fn closest_on_segment(point: Vec2, start: Vec2, end: Vec2) -> Vec2 {
+    let edge = end - start;
+    let length_squared = edge.length_squared();
+    if length_squared <= f32::EPSILON {
+        return start;
+    }
+    let t = (Vec2::dot(&(point - start), &edge) / length_squared).clamp(0.0, 1.0);
+    start + edge * t
+}
+
+fn segments_cross(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> bool {
+    let side = |p: Vec2, q: Vec2, r: Vec2| Vec2::cross(&(q - p), &(r - p));
+    let (d1, d2) = (side(c, d, a), side(c, d, b));
+    let (d3, d4) = (side(a, b, c), side(a, b, d));
+    ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0))
+        && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0))
+}
+
+// Inside a polygon core; points and segments have no inside.
+pub(crate) fn core_contains(points: &[Vec2], point: Vec2) -> bool {
+    points.len() >= 3
+        && edges(points)
+            .iter()
+            .all(|(start, end)| Vec2::dot(&outward_normal(*start, *end), &(point - *start)) <= 0.0)
+}
+
+// The closest point of the core's outline to `point`.
+pub(crate) fn closest_on_core(points: &[Vec2], point: Vec2) -> Vec2 {
+    match points.len() {
+        1 => points[0],
+        _ => edges(points)
+            .into_iter()
+            .map(|(start, end)| closest_on_segment(point, start, end))
+            .min_by(|a, b| {
+                (*a - point)
+                    .length_squared()
+                    .total_cmp(&(*b - point).length_squared())
+            })
+            .unwrap_or(point),
+    }
+}
+
+// Closest points of two cores that don't overlap; `None` if they do.
+pub(crate) fn separated_closest_points(a: &[Vec2], b: &[Vec2]) -> Option<(Vec2, Vec2)> {
+    if a.iter().any(|point| core_contains(b, *point))
+        || b.iter().any(|point| core_contains(a, *point))
+    {
+        return None;
+    }
+    let segments = |points: &[Vec2]| match points.len() {
+        1 => vec![(points[0], points[0])],
+        2 => vec![(points[0], points[1])],
+        _ => edges(points),
+    };
+    let (segments_a, segments_b) = (segments(a), segments(b));
+
+    let mut best: Option<(f32, Vec2, Vec2)> = None;
+    let mut consider = |on_a: Vec2, on_b: Vec2| {
+        let distance = (on_b - on_a).length_squared();
+        if best.is_none_or(|(best_distance, _, _)| distance < best_distance) {
+            best = Some((distance, on_a, on_b));
+        }
+    };
+    for (a_start, a_end) in &segments_a {
+        for (b_start, b_end) in &segments_b {
+            if segments_cross(*a_start, *a_end, *b_start, *b_end) {
+                return None;
+            }
+            for point in [*b_start, *b_end] {
+                consider(closest_on_segment(point, *a_start, *a_end), point);
+            }
+            for point in [*a_start, *a_end] {
+                consider(point, closest_on_segment(point, *b_start, *b_end));
+            }
+        }
+    }
+    let (distance, on_a, on_b) = best?;
+    (distance > 0.0).then_some((on_a, on_b))
+}
+
+pub(crate) fn contact(a: &Core, b: &Core) -> Option<Contact2D> {
+    let radii = a.radius + b.radius;
+    if let Some((on_a, on_b)) = separated_closest_points(a.points, b.points) {
+        let offset = on_b - on_a;
+        let distance = offset.length();
+        if distance > radii {
+            return None;
+        }
+        let normal = offset / distance;
+        let depth = radii - distance;
+        return Some(Contact2D {
+            normal,
+            depth,
+            points: vec![ContactPoint {
+                point: on_a + normal * (a.radius - depth * 0.5),
+                depth,
+            }],
+        });
+    }
+
+    // The cores overlap: the face (of either core) the other core sticks out of the least
+    // is the reference, and the incident edge of the other core is clipped against it.
+    let face_a = least_penetrated_face(a.points, b.points);
+    let face_b = least_penetrated_face(b.points, a.points);
+    match (face_a, face_b) {
+        (None, None) => {
+            // Two points in the same place: any direction will do.
+            let normal = Vec2::y_axis();
+            Some(Contact2D {
+                normal,
+                depth: radii,
+                points: vec![ContactPoint {
+                    point: a.points[0],
+                    depth: radii,
+                }],
+            })
+        }
+        (Some((face, separation_a)), Some((_, separation_b)))
+            if separation_a + 1e-3 >= separation_b =>
+        {
+            clip_contact(a, b, face)
+        }
+        (Some((face, _)), None) => clip_contact(a, b, face),
+        (_, Some((face, _))) => clip_contact(b, a, face).map(Contact2D::flipped),
+    }
+}
+
+// The edge of `reference` with the largest separation of `other` from it.
+fn least_penetrated_face(reference: &[Vec2], other: &[Vec2]) -> Option<(usize, f32)> {
+    edges(reference)
+        .iter()
+        .enumerate()
+        .map(|(index, (start, end))| {
+            let normal = outward_normal(*start, *end);
+            let separation = other
+                .iter()
+                .map(|point| Vec2::dot(&normal, &(*point - *start)))
+                .fold(f32::INFINITY, f32::min);
+            (index, separation)
+        })
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+}
+
+fn clip_contact(reference: &Core, incident: &Core, face: usize) -> Option<Contact2D> {
+    let (start, end) = edges(reference.points)[face];
+    let normal = outward_normal(start, end);
+
+    let mut candidates: Vec<Vec2> = match incident.points.len() {
+        1 => vec![incident.points[0]],
+        _ => {
+            let (incident_start, incident_end) = edges(incident.points).into_iter().min_by(
+                |(a_start, a_end), (b_start, b_end)| {
+                    Vec2::dot(&outward_normal(*a_start, *a_end), &normal)
+                        .total_cmp(&Vec2::dot(&outward_normal(*b_start, *b_end), &normal))
+                },
+            )?;
+            vec![incident_start, incident_end]
+        }
+    };
+    if candidates.len() == 2 {
+        let tangent = Vec2::normalized(&(end - start));
+        let clipped = clip(
+            [candidates[0], candidates[1]],
+            -tangent,
+            -Vec2::dot(&tangent, &start),
+        )
+        .and_then(|edge| clip(edge, tangent, Vec2::dot(&tangent, &end)));
+        candidates = match clipped {
+            Some(edge) => edge.to_vec(),
+            // The incident edge lies beside the face; its closest end is the contact.
+            None => vec![closest_on_core(incident.points, (start + end) * 0.5)],
+        };
+    }
+
+    let radii = reference.radius + incident.radius;
+    let points: Vec<ContactPoint> = candidates
+        .into_iter()
+        .filter_map(|point| {
+            let depth = Vec2::dot(&normal, &(start - point)) + radii;
+            (depth >= 0.0).then(|| ContactPoint {
+                point: point
+                    + normal
+                        * ((Vec2::dot(&normal, &(start - point)) + reference.radius
+                            - incident.radius)
+                            * 0.5),
+                depth,
+            })
+        })
+        .collect();
+    let depth = points
+        .iter()
+        .map(|point| point.depth)
+        .fold(f32::NEG_INFINITY, f32::max);
+    (!points.is_empty()).then_some(Contact2D {
+        normal,
+        depth,
+        points,
+    })
+}
+
+// Keeps the part of the segment where `dot(normal, point) <= offset`.
+fn clip(edge: [Vec2; 2], normal: Vec2, offset: f32) -> Option<[Vec2; 2]> {
+    let distances = edge.map(|point| Vec2::dot(&normal, &point) - offset);
+    match (distances[0] <= 0.0, distances[1] <= 0.0) {
+        (true, true) => Some(edge),
+        (false, false) => None,
+        (inside_first, _) => {
+            let t = distances[0] / (distances[0] - distances[1]);
+            let crossing = edge[0] + (edge[1] - edge[0]) * t;
+            Some(if inside_first {
+                [edge[0], crossing]
+            } else {
+                [crossing, edge[1]]
+            })
+        }
+    }
+}
+
+pub(crate) fn contains(core: &Core, point: Vec2) -> bool {
+    core_contains(core.points, point)
+        || (closest_on_core(core.points, point) - point).length_squared()
+            <= core.radius * core.radius
+}
+
+pub(crate) fn closest_point(core: &Core, point: Vec2) -> Vec2 {
+    if contains(core, point) {
+        return point;
+    }
+    let on_core = closest_on_core(core.points, point);
+    on_core + Vec2::normalized(&(point - on_core)) * core.radius
+}
+
+// `direction` must have length one.
+pub(crate) fn raycast(
+    core: &Core,
+    origin: Vec2,
+    direction: Vec2,
+    max_distance: f32,
+) -> Option<RayHit2D> {
+    if contains(core, origin) {
+        return Some(RayHit2D {
+            distance: 0.0,
+            point: origin,
+            normal: -direction,
+        });
+    }
+
+    let mut best: Option<RayHit2D> = None;
+    let mut consider = |distance: f32, normal: Vec2| {
+        if (0.0..=max_distance).contains(&distance)
+            && best.is_none_or(|hit| distance < hit.distance)
+        {
+            best = Some(RayHit2D {
+                distance,
+                point: origin + direction * distance,
+                normal,
+            });
+        }
+    };
+
+    if core.radius > 0.0 {
+        for center in core.points {
+            let offset = origin - *center;
+            let b = Vec2::dot(&offset, &direction);
+            let c = offset.length_squared() - core.radius * core.radius;
+            let discriminant = b * b - c;
+            if discriminant >= 0.0 {
+                let distance = -b - discriminant.sqrt();
+                consider(distance, (offset + direction * distance) / core.radius);
+            }
+        }
+    }
+    // Faces, moved out by the radius; only entering them counts.
+    for (start, end) in edges(core.points) {
+        let normal = outward_normal(start, end);
+        let approach = Vec2::dot(&direction, &normal);
+        if approach >= 0.0 {
+            continue;
+        }
+        let (start, end) = (start + normal * core.radius, end + normal * core.radius);
+        let distance = Vec2::dot(&(start - origin), &normal) / approach;
+        let point = origin + direction * distance;
+        let edge = end - start;
+        let t = Vec2::dot(&(point - start), &edge) / edge.length_squared().max(f32::EPSILON);
+        if (0.0..=1.0).contains(&t) {
+            consider(distance, normal);
+        }
+    }
+    best
+}
@@ -0,0 +1,134 @@
+use crate::math::{
+    Vec3,
+    matrix::Mat4,
+    shape::{aabb3::Aabb3, plane::Plane, sphere::Sphere},
+};
+
+/// The volume a camera sees, bounded by six planes facing inwards, for culling.
+///
+/// ```rust
+/// use chaos_engine::math::{
+///     Vec3,
+///     matrix::Mat4,
+///     shape::{frustum::Frustum, sphere::Sphere},
+/// };
+///
+/// let view = Mat4::look_at(&Vec3::new(0.0, 0.0, 10.0), &Vec3::zero(), &Vec3::y_axis());
+/// let projection = Mat4::perspective_projection(1.0, 16.0 / 9.0, 0.1, 100.0);
+/// let frustum = Frustum::from_view_projection(&(view * projection));
+///
+/// assert!(frustum.intersects_sphere(&Sphere::new(Vec3::zero(), 1.0)));
+/// assert!(!frustum.intersects_sphere(&Sphere::new(Vec3::new(0.0, 0.0, 20.0), 1.0)));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Frustum {
+    /// Left, right, bottom, top, near and far.
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+    /// Extracts the planes from a view-projection matrix (`view * projection`, as the
+    /// shaders apply them) with depths from -1 to 1, like `Mat4::perspective_projection`
+    /// and `Mat4::orthographic_projection` produce.
+    pub fn from_view_projection(view_projection: &Mat4) -> Self {
+        // With row vectors the clip coordinates are dot products with the columns; a point
+        // is inside when -w <= x, y, z <= w.
+        let column = |index: usize| view_projection.data.map(|row| row[index]);
+        let w = column(3);
+        let plane = |other: [f32; 4], sign: f32| {
+            let [x, y, z, d] = [0, 1, 2, 3].map(|i| w[i] + sign * other[i]);
+            Plane::new(Vec3::new(x, y, z), -d)
+        };
+        Self {
+            planes: [
+                plane(column(0), 1.0),
+                plane(column(0), -1.0),
+                plane(column(1), 1.0),
+                plane(column(1), -1.0),
+                plane(column(2), 1.0),
+                plane(column(2), -1.0),
+            ],
+        }
+    }
+
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.signed_distance(point) >= 0.0)
+    }
+
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.signed_distance(sphere.center) >= -sphere.radius)
+    }
+
+    /// Conservative: a box near a corner of the frustum may pass without being seen, but
+    /// a box that is seen always passes.
+    pub fn intersects_aabb(&self, aabb: &Aabb3) -> bool {
+        self.planes.iter().all(|plane| {
+            // The corner furthest along the plane's normal.
+            let pick = |normal: f32, min: f32, max: f32| if normal >= 0.0 { max } else { min };
+            let corner = Vec3::new(
+                pick(plane.normal.x, aabb.min.x, aabb.max.x),
+                pick(plane.normal.y, aabb.min.y, aabb.max.y),
+                pick(plane.normal.z, aabb.min.z, aabb.max.z),
+            );
+            plane.signed_distance(corner) >= 0.0
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    // Looking down -z from z = 5 with a 90 degree field of view, so at distance d the
+    // frustum is d wide to each side.
+    fn frustum() -> Frustum {
+        let view = Mat4::look_at(&Vec3::new(0.0, 0.0, 5.0), &Vec3::zero(), &Vec3::y_axis());
+        let projection = Mat4::perspective_projection(FRAC_PI_2, 1.0, 0.1, 100.0);
+        Frustum::from_view_projection(&(view * projection))
+    }
+
+    #[test]
+    fn perspective_planes() {
+        let frustum = frustum();
+
+        assert!(frustum.contains_point(Vec3::zero()));
+        assert!(frustum.contains_point(Vec3::new(4.9, 0.0, 0.0)));
+        assert!(!frustum.contains_point(Vec3::new(5.1, 0.0, 0.0)));
+        assert!(!frustum.contains_point(Vec3::new(0.0, -5.1, 0.0)));
+        assert!(!frustum.contains_point(Vec3::new(0.0, 0.0, 4.95)));
+        assert!(frustum.contains_point(Vec3::new(0.0, 0.0, -94.0)));
+        assert!(!frustum.contains_point(Vec3::new(0.0, 0.0, -96.0)));
+
+        let near = frustum.planes[4];
+        assert!((near.normal - -Vec3::z_axis()).length() < 1e-5);
+        assert!((near.signed_distance(Vec3::new(0.0, 0.0, 4.9))).abs() < 1e-4);
+    }
+
+    #[test]
+    fn culling() {
+        let frustum = frustum();
+
+        assert!(frustum.intersects_sphere(&Sphere::new(Vec3::new(5.5, 0.0, 0.0), 1.0)));
+        assert!(!frustum.intersects_sphere(&Sphere::new(Vec3::new(8.0, 0.0, 0.0), 1.0)));
+        assert!(!frustum.intersects_sphere(&Sphere::new(Vec3::new(0.0, 0.0, 7.0), 1.0)));
+
+        let straddling = Aabb3::new(Vec3::new(4.0, -1.0, -1.0), Vec3::new(10.0, 1.0, 1.0));
+        assert!(frustum.intersects_aabb(&straddling));
+        let behind = Aabb3::new(Vec3::new(-1.0, -1.0, 6.0), Vec3::new(1.0, 1.0, 8.0));
+        assert!(!frustum.intersects_aabb(&behind));
+        let above = Aabb3::new(Vec3::new(-1.0, 7.0, -1.0), Vec3::new(1.0, 9.0, 1.0));
+        assert!(!frustum.intersects_aabb(&above));
+
+        let orthographic = Frustum::from_view_projection(&Mat4::orthographic_projection(
+            -2.0, 2.0, -1.0, 1.0, 0.0, 10.0,
+        ));
+        assert!(orthographic.contains_point(Vec3::new(1.9, 0.9, -5.0)));
+        assert!(!orthographic.contains_point(Vec3::new(2.1, 0.0, -5.0)));
+        assert!(!orthographic.contains_point(Vec3::new(0.0, 0.0, 1.0)));
+    }
+}
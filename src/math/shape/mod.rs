@@ -1 +1,16 @@
+pub mod aabb;
+pub mod aabb3;
+pub mod capsule;
+pub mod circle;
+pub mod convex;
+pub mod frustum;
+pub mod obb;
+pub mod obb3;
+pub mod plane;
+pub mod polygon;
+pub mod polygon2d;
+pub mod ray;
+pub mod shape2d;
+pub mod sphere;
 pub mod triangle;
+pub mod triangle3d;
@@ -0,0 +1,37 @@
+use crate::math::{Vec2, matrix::Mat3};
+
+/// Oriented box: a box of `half_extents` turned by `rotation` (like `Mat3::rotation`)
+/// around its center.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Obb2 {
+    pub center: Vec2,
+    pub half_extents: Vec2,
+    pub rotation: f32,
+}
+
+impl Obb2 {
+    pub fn new(center: Vec2, half_extents: Vec2, rotation: f32) -> Self {
+        Self {
+            center,
+            half_extents: Vec2::new(half_extents.x.abs(), half_extents.y.abs()),
+            rotation,
+        }
+    }
+
+    pub fn area(&self) -> f32 {
+        4.0 * self.half_extents.x * self.half_extents.y
+    }
+
+    /// Corners in counter-clockwise order.
+    pub fn corners(&self) -> [Vec2; 4] {
+        let rotation = Mat3::rotation(self.rotation);
+        let (x, y) = (self.half_extents.x, self.half_extents.y);
+        [
+            Vec2::new(-x, -y),
+            Vec2::new(x, -y),
+            Vec2::new(x, y),
+            Vec2::new(-x, y),
+        ]
+        .map(|corner| self.center + rotation * corner)
+    }
+}
@@ -0,0 +1,214 @@
+use crate::math::{
+    Vec3,
+    matrix::Mat4,
+    quaternion::Quaternion,
+    shape::{
+        aabb3::Aabb3,
+        ray::{Ray3, RayHit3D, components, slabs},
+        sphere::Sphere,
+    },
+};
+
+/// Box turned to lie along three perpendicular unit `axes`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Obb3 {
+    pub center: Vec3,
+    pub half_extents: Vec3,
+    pub axes: [Vec3; 3],
+}
+
+impl Obb3 {
+    /// The box with `half_extents` along the world axes turned by `rotation`.
+    pub fn new(center: Vec3, half_extents: Vec3, rotation: &Quaternion) -> Self {
+        Self {
+            center,
+            half_extents,
+            axes: [Vec3::x_axis(), Vec3::y_axis(), Vec3::z_axis()]
+                .map(|axis| Vec3::normalized(&rotation.rotate(&axis))),
+        }
+    }
+
+    /// `aabb` placed by a model matrix of scale, rotation and translation.
+    pub fn from_aabb(aabb: &Aabb3, transform: &Mat4) -> Self {
+        let center = transform
+            .transform_point(&aabb.center())
+            .unwrap_or(aabb.center());
+        let half_extents = components(aabb.half_extents());
+        let mut scaled = [0.0; 3];
+        let axes = [0, 1, 2].map(|row| {
+            let [x, y, z, _] = transform.data[row];
+            let axis = Vec3::new(x, y, z);
+            scaled[row] = half_extents[row] * axis.length();
+            Vec3::normalized(&axis)
+        });
+        Self {
+            center,
+            half_extents: Vec3::from(scaled),
+            axes,
+        }
+    }
+
+    // `point` in the box's frame, around its center.
+    fn local_point(&self, point: Vec3) -> Vec3 {
+        let offset = point - self.center;
+        Vec3::new(
+            Vec3::dot(&offset, &self.axes[0]),
+            Vec3::dot(&offset, &self.axes[1]),
+            Vec3::dot(&offset, &self.axes[2]),
+        )
+    }
+
+    fn world_point(&self, point: Vec3) -> Vec3 {
+        self.center + self.axes[0] * point.x + self.axes[1] * point.y + self.axes[2] * point.z
+    }
+
+    pub fn corners(&self) -> [Vec3; 8] {
+        let h = self.half_extents;
+        Aabb3::new(-h, h)
+            .corners()
+            .map(|corner| self.world_point(corner))
+    }
+
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        Aabb3::new(-self.half_extents, self.half_extents).contains_point(self.local_point(point))
+    }
+
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        let local = Aabb3::new(-self.half_extents, self.half_extents)
+            .closest_point(self.local_point(point));
+        self.world_point(local)
+    }
+
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        sphere.contains_point(self.closest_point(sphere.center))
+    }
+
+    /// Separating axis test over the 15 axes that can part two boxes.
+    pub fn overlaps(&self, other: &Obb3) -> bool {
+        let offset = other.center - self.center;
+        let (a, b) = (
+            components(self.half_extents),
+            components(other.half_extents),
+        );
+        let mut axes: Vec<Vec3> = self.axes.iter().chain(&other.axes).copied().collect();
+        for first in &self.axes {
+            for second in &other.axes {
+                let axis = Vec3::cross(first, second);
+                // Parallel edges add nothing the face axes don't already cover.
+                if axis.length_squared() > 1e-6 {
+                    axes.push(axis);
+                }
+            }
+        }
+        axes.iter().all(|axis| {
+            let reach = |axes: &[Vec3; 3], half: &[f32; 3]| -> f32 {
+                (0..3)
+                    .map(|i| (Vec3::dot(&axes[i], axis) * half[i]).abs())
+                    .sum()
+            };
+            Vec3::dot(&offset, axis).abs() <= reach(&self.axes, &a) + reach(&other.axes, &b)
+        })
+    }
+
+    pub fn overlaps_aabb(&self, aabb: &Aabb3) -> bool {
+        self.overlaps(&Obb3 {
+            center: aabb.center(),
+            half_extents: aabb.half_extents(),
+            axes: [Vec3::x_axis(), Vec3::y_axis(), Vec3::z_axis()],
+        })
+    }
+
+    /// A ray starting inside hits at distance zero.
+    pub fn raycast(&self, ray: &Ray3, max_distance: f32) -> Option<RayHit3D> {
+        if self.contains_point(ray.origin) {
+            return Some(RayHit3D::new(ray, 0.0, -ray.direction));
+        }
+        let direction = self.local_point(self.center + ray.direction);
+        let (enter, _, axis, side) = slabs(
+            components(self.local_point(ray.origin)),
+            components(direction),
+            components(-self.half_extents),
+            components(self.half_extents),
+        )?;
+        (0.0..=max_distance)
+            .contains(&enter)
+            .then(|| RayHit3D::new(ray, enter, self.axes[axis] * side))
+    }
+}
+
+impl From<Aabb3> for Obb3 {
+    fn from(aabb: Aabb3) -> Self {
+        Self::from_aabb(&aabb, &Mat4::identity())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_4;
+
+    fn close(a: Vec3, b: Vec3) -> bool {
+        Vec3::distance(&a, &b) < 1e-5
+    }
+
+    #[test]
+    fn turned_box_points() {
+        let obb = Obb3::from_aabb(
+            &Aabb3::from_center(Vec3::zero(), Vec3::new(2.0, 0.5, 0.5)),
+            &(Mat4::rotation_z(FRAC_PI_4) * Mat4::translation(0.0, 0.0, -3.0)),
+        );
+        // `rotation_z` turns the long x axis clockwise, towards (1, -1).
+        let diagonal = Vec3::normalized(&Vec3::new(1.0, -1.0, 0.0)) * 1.5;
+
+        assert!(close(obb.center, Vec3::new(0.0, 0.0, -3.0)));
+        assert!(obb.contains_point(Vec3::new(0.0, 0.0, -3.0) + diagonal));
+        assert!(!obb.contains_point(Vec3::new(1.5, 0.0, -3.0)));
+        assert!(close(
+            obb.closest_point(Vec3::new(0.0, 0.0, -3.0) + diagonal * 2.0),
+            Vec3::new(0.0, 0.0, -3.0) + diagonal * (2.0 / 1.5)
+        ));
+        assert!(obb.intersects_sphere(&Sphere::new(Vec3::new(0.0, 0.0, -2.0), 0.6)));
+        assert!(!obb.intersects_sphere(&Sphere::new(Vec3::new(0.0, 0.0, -1.0), 0.6)));
+    }
+
+    #[test]
+    fn separating_axes() {
+        let upright = Obb3::from(Aabb3::from_center(Vec3::zero(), Vec3::one()));
+        let turned = Obb3::new(
+            Vec3::new(2.3, 0.0, 0.0),
+            Vec3::one(),
+            &Quaternion::from_axis_angle(&Vec3::z_axis(), FRAC_PI_4),
+        );
+        // The turned box's edge reaches sqrt(2) towards the upright one.
+        assert!(upright.overlaps(&turned));
+        let apart = Obb3 {
+            center: Vec3::new(2.5, 0.0, 0.0),
+            ..turned
+        };
+        assert!(!upright.overlaps(&apart));
+        assert!(turned.overlaps_aabb(&Aabb3::new(
+            Vec3::new(1.0, -0.1, -0.1),
+            Vec3::new(1.2, 0.1, 0.1)
+        )));
+    }
+
+    #[test]
+    fn raycast_uses_the_turned_faces() {
+        let obb = Obb3::new(
+            Vec3::zero(),
+            Vec3::one(),
+            &Quaternion::from_axis_angle(&Vec3::z_axis(), FRAC_PI_4),
+        );
+        let hit = obb
+            .raycast(&Ray3::new(Vec3::new(-5.0, 0.0, 0.0), Vec3::x_axis()), 10.0)
+            .unwrap();
+
+        assert!((hit.distance - (5.0 - 2f32.sqrt())).abs() < 1e-5);
+        assert!(hit.normal.x < 0.0);
+        assert!((hit.normal.length() - 1.0).abs() < 1e-5);
+        assert!(
+            obb.raycast(&Ray3::new(Vec3::new(-5.0, 1.5, 0.0), Vec3::x_axis()), 10.0)
+                .is_none()
+        );
+    }
+}
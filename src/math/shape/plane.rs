@@ -0,0 +1,98 @@
+use crate::math::{
+    Vec3,
+    shape::ray::{Ray3, RayHit3D},
+};
+
+/// The points `p` with `dot(normal, p) == distance`; `normal` has length one and points to
+/// the plane's front.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Plane {
+    pub normal: Vec3,
+    pub distance: f32,
+}
+
+impl Plane {
+    /// `normal` must not be zero; both values are scaled so the normal has length one.
+    pub fn new(normal: Vec3, distance: f32) -> Self {
+        let length = normal.length();
+        Self {
+            normal: normal / length,
+            distance: distance / length,
+        }
+    }
+
+    pub fn from_point_normal(point: Vec3, normal: Vec3) -> Self {
+        let normal = Vec3::normalized(&normal);
+        Self {
+            normal,
+            distance: Vec3::dot(&normal, &point),
+        }
+    }
+
+    /// The plane through three points, facing the side they run counter-clockwise on.
+    /// `None` if the points lie on one line.
+    pub fn from_points(a: Vec3, b: Vec3, c: Vec3) -> Option<Self> {
+        let normal = Vec3::cross(&(b - a), &(c - a));
+        (normal.length_squared() > f32::EPSILON * f32::EPSILON)
+            .then(|| Self::from_point_normal(a, normal))
+    }
+
+    /// Positive in front of the plane, negative behind it.
+    pub fn signed_distance(&self, point: Vec3) -> f32 {
+        Vec3::dot(&self.normal, &point) - self.distance
+    }
+
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        point - self.normal * self.signed_distance(point)
+    }
+
+    /// Hits either side of the plane.
+    pub fn raycast(&self, ray: &Ray3, max_distance: f32) -> Option<RayHit3D> {
+        let approach = Vec3::dot(&self.normal, &ray.direction);
+        if approach.abs() <= f32::EPSILON {
+            return None;
+        }
+        let distance = -self.signed_distance(ray.origin) / approach;
+        let normal = if approach < 0.0 {
+            self.normal
+        } else {
+            -self.normal
+        };
+        (0.0..=max_distance)
+            .contains(&distance)
+            .then(|| RayHit3D::new(ray, distance, normal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances_and_casts() {
+        let plane = Plane::from_points(
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(0.0, 1.0, 1.0),
+            Vec3::new(1.0, 1.0, 0.0),
+        )
+        .unwrap();
+        assert_eq!(plane, Plane::new(Vec3::new(0.0, 2.0, 0.0), 2.0));
+        assert_eq!(plane.signed_distance(Vec3::new(5.0, 3.0, 1.0)), 2.0);
+        assert_eq!(
+            plane.closest_point(Vec3::new(5.0, -3.0, 1.0)),
+            Vec3::new(5.0, 1.0, 1.0)
+        );
+
+        let from_below = Ray3::new(Vec3::zero(), Vec3::new(0.0, 1.0, 1.0));
+        let hit = plane.raycast(&from_below, 10.0).unwrap();
+        assert!((hit.distance - 2f32.sqrt()).abs() < 1e-6);
+        assert_eq!(hit.normal, -plane.normal);
+        assert!(plane.raycast(&from_below, 1.0).is_none());
+        assert!(
+            plane
+                .raycast(&Ray3::new(Vec3::zero(), Vec3::x_axis()), 10.0)
+                .is_none()
+        );
+        assert!(Plane::from_points(Vec3::zero(), Vec3::one(), Vec3::one() * 2.0).is_none());
+    }
+}
@@ -0,0 +1,66 @@
+use crate::math::Vec2;
+
+/// Convex polygon with counter-clockwise points.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConvexPolygon2 {
+    points: Vec<Vec2>,
+}
+
+impl ConvexPolygon2 {
+    /// The convex hull of `points`, so concave outlines become their hull. `None` unless at
+    /// least three of the points don't lie on one line.
+    pub fn from_points(points: &[Vec2]) -> Option<Self> {
+        let points = convex_hull(points);
+        (points.len() >= 3).then_some(Self { points })
+    }
+
+    pub fn points(&self) -> &[Vec2] {
+        &self.points
+    }
+
+    pub fn area(&self) -> f32 {
+        (0..self.points.len())
+            .map(|i| Vec2::cross(&self.points[i], &self.points[(i + 1) % self.points.len()]))
+            .sum::<f32>()
+            * 0.5
+    }
+
+    pub fn centroid(&self) -> Vec2 {
+        let mut centroid = Vec2::zero();
+        let mut area = 0.0;
+        for i in 0..self.points.len() {
+            let (a, b) = (self.points[i], self.points[(i + 1) % self.points.len()]);
+            let cross = Vec2::cross(&a, &b);
+            centroid += (a + b) * cross;
+            area += cross;
+        }
+        centroid / (3.0 * area)
+    }
+}
+
+// Andrew's monotone chain; the hull is counter-clockwise without collinear points.
+pub(crate) fn convex_hull(points: &[Vec2]) -> Vec<Vec2> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    let turn = |o: &Vec2, a: &Vec2, b: &Vec2| Vec2::cross(&(*a - *o), &(*b - *o));
+    let mut hull: Vec<Vec2> = Vec::with_capacity(sorted.len() * 2);
+    for pass in [sorted.clone(), sorted.into_iter().rev().collect()] {
+        let start = hull.len();
+        for point in pass {
+            while hull.len() >= start + 2
+                && turn(&hull[hull.len() - 2], &hull[hull.len() - 1], &point) <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        // The last point of each chain starts the other one.
+        hull.pop();
+    }
+    hull
+}
@@ -0,0 +1,582 @@
+use crate::math::{
+    Vec2,
+    shape::{aabb::Aabb2, circle::Circle, polygon::ConvexPolygon2, triangle::Triangle2D},
+};
+
+/// Simple polygon (an outline that doesn't cross itself), convex or not, in either winding.
+///
+/// ```rust
+/// use chaos_engine::math::{Vec2, shape::polygon2d::Polygon2D};
+///
+/// // An L shape.
+/// let polygon = Polygon2D::new(vec![
+///     Vec2::new(0.0, 0.0),
+///     Vec2::new(2.0, 0.0),
+///     Vec2::new(2.0, 1.0),
+///     Vec2::new(1.0, 1.0),
+///     Vec2::new(1.0, 2.0),
+///     Vec2::new(0.0, 2.0),
+/// ]);
+///
+/// assert_eq!(polygon.area(), 3.0);
+/// assert_eq!(polygon.triangulate().len(), 4);
+/// assert_eq!(polygon.convex_decomposition().len(), 2);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Polygon2D {
+    pub points: Vec<Vec2>,
+}
+
+impl Polygon2D {
+    pub fn new(points: Vec<Vec2>) -> Self {
+        Self { points }
+    }
+
+    /// Positive for counter-clockwise points, negative for clockwise ones.
+    pub fn signed_area(&self) -> f32 {
+        signed_area(&self.points)
+    }
+
+    pub fn area(&self) -> f32 {
+        self.signed_area().abs()
+    }
+
+    pub fn is_ccw(&self) -> bool {
+        self.signed_area() > 0.0
+    }
+
+    /// Reverses the points if needed so they run counter-clockwise.
+    pub fn normalize_winding(&mut self) {
+        if self.signed_area() < 0.0 {
+            self.points.reverse();
+        }
+    }
+
+    pub fn is_convex(&self) -> bool {
+        let sign = self.signed_area().signum();
+        is_convex(&self.points, sign)
+    }
+
+    /// Even-odd test, so it works for either winding.
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        let mut inside = false;
+        for (a, b) in edges(&self.points) {
+            if (a.y > point.y) != (b.y > point.y)
+                && point.x < a.x + (point.y - a.y) * (b.x - a.x) / (b.y - a.y)
+            {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+
+    pub fn bounding_box(&self) -> Option<Aabb2> {
+        Aabb2::from_points(&self.points)
+    }
+
+    /// The smallest circle around all points, `None` without points.
+    pub fn bounding_circle(&self) -> Option<Circle> {
+        let points = &self.points;
+        let mut circle = Circle::new(*points.first()?, 0.0);
+        // Incremental Welzl: each point outside the circle so far lies on the boundary of
+        // the circle around it and the points before it.
+        for i in 1..points.len() {
+            if encloses(&circle, points[i]) {
+                continue;
+            }
+            circle = Circle::new(points[i], 0.0);
+            for j in 0..i {
+                if encloses(&circle, points[j]) {
+                    continue;
+                }
+                circle = circle_through_two(points[i], points[j]);
+                for k in 0..j {
+                    if !encloses(&circle, points[k]) {
+                        circle = circle_through_three(points[i], points[j], points[k]);
+                    }
+                }
+            }
+        }
+        Some(circle)
+    }
+
+    /// `None` if all points lie on one line.
+    pub fn convex_hull(&self) -> Option<ConvexPolygon2> {
+        ConvexPolygon2::from_points(&self.points)
+    }
+
+    /// Counter-clockwise triangles covering the polygon, by ear clipping. Collinear and
+    /// repeated points are skipped; a self-intersecting outline gets as far as it can.
+    pub fn triangulate(&self) -> Vec<Triangle2D> {
+        self.triangulate_with_holes(&[])
+    }
+
+    /// Like [`Polygon2D::triangulate`], leaving out the `holes`, which must lie inside the
+    /// polygon and not touch each other. Each hole is joined to the outline by a bridge to
+    /// a vertex it can see, which makes one outline to clip ears from.
+    pub fn triangulate_with_holes(&self, holes: &[Polygon2D]) -> Vec<Triangle2D> {
+        let mut outline = ccw(&self.points);
+        if outline.len() < 3 {
+            return Vec::new();
+        }
+
+        // Rightmost holes first, so later bridges can't cross earlier ones.
+        let mut holes: Vec<Vec<Vec2>> = holes
+            .iter()
+            .map(|hole| {
+                let mut points = ccw(&hole.points);
+                points.reverse();
+                points
+            })
+            .filter(|points| points.len() >= 3)
+            .collect();
+        holes.sort_by(|a, b| rightmost(b).1.x.total_cmp(&rightmost(a).1.x));
+        for hole in holes {
+            if let Some(merged) = bridge_hole(&outline, &hole) {
+                outline = merged;
+            }
+        }
+        ear_clip(&outline)
+    }
+
+    /// Convex pieces covering the polygon: its triangles, merged across shared edges while
+    /// the result stays convex (Hertel-Mehlhorn, at most four times the fewest pieces).
+    pub fn convex_decomposition(&self) -> Vec<ConvexPolygon2> {
+        let mut pieces: Vec<Vec<Vec2>> = self
+            .triangulate()
+            .into_iter()
+            .map(|triangle| vec![triangle.a, triangle.b, triangle.c])
+            .collect();
+
+        let mut merged_any = true;
+        while merged_any {
+            merged_any = false;
+            'search: for i in 0..pieces.len() {
+                for j in i + 1..pieces.len() {
+                    if let Some(merged) = merge_pieces(&pieces[i], &pieces[j])
+                        && is_convex(&merged, 1.0)
+                    {
+                        pieces[i] = merged;
+                        pieces.swap_remove(j);
+                        merged_any = true;
+                        break 'search;
+                    }
+                }
+            }
+        }
+        pieces
+            .iter()
+            .filter_map(|piece| ConvexPolygon2::from_points(piece))
+            .collect()
+    }
+
+    /// The outline moved outwards by `distance` (inwards if negative), keeping the
+    /// winding. Corners are mitred, but no further than four times `distance`; an inset
+    /// larger than the polygon's narrowest part turns it inside out.
+    pub fn offset(&self, distance: f32) -> Polygon2D {
+        let sign = self.signed_area().signum();
+        let len = self.points.len();
+        let points = (0..len)
+            .map(|i| {
+                let prev = self.points[(i + len - 1) % len];
+                let point = self.points[i];
+                let next = self.points[(i + 1) % len];
+                let before = outward_normal(prev, point) * sign;
+                let after = outward_normal(point, next) * sign;
+                // The mitre's length grows with 1 / cos(half the turn).
+                let scale = distance / (1.0 + Vec2::dot(&before, &after)).max(0.125);
+                point + (before + after) * scale
+            })
+            .collect();
+        Polygon2D::new(points)
+    }
+}
+
+impl From<Vec<Vec2>> for Polygon2D {
+    fn from(points: Vec<Vec2>) -> Self {
+        Self::new(points)
+    }
+}
+
+fn signed_area(points: &[Vec2]) -> f32 {
+    edges(points).map(|(a, b)| Vec2::cross(&a, &b)).sum::<f32>() * 0.5
+}
+
+fn edges(points: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    (0..points.len()).map(|i| (points[i], points[(i + 1) % points.len()]))
+}
+
+// Outward for counter-clockwise points.
+fn outward_normal(start: Vec2, end: Vec2) -> Vec2 {
+    let edge = end - start;
+    let length = edge.length();
+    if length <= f32::EPSILON {
+        return Vec2::zero();
+    }
+    Vec2::new(edge.y, -edge.x) / length
+}
+
+fn ccw(points: &[Vec2]) -> Vec<Vec2> {
+    let mut points = points.to_vec();
+    points.dedup();
+    while points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    if signed_area(&points) < 0.0 {
+        points.reverse();
+    }
+    points
+}
+
+// Every corner turns the same way as `sign` (or not at all).
+fn is_convex(points: &[Vec2], sign: f32) -> bool {
+    points.len() >= 3
+        && (0..points.len()).all(|i| {
+            let (a, b, c) = (
+                points[i],
+                points[(i + 1) % points.len()],
+                points[(i + 2) % points.len()],
+            );
+            turn(a, b, c) * sign >= -tolerance(a, b, c)
+        })
+}
+
+// Positive when `a`, `b`, `c` turn counter-clockwise.
+fn turn(a: Vec2, b: Vec2, c: Vec2) -> f32 {
+    Vec2::cross(&(b - a), &(c - b))
+}
+
+// Below this a turn counts as a straight line.
+fn tolerance(a: Vec2, b: Vec2, c: Vec2) -> f32 {
+    1e-6 * (b - a).length() * (c - b).length()
+}
+
+fn rightmost(points: &[Vec2]) -> (usize, Vec2) {
+    points
+        .iter()
+        .copied()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.x.total_cmp(&b.x))
+        .unwrap_or((0, Vec2::zero()))
+}
+
+// Inside or on the edge of the counter-clockwise triangle `a`, `b`, `c`.
+fn in_triangle(a: Vec2, b: Vec2, c: Vec2, point: Vec2) -> bool {
+    Vec2::cross(&(b - a), &(point - a)) >= 0.0
+        && Vec2::cross(&(c - b), &(point - b)) >= 0.0
+        && Vec2::cross(&(a - c), &(point - c)) >= 0.0
+}
+
+// Splices a clockwise hole into the counter-clockwise outline: a ray to the right from the
+// hole's rightmost vertex finds an outline vertex it can see, and the two are joined by a
+// bridge walked once in each direction.
+fn bridge_hole(outline: &[Vec2], hole: &[Vec2]) -> Option<Vec<Vec2>> {
+    let (hole_index, start) = rightmost(hole);
+
+    let mut hit: Option<(f32, usize)> = None;
+    for (i, (a, b)) in edges(outline).enumerate() {
+        if (a.y > start.y) == (b.y > start.y) && a.y != start.y && b.y != start.y {
+            continue;
+        }
+        let x = if a.y == b.y {
+            a.x.min(b.x)
+        } else {
+            a.x + (start.y - a.y) * (b.x - a.x) / (b.y - a.y)
+        };
+        if x >= start.x && hit.is_none_or(|(best, _)| x < best) {
+            // The edge's end furthest right is the candidate.
+            let candidate = if a.x > b.x {
+                i
+            } else {
+                (i + 1) % outline.len()
+            };
+            hit = Some((x, candidate));
+        }
+    }
+    let (x, mut target) = hit?;
+
+    // A reflex outline vertex inside the triangle between the ray and the candidate
+    // would block the view; the one closest to the ray's direction can be seen instead.
+    let crossing = Vec2::new(x, start.y);
+    let candidate = outline[target];
+    let (a, b) = if candidate.y < start.y {
+        (candidate, crossing)
+    } else {
+        (crossing, candidate)
+    };
+    let len = outline.len();
+    let mut best_tan = f32::INFINITY;
+    for i in 0..len {
+        let point = outline[i];
+        if i == target || point.x < start.x || !in_triangle(start, a, b, point) {
+            continue;
+        }
+        let reflex = turn(outline[(i + len - 1) % len], point, outline[(i + 1) % len]) < 0.0;
+        let tan = (point.y - start.y).abs() / (point.x - start.x).max(f32::EPSILON);
+        if reflex && tan < best_tan {
+            best_tan = tan;
+            target = i;
+        }
+    }
+
+    let mut merged = Vec::with_capacity(len + hole.len() + 2);
+    merged.extend_from_slice(&outline[..=target]);
+    merged.extend(hole[hole_index..].iter().chain(&hole[..=hole_index]));
+    merged.extend_from_slice(&outline[target..]);
+    Some(merged)
+}
+
+fn ear_clip(points: &[Vec2]) -> Vec<Triangle2D> {
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    let mut triangles = Vec::with_capacity(points.len().saturating_sub(2));
+
+    while remaining.len() > 3 {
+        let len = remaining.len();
+        let corner = |i: usize| {
+            (
+                points[remaining[(i + len - 1) % len]],
+                points[remaining[i]],
+                points[remaining[(i + 1) % len]],
+            )
+        };
+        let is_ear = |i: usize| {
+            let (a, b, c) = corner(i);
+            // Only reflex vertices can poke into a convex corner. Copies of the corner's
+            // own points, left by hole bridges, don't count.
+            remaining.iter().enumerate().all(|(j, &index)| {
+                let point = points[index];
+                let (prev, next) = (
+                    points[remaining[(j + len - 1) % len]],
+                    points[remaining[(j + 1) % len]],
+                );
+                point == a
+                    || point == b
+                    || point == c
+                    || turn(prev, point, next) > 0.0
+                    || !in_triangle(a, b, c, point)
+            })
+        };
+
+        // Straight corners are dropped without a triangle.
+        let straight = (0..len).find(|&i| {
+            let (a, b, c) = corner(i);
+            turn(a, b, c).abs() <= tolerance(a, b, c)
+        });
+        if let Some(i) = straight {
+            remaining.remove(i);
+            continue;
+        }
+        let convex = |i: &usize| {
+            let (a, b, c) = corner(*i);
+            turn(a, b, c) > 0.0
+        };
+        // Without a clean ear (a self-intersecting outline), any convex corner will do.
+        let Some(i) = (0..len)
+            .filter(convex)
+            .find(|&i| is_ear(i))
+            .or_else(|| (0..len).find(convex))
+        else {
+            return triangles;
+        };
+        let (a, b, c) = corner(i);
+        triangles.push(Triangle2D::new(a, b, c));
+        remaining.remove(i);
+    }
+
+    if let [a, b, c] = remaining[..]
+        && turn(points[a], points[b], points[c]) > 0.0
+    {
+        triangles.push(Triangle2D::new(points[a], points[b], points[c]));
+    }
+    triangles
+}
+
+// Joins two counter-clockwise pieces sharing an edge; `None` if they don't share one.
+fn merge_pieces(a: &[Vec2], b: &[Vec2]) -> Option<Vec<Vec2>> {
+    let (len_a, len_b) = (a.len(), b.len());
+    for i in 0..len_a {
+        let (start, end) = (a[i], a[(i + 1) % len_a]);
+        // The same edge runs the other way in `b`.
+        let Some(j) = (0..len_b).find(|&j| b[j] == end && b[(j + 1) % len_b] == start) else {
+            continue;
+        };
+        let mut merged = Vec::with_capacity(len_a + len_b - 2);
+        merged.extend((1..=len_a).map(|k| a[(i + k) % len_a]));
+        merged.extend((2..len_b).map(|k| b[(j + k) % len_b]));
+        return Some(merged);
+    }
+    None
+}
+
+fn encloses(circle: &Circle, point: Vec2) -> bool {
+    Vec2::distance(&circle.center, &point) <= circle.radius * (1.0 + 1e-5) + 1e-6
+}
+
+fn circle_through_two(a: Vec2, b: Vec2) -> Circle {
+    Circle::new((a + b) * 0.5, Vec2::distance(&a, &b) * 0.5)
+}
+
+// The circumcircle, or the circle around the two furthest points if they lie on one line.
+fn circle_through_three(a: Vec2, b: Vec2, c: Vec2) -> Circle {
+    let (ab, ac) = (b - a, c - a);
+    let denominator = 2.0 * Vec2::cross(&ab, &ac);
+    if denominator.abs() <= f32::EPSILON {
+        return [(a, b), (a, c), (b, c)]
+            .into_iter()
+            .map(|(p, q)| circle_through_two(p, q))
+            .max_by(|p, q| p.radius.total_cmp(&q.radius))
+            .unwrap_or(Circle::new(a, 0.0));
+    }
+    let offset = Vec2::new(
+        ac.y * ab.length_squared() - ab.y * ac.length_squared(),
+        ab.x * ac.length_squared() - ac.x * ab.length_squared(),
+    ) / denominator;
+    Circle::new(a + offset, offset.length())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(size: f32) -> Vec<Vec2> {
+        vec![
+            Vec2::new(-size, -size),
+            Vec2::new(size, -size),
+            Vec2::new(size, size),
+            Vec2::new(-size, size),
+        ]
+    }
+
+    fn covered_area(triangles: &[Triangle2D]) -> f32 {
+        triangles
+            .iter()
+            .map(|t| Vec2::cross(&(t.b - t.a), &(t.c - t.a)) * 0.5)
+            .sum()
+    }
+
+    // A star with `tips` points, clockwise.
+    fn star(tips: usize) -> Polygon2D {
+        Polygon2D::new(
+            (0..tips * 2)
+                .map(|i| {
+                    let angle = -(i as f32) * std::f32::consts::PI / tips as f32;
+                    let radius = if i % 2 == 0 { 2.0 } else { 0.8 };
+                    Vec2::new(angle.cos(), angle.sin()) * radius
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn winding_and_area() {
+        let mut polygon = star(5);
+        assert!(!polygon.is_ccw());
+        assert!(polygon.signed_area() < 0.0);
+        assert!(!polygon.is_convex());
+
+        polygon.normalize_winding();
+        assert!(polygon.is_ccw());
+        assert!(Polygon2D::new(square(1.0)).is_convex());
+        assert_eq!(Polygon2D::new(square(1.0)).area(), 4.0);
+        assert!(polygon.contains_point(Vec2::zero()));
+        assert!(polygon.contains_point(Vec2::new(1.9, 0.0)));
+        assert!(!polygon.contains_point(Vec2::new(1.2, 0.4)));
+    }
+
+    #[test]
+    fn triangulation_covers_concave_polygons() {
+        let polygon = star(5);
+        let triangles = polygon.triangulate();
+
+        assert_eq!(triangles.len(), 8);
+        assert!(triangles.iter().all(Triangle2D::is_ccw));
+        assert!((covered_area(&triangles) - polygon.area()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn triangulation_skips_straight_and_repeated_points() {
+        let mut points = square(1.0);
+        points.insert(1, Vec2::new(0.0, -1.0));
+        points.insert(1, Vec2::new(0.0, -1.0));
+        points.push(points[0]);
+        let triangles = Polygon2D::new(points).triangulate();
+
+        assert_eq!(triangles.len(), 2);
+        assert!((covered_area(&triangles) - 4.0).abs() < 1e-5);
+        assert!(
+            Polygon2D::new(vec![Vec2::zero(), Vec2::one()])
+                .triangulate()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn triangulation_leaves_out_holes() {
+        let outline = Polygon2D::new(square(4.0));
+        let holes = [
+            Polygon2D::new(square(1.0)),
+            Polygon2D::new(vec![
+                Vec2::new(2.0, 2.0),
+                Vec2::new(3.0, 2.0),
+                Vec2::new(2.5, 3.0),
+            ]),
+        ];
+        let triangles = outline.triangulate_with_holes(&holes);
+
+        assert!((covered_area(&triangles) - (64.0 - 4.0 - 0.5)).abs() < 1e-3);
+        assert!(triangles.iter().all(Triangle2D::is_ccw));
+        for triangle in &triangles {
+            let center = (triangle.a + triangle.b + triangle.c) / 3.0;
+            assert!(!holes.iter().any(|hole| hole.contains_point(center)));
+        }
+    }
+
+    #[test]
+    fn hull_and_bounds() {
+        let polygon = star(4);
+        let hull = polygon.convex_hull().unwrap();
+        assert_eq!(hull.points().len(), 4);
+
+        let circle = polygon.bounding_circle().unwrap();
+        assert!(Vec2::distance(&circle.center, &Vec2::zero()) < 1e-4);
+        assert!((circle.radius - 2.0).abs() < 1e-4);
+        let triangle = Polygon2D::new(vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(4.0, 0.0),
+            Vec2::new(2.0, 1.0),
+        ]);
+        // Obtuse: the longest side is the diameter.
+        assert_eq!(
+            triangle.bounding_circle(),
+            Some(Circle::new(Vec2::new(2.0, 0.0), 2.0))
+        );
+
+        let aabb = polygon.bounding_box().unwrap();
+        assert!(Vec2::distance(&aabb.max, &Vec2::new(2.0, 2.0)) < 1e-4);
+        assert_eq!(Polygon2D::default().bounding_box(), None);
+    }
+
+    #[test]
+    fn convex_decomposition_merges_triangles() {
+        let polygon = star(5);
+        let pieces = polygon.convex_decomposition();
+
+        // A diagonal can fix at most two of the five reflex corners, so four is the fewest.
+        assert!((4..8).contains(&pieces.len()));
+        let area: f32 = pieces.iter().map(ConvexPolygon2::area).sum();
+        assert!((area - polygon.area()).abs() < 1e-4);
+        assert_eq!(Polygon2D::new(square(1.0)).convex_decomposition().len(), 1);
+    }
+
+    #[test]
+    fn offset_grows_and_shrinks() {
+        let polygon = Polygon2D::new(square(1.0));
+        assert_eq!(polygon.offset(0.5), Polygon2D::new(square(1.5)));
+
+        let mut clockwise = square(1.0);
+        clockwise.reverse();
+        let inset = Polygon2D::new(clockwise).offset(-0.5);
+        assert!(!inset.is_ccw());
+        assert!((inset.area() - 1.0).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::math::Vec3;
+
+/// Half-line from `origin` along `direction`, which has length one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ray3 {
+    pub origin: Vec3,
+    pub direction: Vec3,
+}
+
+impl Ray3 {
+    /// `direction` is normalized and must not be zero.
+    pub fn new(origin: Vec3, direction: Vec3) -> Self {
+        Self {
+            origin,
+            direction: Vec3::normalized(&direction),
+        }
+    }
+
+    /// The point `distance` along the ray.
+    pub fn at(&self, distance: f32) -> Vec3 {
+        self.origin + self.direction * distance
+    }
+
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        self.at(Vec3::dot(&(point - self.origin), &self.direction).max(0.0))
+    }
+}
+
+/// Ray and direction, as returned by `cursor_to_world_ray`.
+impl From<(Vec3, Vec3)> for Ray3 {
+    fn from((origin, direction): (Vec3, Vec3)) -> Self {
+        Self::new(origin, direction)
+    }
+}
+
+/// Where a ray first hits a 3D shape.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit3D {
+    pub distance: f32,
+    pub point: Vec3,
+    /// Surface normal at the hit point, facing the ray.
+    pub normal: Vec3,
+}
+
+impl RayHit3D {
+    pub(crate) fn new(ray: &Ray3, distance: f32, normal: Vec3) -> Self {
+        Self {
+            distance,
+            point: ray.at(distance),
+            normal,
+        }
+    }
+}
+
+// Slab test against the box from `min` to `max`: the distances the ray enters and leaves
+// it, with the axis (0 to 2) and side (-1 or 1) of the entry face. `None` if it misses.
+pub(crate) fn slabs(
+    origin: [f32; 3],
+    direction: [f32; 3],
+    min: [f32; 3],
+    max: [f32; 3],
+) -> Option<(f32, f32, usize, f32)> {
+    let (mut enter, mut exit) = (f32::NEG_INFINITY, f32::INFINITY);
+    let (mut axis, mut side) = (0, -1.0);
+    for i in 0..3 {
+        if direction[i].abs() <= f32::EPSILON {
+            if origin[i] < min[i] || origin[i] > max[i] {
+                return None;
+            }
+            continue;
+        }
+        let (near, far) = (
+            (min[i] - origin[i]) / direction[i],
+            (max[i] - origin[i]) / direction[i],
+        );
+        let (near, far, near_side) = if near <= far {
+            (near, far, -1.0)
+        } else {
+            (far, near, 1.0)
+        };
+        if near > enter {
+            (enter, axis, side) = (near, i, near_side);
+        }
+        exit = exit.min(far);
+        if enter > exit {
+            return None;
+        }
+    }
+    Some((enter, exit, axis, side))
+}
+
+pub(crate) fn components(vector: Vec3) -> [f32; 3] {
+    [vector.x, vector.y, vector.z]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ray_points() {
+        let ray = Ray3::new(Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -2.0));
+
+        assert_eq!(ray.direction, Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(ray.at(3.0), Vec3::new(1.0, 0.0, -3.0));
+        assert_eq!(
+            ray.closest_point(Vec3::new(4.0, 0.0, -2.0)),
+            Vec3::new(1.0, 0.0, -2.0)
+        );
+        // Behind the origin, the origin is closest.
+        assert_eq!(ray.closest_point(Vec3::new(0.0, 0.0, 5.0)), ray.origin);
+    }
+}
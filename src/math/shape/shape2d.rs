@@ -0,0 +1,381 @@
+use crate::math::{
+    Vec2,
+    matrix::Mat3,
+    shape::{
+        aabb::Aabb2,
+        capsule::Capsule2,
+        circle::Circle,
+        convex::{self, Contact2D, Core, RayHit2D},
+        obb::Obb2,
+        polygon::ConvexPolygon2,
+        triangle::Triangle2D,
+    },
+};
+
+/// Any of the 2D shapes, for intersection tests, contacts and casts between them.
+///
+/// ```rust
+/// use chaos_engine::math::{
+///     Vec2,
+///     matrix::Mat3,
+///     shape::{aabb::Aabb2, circle::Circle, shape2d::Shape2D},
+/// };
+///
+/// let wall = Shape2D::from(Aabb2::new(Vec2::new(-1.0, -5.0), Vec2::new(1.0, 5.0)));
+/// let ball = Shape2D::from(Circle::new(Vec2::zero(), 0.5));
+///
+/// let contact = wall.contact_at(&Mat3::identity(), &ball, &Mat3::translation(1.25, 0.0));
+/// assert_eq!(contact.unwrap().normal, Vec2::x_axis());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape2D {
+    Circle(Circle),
+    Aabb(Aabb2),
+    Obb(Obb2),
+    Capsule(Capsule2),
+    Polygon(ConvexPolygon2),
+    Triangle(Triangle2D),
+}
+
+impl Shape2D {
+    // The convex core and radius the algorithms work on.
+    pub(crate) fn core(&self) -> (Vec<Vec2>, f32) {
+        match self {
+            Shape2D::Circle(circle) => (vec![circle.center], circle.radius),
+            Shape2D::Aabb(aabb) => (aabb.corners().to_vec(), 0.0),
+            Shape2D::Obb(obb) => (obb.corners().to_vec(), 0.0),
+            Shape2D::Capsule(capsule) if capsule.start == capsule.end => {
+                (vec![capsule.start], capsule.radius)
+            }
+            Shape2D::Capsule(capsule) => (vec![capsule.start, capsule.end], capsule.radius),
+            Shape2D::Polygon(polygon) => (polygon.points().to_vec(), 0.0),
+            Shape2D::Triangle(triangle) if triangle.is_ccw() => {
+                (vec![triangle.a, triangle.b, triangle.c], 0.0)
+            }
+            Shape2D::Triangle(triangle) => (vec![triangle.a, triangle.c, triangle.b], 0.0),
+        }
+    }
+
+    fn with_core<R>(&self, f: impl FnOnce(&Core) -> R) -> R {
+        let (points, radius) = self.core();
+        f(&Core {
+            points: &points,
+            radius,
+        })
+    }
+
+    /// The shape moved by `transform`. Circles and capsules stay what they are (scaled by the
+    /// transform's largest scale); boxes and triangles become polygons, which keeps them
+    /// exact under rotation and skew.
+    pub fn transformed(&self, transform: &Mat3) -> Shape2D {
+        let scale = {
+            let data = &transform.data;
+            let x = Vec2::new(data[0][0], data[0][1]).length();
+            let y = Vec2::new(data[1][0], data[1][1]).length();
+            x.max(y)
+        };
+        match self {
+            Shape2D::Circle(circle) => Shape2D::Circle(Circle::new(
+                *transform * circle.center,
+                circle.radius * scale,
+            )),
+            Shape2D::Capsule(capsule) => Shape2D::Capsule(Capsule2::new(
+                *transform * capsule.start,
+                *transform * capsule.end,
+                capsule.radius * scale,
+            )),
+            Shape2D::Triangle(triangle) => Shape2D::Triangle(triangle * *transform),
+            _ => {
+                let (points, _) = self.core();
+                let points: Vec<Vec2> =
+                    points.into_iter().map(|point| *transform * point).collect();
+                match ConvexPolygon2::from_points(&points) {
+                    Some(polygon) => Shape2D::Polygon(polygon),
+                    // Scaled down to a line or a point.
+                    None => Shape2D::Capsule(Capsule2::new(points[0], points[2], 0.0)),
+                }
+            }
+        }
+    }
+
+    pub fn aabb(&self) -> Aabb2 {
+        let (points, radius) = self.core();
+        let aabb = Aabb2::from_points(&points).unwrap_or(Aabb2::new(Vec2::zero(), Vec2::zero()));
+        Aabb2::new(
+            aabb.min - Vec2::one() * radius,
+            aabb.max + Vec2::one() * radius,
+        )
+    }
+
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        self.with_core(|core| convex::contains(core, point))
+    }
+
+    /// The point of the shape closest to `point`; `point` itself if it is inside.
+    pub fn closest_point(&self, point: Vec2) -> Vec2 {
+        self.with_core(|core| convex::closest_point(core, point))
+    }
+
+    pub fn intersects(&self, other: &Shape2D) -> bool {
+        self.contact(other).is_some()
+    }
+
+    /// How the shapes touch, `None` if they don't. The normal points from `self` to `other`.
+    pub fn contact(&self, other: &Shape2D) -> Option<Contact2D> {
+        self.with_core(|a| other.with_core(|b| convex::contact(a, b)))
+    }
+
+    /// Gap between the shapes, zero if they touch.
+    pub fn distance(&self, other: &Shape2D) -> f32 {
+        let (a, radius_a) = self.core();
+        let (b, radius_b) = other.core();
+        convex::separated_closest_points(&a, &b).map_or(0.0, |(on_a, on_b)| {
+            (Vec2::distance(&on_a, &on_b) - radius_a - radius_b).max(0.0)
+        })
+    }
+
+    /// [`Shape2D::intersects`] with both shapes placed by a transform, like
+    /// `TransformComponent::as_mat3` in the examples.
+    pub fn intersects_at(&self, transform: &Mat3, other: &Shape2D, other_transform: &Mat3) -> bool {
+        self.contact_at(transform, other, other_transform).is_some()
+    }
+
+    /// [`Shape2D::contact`] with both shapes placed by a transform.
+    pub fn contact_at(
+        &self,
+        transform: &Mat3,
+        other: &Shape2D,
+        other_transform: &Mat3,
+    ) -> Option<Contact2D> {
+        self.transformed(transform)
+            .contact(&other.transformed(other_transform))
+    }
+
+    /// Where the ray from `origin` along `direction` first hits the shape within
+    /// `max_distance`. A ray starting inside hits at distance zero.
+    pub fn raycast(&self, origin: Vec2, direction: Vec2, max_distance: f32) -> Option<RayHit2D> {
+        if direction.length_squared() == 0.0 {
+            return None;
+        }
+        let direction = Vec2::normalized(&direction);
+        self.with_core(|core| convex::raycast(core, origin, direction, max_distance))
+    }
+
+    /// Where the segment from `start` to `end` first hits the shape.
+    pub fn segment_cast(&self, start: Vec2, end: Vec2) -> Option<RayHit2D> {
+        self.raycast(start, end - start, Vec2::distance(&start, &end))
+    }
+}
+
+impl From<Circle> for Shape2D {
+    fn from(circle: Circle) -> Self {
+        Shape2D::Circle(circle)
+    }
+}
+
+impl From<Aabb2> for Shape2D {
+    fn from(aabb: Aabb2) -> Self {
+        Shape2D::Aabb(aabb)
+    }
+}
+
+impl From<Obb2> for Shape2D {
+    fn from(obb: Obb2) -> Self {
+        Shape2D::Obb(obb)
+    }
+}
+
+impl From<Capsule2> for Shape2D {
+    fn from(capsule: Capsule2) -> Self {
+        Shape2D::Capsule(capsule)
+    }
+}
+
+impl From<ConvexPolygon2> for Shape2D {
+    fn from(polygon: ConvexPolygon2) -> Self {
+        Shape2D::Polygon(polygon)
+    }
+}
+
+impl From<Triangle2D> for Shape2D {
+    fn from(triangle: Triangle2D) -> Self {
+        Shape2D::Triangle(triangle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_4;
+
+    fn circle(x: f32, y: f32, radius: f32) -> Shape2D {
+        Circle::new(Vec2::new(x, y), radius).into()
+    }
+
+    fn square(x: f32, y: f32) -> Shape2D {
+        Aabb2::from_center(Vec2::new(x, y), Vec2::one()).into()
+    }
+
+    fn close(a: Vec2, b: Vec2) -> bool {
+        (a - b).length() < 1e-4
+    }
+
+    #[test]
+    fn circles_touch_along_the_line_between_them() {
+        let contact = circle(0.0, 0.0, 1.0)
+            .contact(&circle(1.5, 0.0, 1.0))
+            .unwrap();
+
+        assert!(close(contact.normal, Vec2::x_axis()));
+        assert!((contact.depth - 0.5).abs() < 1e-5);
+        assert_eq!(contact.points.len(), 1);
+        assert!(close(contact.points[0].point, Vec2::new(0.75, 0.0)));
+        assert!(!circle(0.0, 0.0, 1.0).intersects(&circle(2.5, 0.0, 1.0)));
+        assert!((circle(0.0, 0.0, 1.0).distance(&circle(2.5, 0.0, 1.0)) - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn circle_against_box_face_corner_and_inside() {
+        let face = square(0.0, 0.0).contact(&circle(0.0, 1.25, 0.5)).unwrap();
+        assert!(close(face.normal, Vec2::y_axis()));
+        assert!((face.depth - 0.25).abs() < 1e-5);
+
+        let flipped = circle(0.0, 1.25, 0.5).contact(&square(0.0, 0.0)).unwrap();
+        assert!(close(flipped.normal, -Vec2::y_axis()));
+
+        let corner = square(0.0, 0.0).contact(&circle(1.3, 1.3, 0.5)).unwrap();
+        assert!(close(corner.normal, Vec2::normalized(&Vec2::one())));
+        assert!(!square(0.0, 0.0).intersects(&circle(1.4, 1.4, 0.5)));
+
+        let inside = square(0.0, 0.0).contact(&circle(0.0, 0.8, 0.1)).unwrap();
+        assert!(close(inside.normal, Vec2::y_axis()));
+        assert!((inside.depth - 0.3).abs() < 1e-5);
+    }
+
+    #[test]
+    fn stacked_boxes_have_two_contacts() {
+        let contact = square(0.0, 0.0).contact(&square(0.5, 1.9)).unwrap();
+        assert!(close(contact.normal, Vec2::y_axis()));
+        assert_eq!(contact.points.len(), 2);
+        for point in &contact.points {
+            assert!((point.depth - 0.1).abs() < 1e-4);
+        }
+
+        let diamond = Shape2D::from(Obb2::new(Vec2::new(0.0, 2.3), Vec2::one(), FRAC_PI_4));
+        let corner = square(0.0, 0.0).contact(&diamond).unwrap();
+        assert_eq!(corner.points.len(), 1);
+        assert!((corner.depth - (1.0 - (2.3 - 2f32.sqrt()))).abs() < 1e-4);
+        let apart = Obb2::new(Vec2::new(0.0, 2.5), Vec2::one(), FRAC_PI_4);
+        assert!(!square(0.0, 0.0).intersects(&apart.into()));
+    }
+
+    #[test]
+    fn capsules_and_polygons() {
+        let capsule = Shape2D::from(Capsule2::new(
+            Vec2::new(-2.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            0.5,
+        ));
+        let crossing = Shape2D::from(Capsule2::new(
+            Vec2::new(0.0, -2.0),
+            Vec2::new(0.0, 2.0),
+            0.5,
+        ));
+        assert!(capsule.intersects(&crossing));
+
+        let resting = capsule.contact(&circle(1.0, 0.9, 0.5)).unwrap();
+        assert!(close(resting.normal, Vec2::y_axis()));
+        assert!((resting.depth - 0.1).abs() < 1e-5);
+
+        // Sunk into the capsule's core, a flat side gets a point at each end.
+        let triangle = Shape2D::from(Triangle2D::new(
+            Vec2::new(0.0, 3.0),
+            Vec2::new(-1.0, -0.1),
+            Vec2::new(1.0, -0.1),
+        ));
+        let contact = capsule.contact(&triangle).unwrap();
+        assert!(close(contact.normal, Vec2::y_axis()));
+        assert_eq!(contact.points.len(), 2);
+        assert!((contact.depth - 0.6).abs() < 1e-5);
+
+        let hexagon = ConvexPolygon2::from_points(
+            &(0..6)
+                .map(|i| {
+                    let angle = i as f32 * std::f32::consts::TAU / 6.0;
+                    Vec2::new(angle.cos(), angle.sin())
+                })
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
+        assert!((hexagon.area() - 3.0 * 3f32.sqrt() / 2.0).abs() < 1e-4);
+        assert!(close(hexagon.centroid(), Vec2::zero()));
+        assert!(Shape2D::from(hexagon).contains_point(Vec2::new(0.5, 0.5)));
+    }
+
+    #[test]
+    fn closest_points_and_casts() {
+        let capsule = Shape2D::from(Capsule2::new(
+            Vec2::new(-2.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            0.5,
+        ));
+        assert!(close(
+            capsule.closest_point(Vec2::new(1.0, 3.0)),
+            Vec2::new(1.0, 0.5)
+        ));
+        assert!(close(
+            capsule.closest_point(Vec2::new(5.0, 0.0)),
+            Vec2::new(2.5, 0.0)
+        ));
+        assert_eq!(
+            capsule.closest_point(Vec2::new(0.0, 0.2)),
+            Vec2::new(0.0, 0.2)
+        );
+
+        let hit = capsule
+            .raycast(Vec2::new(0.0, 5.0), Vec2::new(0.0, -2.0), 10.0)
+            .unwrap();
+        assert!((hit.distance - 4.5).abs() < 1e-5);
+        assert!(close(hit.normal, Vec2::y_axis()));
+        let end = capsule
+            .raycast(Vec2::new(5.0, 0.0), Vec2::new(-1.0, 0.0), 10.0)
+            .unwrap();
+        assert!((end.distance - 2.5).abs() < 1e-5);
+        assert!(close(end.normal, Vec2::x_axis()));
+
+        let hit = square(0.0, 0.0)
+            .segment_cast(Vec2::new(-3.0, 0.5), Vec2::new(3.0, 0.5))
+            .unwrap();
+        assert!(close(hit.point, Vec2::new(-1.0, 0.5)));
+        assert!(close(hit.normal, -Vec2::x_axis()));
+        assert!(
+            square(0.0, 0.0)
+                .segment_cast(Vec2::new(-3.0, 0.5), Vec2::new(-2.0, 0.5))
+                .is_none()
+        );
+        assert_eq!(
+            square(0.0, 0.0)
+                .raycast(Vec2::zero(), Vec2::x_axis(), 1.0)
+                .unwrap()
+                .distance,
+            0.0
+        );
+    }
+
+    #[test]
+    fn transforms_place_shapes() {
+        let shape = square(0.0, 0.0);
+        let transform = Mat3::rotation(FRAC_PI_4) * Mat3::translation(3.0, 0.0);
+
+        assert!(shape.intersects_at(&transform, &circle(3.0, 1.3, 0.1), &Mat3::identity()));
+        // Turned, the box reaches further along the diagonal's axis than upright.
+        assert!(!shape.intersects(&circle(0.0, 1.3, 0.1)));
+        assert!(!shape.intersects_at(&transform, &circle(3.0, 1.6, 0.1), &Mat3::identity()));
+
+        let scaled = circle(0.0, 0.0, 1.0)
+            .transformed(&(Mat3::scale(2.0, 2.0) * Mat3::translation(1.0, 0.0)));
+        assert_eq!(scaled, circle(1.0, 0.0, 2.0));
+        let aabb = Shape2D::from(Obb2::new(Vec2::zero(), Vec2::one(), FRAC_PI_4)).aabb();
+        assert!(close(aabb.max, Vec2::one() * 2f32.sqrt()));
+    }
+}
@@ -0,0 +1,94 @@
+use crate::math::{
+    Vec3,
+    shape::{
+        aabb3::Aabb3,
+        ray::{Ray3, RayHit3D},
+    },
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sphere {
+    pub center: Vec3,
+    pub radius: f32,
+}
+
+impl Sphere {
+    pub fn new(center: Vec3, radius: f32) -> Self {
+        Self {
+            center,
+            radius: radius.abs(),
+        }
+    }
+
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        Vec3::distance_squared(&self.center, &point) <= self.radius * self.radius
+    }
+
+    /// The point of the sphere closest to `point`; `point` itself if it is inside.
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        if self.contains_point(point) {
+            return point;
+        }
+        self.center + Vec3::normalized(&(point - self.center)) * self.radius
+    }
+
+    pub fn intersects(&self, other: &Sphere) -> bool {
+        let radii = self.radius + other.radius;
+        Vec3::distance_squared(&self.center, &other.center) <= radii * radii
+    }
+
+    pub fn intersects_aabb(&self, aabb: &Aabb3) -> bool {
+        self.contains_point(aabb.closest_point(self.center))
+    }
+
+    /// A ray starting inside hits at distance zero.
+    pub fn raycast(&self, ray: &Ray3, max_distance: f32) -> Option<RayHit3D> {
+        let offset = ray.origin - self.center;
+        let c = offset.length_squared() - self.radius * self.radius;
+        if c <= 0.0 {
+            return Some(RayHit3D::new(ray, 0.0, -ray.direction));
+        }
+        let b = Vec3::dot(&offset, &ray.direction);
+        let discriminant = b * b - c;
+        if b > 0.0 || discriminant < 0.0 {
+            return None;
+        }
+        let distance = -b - discriminant.sqrt();
+        (distance <= max_distance).then(|| {
+            let normal = (offset + ray.direction * distance) / self.radius;
+            RayHit3D::new(ray, distance, normal)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlaps_and_casts() {
+        let sphere = Sphere::new(Vec3::new(0.0, 0.0, -5.0), 1.0);
+
+        assert!(sphere.intersects(&Sphere::new(Vec3::new(0.0, 1.5, -5.0), 0.5)));
+        assert!(!sphere.intersects(&Sphere::new(Vec3::new(0.0, 1.6, -5.0), 0.5)));
+        assert!(sphere.intersects_aabb(&Aabb3::new(Vec3::new(0.5, 0.5, -6.0), Vec3::one())));
+        assert!(!sphere.intersects_aabb(&Aabb3::new(Vec3::new(0.8, 0.8, -6.0), Vec3::one())));
+        assert_eq!(
+            sphere.closest_point(Vec3::new(0.0, 3.0, -5.0)),
+            Vec3::new(0.0, 1.0, -5.0)
+        );
+
+        let ray = Ray3::new(Vec3::zero(), Vec3::new(0.0, 0.0, -1.0));
+        let hit = sphere.raycast(&ray, 10.0).unwrap();
+        assert_eq!(hit.distance, 4.0);
+        assert_eq!(hit.normal, Vec3::new(0.0, 0.0, 1.0));
+        assert!(sphere.raycast(&ray, 3.0).is_none());
+        assert!(
+            sphere
+                .raycast(&Ray3::new(Vec3::zero(), Vec3::z_axis()), 10.0)
+                .is_none()
+        );
+        let inside = Ray3::new(sphere.center, Vec3::x_axis());
+        assert_eq!(sphere.raycast(&inside, 10.0).unwrap().distance, 0.0);
+    }
+}
@@ -2,6 +2,7 @@ use crate::math::Vec2;
 use crate::math::matrix::Mat3;
 use std::ops::Mul;
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Triangle2D {
     pub a: Vec2,
     pub b: Vec2,
@@ -0,0 +1,176 @@
+use crate::math::{
+    Vec3,
+    shape::{
+        plane::Plane,
+        ray::{Ray3, RayHit3D},
+    },
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Triangle3D {
+    pub a: Vec3,
+    pub b: Vec3,
+    pub c: Vec3,
+}
+
+impl Triangle3D {
+    pub fn new(a: Vec3, b: Vec3, c: Vec3) -> Self {
+        Self { a, b, c }
+    }
+
+    /// Unit normal of the side the points run counter-clockwise on; zero if the triangle
+    /// is degenerate.
+    pub fn normal(&self) -> Vec3 {
+        let normal = Vec3::cross(&(self.b - self.a), &(self.c - self.a));
+        if normal.length_squared() <= f32::EPSILON * f32::EPSILON {
+            return Vec3::zero();
+        }
+        Vec3::normalized(&normal)
+    }
+
+    pub fn area(&self) -> f32 {
+        Vec3::cross(&(self.b - self.a), &(self.c - self.a)).length() * 0.5
+    }
+
+    pub fn plane(&self) -> Option<Plane> {
+        Plane::from_points(self.a, self.b, self.c)
+    }
+
+    /// Finds the region (corner, edge or face) `point` projects into.
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        let (a, b, c) = (self.a, self.b, self.c);
+        let (ab, ac, ap) = (b - a, c - a, point - a);
+        let (d1, d2) = (Vec3::dot(&ab, &ap), Vec3::dot(&ac, &ap));
+        if d1 <= 0.0 && d2 <= 0.0 {
+            return a;
+        }
+
+        let bp = point - b;
+        let (d3, d4) = (Vec3::dot(&ab, &bp), Vec3::dot(&ac, &bp));
+        if d3 >= 0.0 && d4 <= d3 {
+            return b;
+        }
+
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+            return a + ab * (d1 / (d1 - d3));
+        }
+
+        let cp = point - c;
+        let (d5, d6) = (Vec3::dot(&ab, &cp), Vec3::dot(&ac, &cp));
+        if d6 >= 0.0 && d5 <= d6 {
+            return c;
+        }
+
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+            return a + ac * (d2 / (d2 - d6));
+        }
+
+        let va = d3 * d6 - d5 * d4;
+        if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+            return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+        }
+
+        let denominator = 1.0 / (va + vb + vc);
+        a + ab * (vb * denominator) + ac * (vc * denominator)
+    }
+
+    /// Hits either side (Möller-Trumbore), e.g. for picking meshes.
+    pub fn raycast(&self, ray: &Ray3, max_distance: f32) -> Option<RayHit3D> {
+        let (ab, ac) = (self.b - self.a, self.c - self.a);
+        let p = Vec3::cross(&ray.direction, &ac);
+        let determinant = Vec3::dot(&ab, &p);
+        if determinant.abs() <= f32::EPSILON {
+            return None;
+        }
+        let inverse = 1.0 / determinant;
+        let offset = ray.origin - self.a;
+        let u = Vec3::dot(&offset, &p) * inverse;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = Vec3::cross(&offset, &ab);
+        let v = Vec3::dot(&ray.direction, &q) * inverse;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let distance = Vec3::dot(&ac, &q) * inverse;
+        if !(0.0..=max_distance).contains(&distance) {
+            return None;
+        }
+        let normal = self.normal();
+        let normal = if Vec3::dot(&normal, &ray.direction) > 0.0 {
+            -normal
+        } else {
+            normal
+        };
+        Some(RayHit3D::new(ray, distance, normal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle() -> Triangle3D {
+        Triangle3D::new(
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(2.0, 0.0, 0.0),
+            Vec3::new(0.0, 2.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn closest_point_regions() {
+        let triangle = triangle();
+
+        assert_eq!(triangle.normal(), Vec3::z_axis());
+        assert_eq!(triangle.area(), 2.0);
+        // Face, corners and edges.
+        assert_eq!(
+            triangle.closest_point(Vec3::new(0.5, 0.5, 3.0)),
+            Vec3::new(0.5, 0.5, 0.0)
+        );
+        assert_eq!(
+            triangle.closest_point(Vec3::new(-1.0, -1.0, 0.0)),
+            triangle.a
+        );
+        assert_eq!(
+            triangle.closest_point(Vec3::new(3.0, -1.0, 1.0)),
+            triangle.b
+        );
+        assert_eq!(
+            triangle.closest_point(Vec3::new(-0.5, 3.0, 0.0)),
+            triangle.c
+        );
+        assert_eq!(
+            triangle.closest_point(Vec3::new(1.0, -2.0, 0.0)),
+            Vec3::new(1.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            triangle.closest_point(Vec3::new(2.0, 2.0, -1.0)),
+            Vec3::new(1.0, 1.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn raycast_hits_both_sides() {
+        let triangle = triangle();
+
+        let from_above = Ray3::new(Vec3::new(0.5, 0.5, 4.0), -Vec3::z_axis());
+        let hit = triangle.raycast(&from_above, 10.0).unwrap();
+        assert_eq!(hit.distance, 4.0);
+        assert_eq!(hit.point, Vec3::new(0.5, 0.5, 0.0));
+        assert_eq!(hit.normal, Vec3::z_axis());
+
+        let from_below = Ray3::new(Vec3::new(0.5, 0.5, -1.0), Vec3::z_axis());
+        assert_eq!(
+            triangle.raycast(&from_below, 10.0).unwrap().normal,
+            -Vec3::z_axis()
+        );
+        let beside = Ray3::new(Vec3::new(1.5, 1.5, 4.0), -Vec3::z_axis());
+        assert!(triangle.raycast(&beside, 10.0).is_none());
+        assert!(triangle.raycast(&from_above, 3.0).is_none());
+    }
+}
@@ -0,0 +1,391 @@
+//! Kernels behind the math types' hot operations: 4-wide vector arithmetic, dot and cross
+//! products, matrix products, the `Mat4` inverse and the quaternion product. With the
+//! `simd` feature on x86_64 they use SSE2, which every x86_64 CPU has; everywhere else
+//! the scalar versions. The types keep their `#[repr(C)]` layouts either way, the kernels
+//! only load from and store to them.
+//!
+//! `Vec2` and `Vec3` element-wise arithmetic stays scalar: with two or three lanes the
+//! loads cost about as much as they save.
+
+#[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+pub(crate) use scalar::*;
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+pub(crate) use sse::*;
+
+pub(crate) type Mat3Data = [[f32; 3]; 3];
+pub(crate) type Mat4Data = [[f32; 4]; 4];
+
+// Gauss-Jordan elimination with partial pivoting, in f64 to keep projection matrices
+// (which mix very large and very small entries) accurate. Also inverts `DMat4`.
+pub(crate) fn mat4_inverse_f64(mut m: [[f64; 4]; 4]) -> Option<[[f64; 4]; 4]> {
+    let mut inv = [[0.0f64; 4]; 4];
+    for (i, row) in inv.iter_mut().enumerate() {
+        row[i] = 1.0;
+    }
+
+    for col in 0..4 {
+        let pivot = (col..4)
+            .max_by(|&a, &b| m[a][col].abs().total_cmp(&m[b][col].abs()))
+            .unwrap();
+        if m[pivot][col].abs() < f64::EPSILON {
+            return None;
+        }
+        m.swap(col, pivot);
+        inv.swap(col, pivot);
+
+        let scale = m[col][col];
+        for j in 0..4 {
+            m[col][j] /= scale;
+            inv[col][j] /= scale;
+        }
+
+        for row in 0..4 {
+            if row == col {
+                continue;
+            }
+            let factor = m[row][col];
+            for j in 0..4 {
+                m[row][j] -= factor * m[col][j];
+                inv[row][j] -= factor * inv[col][j];
+            }
+        }
+    }
+
+    Some(inv)
+}
+
+#[cfg(any(test, not(all(feature = "simd", target_arch = "x86_64"))))]
+mod scalar {
+    use super::{Mat3Data, Mat4Data};
+
+    pub(crate) fn add4(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+        [0, 1, 2, 3].map(|i| a[i] + b[i])
+    }
+
+    pub(crate) fn sub4(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+        [0, 1, 2, 3].map(|i| a[i] - b[i])
+    }
+
+    pub(crate) fn mul4(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+        [0, 1, 2, 3].map(|i| a[i] * b[i])
+    }
+
+    pub(crate) fn div4(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+        [0, 1, 2, 3].map(|i| a[i] / b[i])
+    }
+
+    pub(crate) fn scale4(a: [f32; 4], scalar: f32) -> [f32; 4] {
+        a.map(|value| value * scalar)
+    }
+
+    pub(crate) fn dot4(a: [f32; 4], b: [f32; 4]) -> f32 {
+        a[0] * b[0] + a[1] * b[1] + a[2] * b[2] + a[3] * b[3]
+    }
+
+    pub(crate) fn dot3(a: [f32; 3], b: [f32; 3]) -> f32 {
+        a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+    }
+
+    pub(crate) fn cross3(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+        [
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        ]
+    }
+
+    pub(crate) fn mat3_mul(a: &Mat3Data, b: &Mat3Data) -> Mat3Data {
+        let mut result = [[0.0; 3]; 3];
+        for i in 0..3 {
+            for j in 0..3 {
+                for k in 0..3 {
+                    result[i][j] += a[i][k] * b[k][j];
+                }
+            }
+        }
+        result
+    }
+
+    pub(crate) fn mat4_mul(a: &Mat4Data, b: &Mat4Data) -> Mat4Data {
+        let mut result = [[0.0; 4]; 4];
+        for i in 0..4 {
+            for j in 0..4 {
+                for k in 0..4 {
+                    result[i][j] += a[i][k] * b[k][j];
+                }
+            }
+        }
+        result
+    }
+
+    pub(crate) fn mat4_inverse(matrix: &Mat4Data) -> Option<Mat4Data> {
+        super::mat4_inverse_f64(matrix.map(|row| row.map(|value| value as f64)))
+            .map(|inverse| inverse.map(|row| row.map(|value| value as f32)))
+    }
+
+    // Quaternions as `[a, b, c, d]`, with `d` the real part.
+    pub(crate) fn quat_mul(q: [f32; 4], r: [f32; 4]) -> [f32; 4] {
+        [
+            q[3] * r[0] + q[0] * r[3] + q[1] * r[2] - q[2] * r[1],
+            q[3] * r[1] - q[0] * r[2] + q[1] * r[3] + q[2] * r[0],
+            q[3] * r[2] + q[0] * r[1] - q[1] * r[0] + q[2] * r[3],
+            q[3] * r[3] - q[0] * r[0] - q[1] * r[1] - q[2] * r[2],
+        ]
+    }
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod sse {
+    use std::arch::x86_64::*;
+
+    use super::{Mat3Data, Mat4Data};
+
+    // SSE2 is part of x86_64, so the intrinsics are always available; they are only
+    // `unsafe` because the compiler can't see that, and for the unaligned loads and
+    // stores through pointers to local arrays.
+
+    // `_mm_shuffle_ps` lane orders, two bits per lane with the first lane lowest.
+    const YYYY: i32 = 0b01_01_01_01;
+    const YZXW: i32 = 0b11_00_10_01;
+    const ZXYW: i32 = 0b11_01_00_10;
+    const WZYX: i32 = 0b00_01_10_11;
+    const ZWXY: i32 = 0b01_00_11_10;
+    const YXWZ: i32 = 0b10_11_00_01;
+
+    #[inline]
+    fn load(values: [f32; 4]) -> __m128 {
+        unsafe { _mm_loadu_ps(values.as_ptr()) }
+    }
+
+    #[inline]
+    fn load3(values: [f32; 3]) -> __m128 {
+        load([values[0], values[1], values[2], 0.0])
+    }
+
+    #[inline]
+    fn store(vector: __m128) -> [f32; 4] {
+        let mut values = [0.0; 4];
+        unsafe { _mm_storeu_ps(values.as_mut_ptr(), vector) };
+        values
+    }
+
+    #[inline]
+    fn store3(vector: __m128) -> [f32; 3] {
+        let [x, y, z, _] = store(vector);
+        [x, y, z]
+    }
+
+    #[inline]
+    fn horizontal_sum(vector: __m128) -> f32 {
+        unsafe {
+            let pairs = _mm_add_ps(vector, _mm_movehl_ps(vector, vector));
+            let second = _mm_shuffle_ps::<YYYY>(pairs, pairs);
+            _mm_cvtss_f32(_mm_add_ss(pairs, second))
+        }
+    }
+
+    pub(crate) fn add4(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+        store(unsafe { _mm_add_ps(load(a), load(b)) })
+    }
+
+    pub(crate) fn sub4(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+        store(unsafe { _mm_sub_ps(load(a), load(b)) })
+    }
+
+    pub(crate) fn mul4(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+        store(unsafe { _mm_mul_ps(load(a), load(b)) })
+    }
+
+    pub(crate) fn div4(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+        store(unsafe { _mm_div_ps(load(a), load(b)) })
+    }
+
+    pub(crate) fn scale4(a: [f32; 4], scalar: f32) -> [f32; 4] {
+        store(unsafe { _mm_mul_ps(load(a), _mm_set1_ps(scalar)) })
+    }
+
+    pub(crate) fn dot4(a: [f32; 4], b: [f32; 4]) -> f32 {
+        horizontal_sum(unsafe { _mm_mul_ps(load(a), load(b)) })
+    }
+
+    pub(crate) fn dot3(a: [f32; 3], b: [f32; 3]) -> f32 {
+        horizontal_sum(unsafe { _mm_mul_ps(load3(a), load3(b)) })
+    }
+
+    // a.yzx * b.zxy - a.zxy * b.yzx
+    pub(crate) fn cross3(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+        let (a, b) = (load3(a), load3(b));
+        store3(unsafe {
+            _mm_sub_ps(
+                _mm_mul_ps(_mm_shuffle_ps::<YZXW>(a, a), _mm_shuffle_ps::<ZXYW>(b, b)),
+                _mm_mul_ps(_mm_shuffle_ps::<ZXYW>(a, a), _mm_shuffle_ps::<YZXW>(b, b)),
+            )
+        })
+    }
+
+    // Each result row is the rows of `b` weighted by the entries of the row of `a`.
+    pub(crate) fn mat3_mul(a: &Mat3Data, b: &Mat3Data) -> Mat3Data {
+        let rows = b.map(load3);
+        a.map(|row| unsafe {
+            let mut result = _mm_mul_ps(_mm_set1_ps(row[0]), rows[0]);
+            result = _mm_add_ps(result, _mm_mul_ps(_mm_set1_ps(row[1]), rows[1]));
+            result = _mm_add_ps(result, _mm_mul_ps(_mm_set1_ps(row[2]), rows[2]));
+            store3(result)
+        })
+    }
+
+    pub(crate) fn mat4_mul(a: &Mat4Data, b: &Mat4Data) -> Mat4Data {
+        let rows = b.map(load);
+        a.map(|row| unsafe {
+            let mut result = _mm_mul_ps(_mm_set1_ps(row[0]), rows[0]);
+            for k in 1..4 {
+                result = _mm_add_ps(result, _mm_mul_ps(_mm_set1_ps(row[k]), rows[k]));
+            }
+            store(result)
+        })
+    }
+
+    // The elimination of `mat4_inverse_f64` (in f64, for projection matrices), with each
+    // augmented row `[m | inverse]` as four pairs of f64 lanes.
+    pub(crate) fn mat4_inverse(matrix: &Mat4Data) -> Option<Mat4Data> {
+        let mut rows = [[0.0f64; 8]; 4];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for j in 0..4 {
+                row[j] = matrix[i][j] as f64;
+            }
+            row[4 + i] = 1.0;
+        }
+
+        let load_row = |row: &[f64; 8]| -> [__m128d; 4] {
+            [0, 2, 4, 6].map(|i| unsafe { _mm_loadu_pd(row[i..].as_ptr()) })
+        };
+        let store_row = |row: &mut [f64; 8], lanes: [__m128d; 4]| {
+            for (pair, lane) in row.chunks_exact_mut(2).zip(lanes) {
+                unsafe { _mm_storeu_pd(pair.as_mut_ptr(), lane) };
+            }
+        };
+
+        for col in 0..4 {
+            let pivot = (col..4)
+                .max_by(|&a, &b| rows[a][col].abs().total_cmp(&rows[b][col].abs()))
+                .unwrap();
+            if rows[pivot][col].abs() < f64::EPSILON {
+                return None;
+            }
+            rows.swap(col, pivot);
+
+            let pivot_row = unsafe {
+                let scale = _mm_set1_pd(rows[col][col]);
+                load_row(&rows[col]).map(|lane| _mm_div_pd(lane, scale))
+            };
+            store_row(&mut rows[col], pivot_row);
+
+            for (index, row) in rows.iter_mut().enumerate() {
+                if index == col {
+                    continue;
+                }
+                let mut lanes = load_row(row);
+                unsafe {
+                    let factor = _mm_set1_pd(row[col]);
+                    for (lane, pivot_lane) in lanes.iter_mut().zip(pivot_row) {
+                        *lane = _mm_sub_pd(*lane, _mm_mul_pd(factor, pivot_lane));
+                    }
+                }
+                store_row(row, lanes);
+            }
+        }
+
+        Some(rows.map(|row| [row[4], row[5], row[6], row[7]].map(|value| value as f32)))
+    }
+
+    // Quaternions as `[a, b, c, d]`, with `d` the real part: the product is `r` scaled by
+    // each component of `q`, shuffled and signed.
+    pub(crate) fn quat_mul(q: [f32; 4], r: [f32; 4]) -> [f32; 4] {
+        let r = load(r);
+        store(unsafe {
+            let term = |component: f32, shuffled: __m128, signs: [f32; 4]| {
+                _mm_mul_ps(_mm_mul_ps(_mm_set1_ps(component), shuffled), load(signs))
+            };
+            let by_d = _mm_mul_ps(_mm_set1_ps(q[3]), r);
+            let by_a = term(q[0], _mm_shuffle_ps::<WZYX>(r, r), [1.0, -1.0, 1.0, -1.0]);
+            let by_b = term(q[1], _mm_shuffle_ps::<ZWXY>(r, r), [1.0, 1.0, -1.0, -1.0]);
+            let by_c = term(q[2], _mm_shuffle_ps::<YXWZ>(r, r), [-1.0, 1.0, 1.0, -1.0]);
+            _mm_add_ps(_mm_add_ps(by_d, by_a), _mm_add_ps(by_b, by_c))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: Mat4Data = [
+        [2.0, 0.5, -1.0, 0.0],
+        [0.0, 3.0, 0.25, 1.0],
+        [1.0, -2.0, 4.0, 0.5],
+        [0.0, 1.0, 0.0, 1.0],
+    ];
+
+    fn close(a: &[f32], b: &[f32]) -> bool {
+        a.iter()
+            .zip(b)
+            .all(|(a, b)| (a - b).abs() <= 1e-5 * a.abs().max(1.0))
+    }
+
+    #[test]
+    fn kernels_match_known_results() {
+        assert_eq!(add4([1.0, 2.0, 3.0, 4.0], [4.0, 3.0, 2.0, 1.0]), [5.0; 4]);
+        assert_eq!(
+            div4([1.0, 2.0, 3.0, 4.0], [2.0; 4]),
+            scale4([1.0, 2.0, 3.0, 4.0], 0.5)
+        );
+        assert_eq!(dot4([1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]), 70.0);
+        assert_eq!(dot3([1.0, 2.0, 3.0], [4.0, 5.0, 6.0]), 32.0);
+        assert_eq!(cross3([1.0, 0.0, 0.0], [0.0, 1.0, 0.0]), [0.0, 0.0, 1.0]);
+
+        let identity = [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        assert_eq!(mat4_mul(&A, &identity), A);
+        let product = mat4_mul(&A, &mat4_inverse(&A).unwrap());
+        assert!(close(product.as_flattened(), identity.as_flattened()));
+        assert_eq!(mat4_inverse(&[[1.0; 4]; 4]), None);
+
+        // i * j = k
+        assert_eq!(
+            quat_mul([1.0, 0.0, 0.0, 0.0], [0.0, 1.0, 0.0, 0.0]),
+            [0.0, 0.0, 1.0, 0.0]
+        );
+    }
+
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    #[test]
+    fn sse_matches_scalar() {
+        let (a, b) = ([1.5, -2.0, 0.25, 8.0], [-3.0, 0.5, 4.0, 2.0]);
+        assert_eq!(sse::add4(a, b), scalar::add4(a, b));
+        assert_eq!(sse::sub4(a, b), scalar::sub4(a, b));
+        assert_eq!(sse::mul4(a, b), scalar::mul4(a, b));
+        assert_eq!(sse::div4(a, b), scalar::div4(a, b));
+        assert_eq!(sse::scale4(a, 3.0), scalar::scale4(a, 3.0));
+        assert!(close(&[sse::dot4(a, b)], &[scalar::dot4(a, b)]));
+        let (a3, b3) = ([a[0], a[1], a[2]], [b[0], b[1], b[2]]);
+        assert!(close(&[sse::dot3(a3, b3)], &[scalar::dot3(a3, b3)]));
+        assert_eq!(sse::cross3(a3, b3), scalar::cross3(a3, b3));
+        assert_eq!(sse::quat_mul(a, b), scalar::quat_mul(a, b));
+
+        let b4 = mat4_inverse(&A).unwrap();
+        assert!(close(
+            sse::mat4_mul(&A, &b4).as_flattened(),
+            scalar::mat4_mul(&A, &b4).as_flattened()
+        ));
+        assert_eq!(sse::mat4_inverse(&A), scalar::mat4_inverse(&A));
+        let m3 = [[1.0, 2.0, 0.5], [-1.0, 0.0, 3.0], [2.0, 2.0, 1.0]];
+        assert!(close(
+            sse::mat3_mul(&m3, &m3).as_flattened(),
+            scalar::mat3_mul(&m3, &m3).as_flattened()
+        ));
+    }
+}
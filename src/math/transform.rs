@@ -0,0 +1,374 @@
+use std::ops::{Mul, MulAssign};
+
+use crate::math::{
+    Vec2, Vec3,
+    matrix::{Mat3, Mat4},
+    quaternion::Quaternion,
+    shape::{aabb3::Aabb3, shape2d::Shape2D, sphere::Sphere},
+};
+
+/// Scale, then rotation, then translation in 2D: the same as
+/// `Mat3::scale * Mat3::rotation * Mat3::translation`, so positive rotations turn clockwise.
+///
+/// `a * b` applies `a` first, like the matrices, so a child's world transform is
+/// `child * parent`.
+///
+/// ```rust
+/// use chaos_engine::math::{Vec2, transform::Transform2D};
+///
+/// let parent = Transform2D::from_translation(Vec2::new(10.0, 0.0)).with_scale(Vec2::splat(2.0));
+/// let child = Transform2D::from_translation(Vec2::new(1.0, 0.0));
+/// let world = child * parent;
+/// assert_eq!(world.transform_point(Vec2::zero()), Vec2::new(12.0, 0.0));
+/// assert_eq!(world.inverse().unwrap().transform_point(Vec2::new(12.0, 0.0)), Vec2::zero());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform2D {
+    pub translation: Vec2,
+    pub rotation: f32,
+    pub scale: Vec2,
+}
+
+impl Transform2D {
+    pub fn new(translation: Vec2, rotation: f32, scale: Vec2) -> Self {
+        Self {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(Vec2::zero(), 0.0, Vec2::one())
+    }
+
+    pub fn from_translation(translation: Vec2) -> Self {
+        Self::identity().with_translation(translation)
+    }
+
+    pub fn from_rotation(rotation: f32) -> Self {
+        Self::identity().with_rotation(rotation)
+    }
+
+    pub fn from_scale(scale: Vec2) -> Self {
+        Self::identity().with_scale(scale)
+    }
+
+    pub fn with_translation(mut self, translation: Vec2) -> Self {
+        self.translation = translation;
+        self
+    }
+
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: Vec2) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn as_mat3(&self) -> Mat3 {
+        Mat3::scale(self.scale.x, self.scale.y)
+            * Mat3::rotation(self.rotation)
+            * Mat3::translation(self.translation.x, self.translation.y)
+    }
+
+    /// The transform in the z = 0 plane, e.g. as a model matrix.
+    pub fn as_mat4(&self) -> Mat4 {
+        Mat4::scale(self.scale.x, self.scale.y, 1.0)
+            * Mat4::rotation_z(self.rotation)
+            * Mat4::translation(self.translation.x, self.translation.y, 0.0)
+    }
+
+    pub fn transform_point(&self, point: Vec2) -> Vec2 {
+        self.transform_direction(point) + self.translation
+    }
+
+    /// Scales and turns `direction` without moving it, e.g. for velocities.
+    pub fn transform_direction(&self, direction: Vec2) -> Vec2 {
+        let scaled = direction * self.scale;
+        let (sin, cos) = self.rotation.sin_cos();
+        Vec2::new(
+            cos * scaled.x + sin * scaled.y,
+            cos * scaled.y - sin * scaled.x,
+        )
+    }
+
+    pub fn transform_shape(&self, shape: &Shape2D) -> Shape2D {
+        shape.transformed(&self.as_mat3())
+    }
+
+    /// `None` when a scale is zero. Like composing, exact for uniform scales; with
+    /// non-uniform scales and a rotation the result only approximates the inverse.
+    pub fn inverse(&self) -> Option<Self> {
+        if self.scale.x == 0.0 || self.scale.y == 0.0 {
+            return None;
+        }
+        let inverse = Self::new(Vec2::zero(), -self.rotation, Vec2::one() / self.scale);
+        Some(inverse.with_translation(inverse.transform_direction(-self.translation)))
+    }
+}
+
+impl Default for Transform2D {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// Exact when `other` has a uniform scale; otherwise the combined scale ignores the
+/// rotation between the two.
+impl Mul<Transform2D> for Transform2D {
+    type Output = Self;
+
+    fn mul(self, other: Transform2D) -> Self::Output {
+        Self {
+            translation: other.transform_point(self.translation),
+            rotation: self.rotation + other.rotation,
+            scale: self.scale * other.scale,
+        }
+    }
+}
+
+impl MulAssign<Transform2D> for Transform2D {
+    fn mul_assign(&mut self, other: Transform2D) {
+        *self = *self * other;
+    }
+}
+
+impl From<Transform2D> for Mat3 {
+    fn from(transform: Transform2D) -> Self {
+        transform.as_mat3()
+    }
+}
+
+/// Scale, then rotation, then translation in 3D, the order `Mat4::transform` uses. The
+/// rotation turns the way `Quaternion::rotate` does.
+///
+/// `a * b` applies `a` first, like the matrices.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform3D {
+    pub translation: Vec3,
+    pub rotation: Quaternion,
+    pub scale: Vec3,
+}
+
+impl Transform3D {
+    pub fn new(translation: Vec3, rotation: Quaternion, scale: Vec3) -> Self {
+        Self {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(Vec3::zero(), Quaternion::identity(), Vec3::one())
+    }
+
+    pub fn from_translation(translation: Vec3) -> Self {
+        Self::identity().with_translation(translation)
+    }
+
+    pub fn from_rotation(rotation: Quaternion) -> Self {
+        Self::identity().with_rotation(rotation)
+    }
+
+    pub fn from_scale(scale: Vec3) -> Self {
+        Self::identity().with_scale(scale)
+    }
+
+    pub fn with_translation(mut self, translation: Vec3) -> Self {
+        self.translation = translation;
+        self
+    }
+
+    pub fn with_rotation(mut self, rotation: Quaternion) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: Vec3) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn as_mat4(&self) -> Mat4 {
+        Mat4::transform(&self.translation, &self.rotation, &self.scale)
+    }
+
+    pub fn transform_point(&self, point: Vec3) -> Vec3 {
+        self.transform_direction(point) + self.translation
+    }
+
+    /// Scales and turns `direction` without moving it.
+    pub fn transform_direction(&self, direction: Vec3) -> Vec3 {
+        self.rotation.rotate(&(direction * self.scale))
+    }
+
+    /// The box around `aabb` after the transform.
+    pub fn transform_aabb(&self, aabb: &Aabb3) -> Aabb3 {
+        aabb.transformed(&self.as_mat4())
+    }
+
+    /// Grows the radius by the largest scale, so the sphere still covers the shape it
+    /// bounds.
+    pub fn transform_sphere(&self, sphere: &Sphere) -> Sphere {
+        let scale = self
+            .scale
+            .x
+            .abs()
+            .max(self.scale.y.abs())
+            .max(self.scale.z.abs());
+        Sphere::new(self.transform_point(sphere.center), sphere.radius * scale)
+    }
+
+    /// `None` when a scale is zero. Exact for uniform scales, like composing.
+    pub fn inverse(&self) -> Option<Self> {
+        if self.scale.x == 0.0 || self.scale.y == 0.0 || self.scale.z == 0.0 {
+            return None;
+        }
+        let rotation = Quaternion::normalized(self.rotation).conjugate();
+        let scale = Vec3::one() / self.scale;
+        Some(Self::new(
+            rotation.rotate(&-self.translation) * scale,
+            rotation,
+            scale,
+        ))
+    }
+}
+
+impl Default for Transform3D {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// Exact when `other` has a uniform scale.
+impl Mul<Transform3D> for Transform3D {
+    type Output = Self;
+
+    fn mul(self, other: Transform3D) -> Self::Output {
+        Self {
+            translation: other.transform_point(self.translation),
+            rotation: other.rotation * self.rotation,
+            scale: self.scale * other.scale,
+        }
+    }
+}
+
+impl MulAssign<Transform3D> for Transform3D {
+    fn mul_assign(&mut self, other: Transform3D) {
+        *self = *self * other;
+    }
+}
+
+impl From<Transform3D> for Mat4 {
+    fn from(transform: Transform3D) -> Self {
+        transform.as_mat4()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn close2(a: Vec2, b: Vec2) -> bool {
+        Vec2::distance(&a, &b) < 1e-5
+    }
+
+    fn close3(a: Vec3, b: Vec3) -> bool {
+        Vec3::distance(&a, &b) < 1e-5
+    }
+
+    #[test]
+    fn transform_2d_matches_its_matrix() {
+        let transform = Transform2D::new(Vec2::new(3.0, -1.0), 0.7, Vec2::new(2.0, 0.5));
+        let point = Vec2::new(1.5, 2.0);
+
+        assert!(close2(
+            transform.transform_point(point),
+            transform.as_mat3() * point
+        ));
+        let in_plane = transform
+            .as_mat4()
+            .transform_point(&point.as_vec3(0.0))
+            .unwrap();
+        assert!(close2(in_plane.xy(), transform.transform_point(point)));
+        // Clockwise, like `Mat3::rotation`.
+        assert!(close2(
+            Transform2D::from_rotation(FRAC_PI_2).transform_direction(Vec2::x_axis()),
+            -Vec2::y_axis()
+        ));
+    }
+
+    #[test]
+    fn transform_2d_composes_and_inverts() {
+        let child = Transform2D::new(Vec2::new(1.0, 2.0), 0.3, Vec2::new(1.0, 3.0));
+        let parent = Transform2D::new(Vec2::new(-4.0, 0.5), -1.1, Vec2::splat(2.0));
+        let point = Vec2::new(0.5, -0.5);
+
+        let world = child * parent;
+        assert!(close2(
+            world.transform_point(point),
+            parent.transform_point(child.transform_point(point))
+        ));
+        assert!(close2(
+            world.as_mat3() * point,
+            (child.as_mat3() * parent.as_mat3()) * point
+        ));
+
+        let inverse = parent.inverse().unwrap();
+        assert!(close2(
+            inverse.transform_point(parent.transform_point(point)),
+            point
+        ));
+        assert!(
+            Transform2D::from_scale(Vec2::new(1.0, 0.0))
+                .inverse()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn transform_3d_composes_inverts_and_matches_its_matrix() {
+        let child = Transform3D::new(
+            Vec3::new(1.0, 0.0, 2.0),
+            Quaternion::from_axis_angle(&Vec3::y_axis(), 0.4),
+            Vec3::new(1.0, 2.0, 0.5),
+        );
+        let parent = Transform3D::new(
+            Vec3::new(0.0, 5.0, 0.0),
+            Quaternion::from_axis_angle(&Vec3::normalized(&Vec3::new(1.0, 1.0, 0.0)), 1.2),
+            Vec3::splat(3.0),
+        );
+        let point = Vec3::new(0.5, -1.0, 2.0);
+
+        for transform in [child, parent] {
+            let by_matrix = transform.as_mat4().transform_point(&point).unwrap();
+            assert!(close3(by_matrix, transform.transform_point(point)));
+        }
+        let world = child * parent;
+        assert!(close3(
+            world.transform_point(point),
+            parent.transform_point(child.transform_point(point))
+        ));
+        let inverse = parent.inverse().unwrap();
+        assert!(close3(
+            inverse.transform_point(parent.transform_point(point)),
+            point
+        ));
+
+        let sphere = parent.transform_sphere(&Sphere::new(Vec3::x_axis(), 1.0));
+        assert_eq!(sphere.radius, 3.0);
+        assert!(close3(
+            sphere.center,
+            parent.transform_point(Vec3::x_axis())
+        ));
+        let aabb = Transform3D::from_translation(Vec3::one())
+            .transform_aabb(&Aabb3::from_center(Vec3::zero(), Vec3::one()));
+        assert!(close3(aabb.min, Vec3::zero()));
+    }
+}
@@ -0,0 +1,188 @@
+//! Double precision vectors, for positions far from the origin where `f32` runs out of
+//! precision, e.g. large worlds and tooling. Convert to the `f32` types for rendering,
+//! after moving the origin close to the camera.
+//!
+//! ```rust
+//! use chaos_engine::math::{DVec3, Vec3};
+//!
+//! let far = DVec3::new(10_000_000.0, 0.0, 0.0);
+//! let step = DVec3::new(0.001, 0.0, 0.0);
+//! let camera = DVec3::new(9_999_990.0, 0.0, 0.0);
+//! // In f32 the step would vanish at this distance.
+//! let relative = (far + step - camera).as_vec3();
+//! assert!((relative.x - 10.001).abs() < 1e-5);
+//! assert_eq!(DVec3::from(Vec3::one()), DVec3::one());
+//! ```
+
+use vulkano_macros::BufferContents;
+
+use crate::math::vector::{ivec::IVec2, ivec::IVec3, vec2::Vec2, vec3::Vec3, vec4::Vec4};
+
+#[derive(Debug, Clone, Copy, PartialEq, BufferContents)]
+#[repr(C)]
+pub struct DVec2 {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, BufferContents)]
+#[repr(C)]
+pub struct DVec3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, BufferContents)]
+#[repr(C)]
+pub struct DVec4 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+impl_vector_common!(DVec2, f64, 2; x, y);
+impl_vector_common!(DVec3, f64, 3; x, y, z);
+impl_vector_common!(DVec4, f64, 4; x, y, z, w);
+impl_float_vector!(DVec2, f64; x, y);
+impl_float_vector!(DVec3, f64; x, y, z);
+impl_float_vector!(DVec4, f64; x, y, z, w);
+impl_vector_ops!(DVec2, f64; x, y);
+impl_vector_ops!(DVec3, f64; x, y, z);
+impl_vector_ops!(DVec4, f64; x, y, z, w);
+impl_vector_neg!(DVec2; x, y);
+impl_vector_neg!(DVec3; x, y, z);
+impl_vector_neg!(DVec4; x, y, z, w);
+
+impl_vector_cast!(DVec2 => Vec2, as_vec2, f32; x, y);
+impl_vector_cast!(DVec3 => Vec3, as_vec3, f32; x, y, z);
+impl_vector_cast!(DVec4 => Vec4, as_vec4, f32; x, y, z, w);
+impl_vector_cast!(DVec2 => IVec2, as_ivec2, i32; x, y);
+impl_vector_cast!(DVec3 => IVec3, as_ivec3, i32; x, y, z);
+
+impl DVec2 {
+    pub const fn x_axis() -> Self {
+        Self::new(1.0, 0.0)
+    }
+
+    pub const fn y_axis() -> Self {
+        Self::new(0.0, 1.0)
+    }
+
+    /// The z component of the 3D cross product.
+    pub fn cross(one: &Self, other: &Self) -> f64 {
+        one.x * other.y - one.y * other.x
+    }
+
+    pub fn as_dvec3(&self, z: f64) -> DVec3 {
+        DVec3::new(self.x, self.y, z)
+    }
+
+    // swizzling methods
+    impl_swizzle2_all!((x, y), DVec2; x, y);
+    impl_swizzle3_all!((x, y), DVec3; x, y);
+    impl_swizzle4_all!((x, y), DVec4; x, y);
+}
+
+impl DVec3 {
+    pub const fn x_axis() -> Self {
+        Self::new(1.0, 0.0, 0.0)
+    }
+
+    pub const fn y_axis() -> Self {
+        Self::new(0.0, 1.0, 0.0)
+    }
+
+    pub const fn z_axis() -> Self {
+        Self::new(0.0, 0.0, 1.0)
+    }
+
+    pub fn cross(one: &Self, other: &Self) -> Self {
+        Self {
+            x: one.y * other.z - one.z * other.y,
+            y: one.z * other.x - one.x * other.z,
+            z: one.x * other.y - one.y * other.x,
+        }
+    }
+
+    pub fn as_dvec4(&self, w: f64) -> DVec4 {
+        DVec4::new(self.x, self.y, self.z, w)
+    }
+
+    // swizzling methods
+    impl_swizzle2_all!((x, y, z), DVec2; x, y, z);
+    impl_swizzle3_all!((x, y, z), DVec3; x, y, z);
+    impl_swizzle4_all!((x, y, z), DVec4; x, y, z);
+}
+
+impl DVec4 {
+    // swizzling methods
+    impl_swizzle2_all!((x, y, z, w), DVec2; x, y, z, w);
+    impl_swizzle3_all!((x, y, z, w), DVec3; x, y, z, w);
+    impl_swizzle4_all!((x, y, z, w), DVec4; x, y, z, w);
+}
+
+impl From<Vec2> for DVec2 {
+    fn from(vec: Vec2) -> Self {
+        Self::new(vec.x as f64, vec.y as f64)
+    }
+}
+
+impl From<Vec3> for DVec3 {
+    fn from(vec: Vec3) -> Self {
+        Self::new(vec.x as f64, vec.y as f64, vec.z as f64)
+    }
+}
+
+impl From<Vec4> for DVec4 {
+    fn from(vec: Vec4) -> Self {
+        Self::new(vec.x as f64, vec.y as f64, vec.z as f64, vec.w as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_and_lengths() {
+        let a = DVec3::new(1.0, 2.0, 2.0);
+        let b = DVec3::new(-1.0, 0.5, 4.0);
+
+        assert_eq!(a + b, DVec3::new(0.0, 2.5, 6.0));
+        assert_eq!(a - b, DVec3::new(2.0, 1.5, -2.0));
+        assert_eq!(a * 2.0, DVec3::new(2.0, 4.0, 4.0));
+        assert_eq!(a / b, DVec3::new(-1.0, 4.0, 0.5));
+        assert_eq!(-a, DVec3::new(-1.0, -2.0, -2.0));
+        assert_eq!(a.length(), 3.0);
+        assert_eq!(DVec3::dot(&a, &b), 8.0);
+        assert_eq!(
+            DVec3::cross(&DVec3::x_axis(), &DVec3::y_axis()),
+            DVec3::z_axis()
+        );
+        assert_eq!(DVec3::normalized(&DVec3::zero()), DVec3::zero());
+        assert_eq!(
+            DVec2::lerp(&DVec2::zero(), &DVec2::new(4.0, -2.0), 0.25),
+            DVec2::new(1.0, -0.5)
+        );
+        assert_eq!(
+            DVec4::from([1.0, 2.0, 3.0, 4.0]).wzyx(),
+            DVec4::new(4.0, 3.0, 2.0, 1.0)
+        );
+
+        let mut c = a;
+        c += b;
+        c *= 2.0;
+        assert_eq!(c, DVec3::new(0.0, 5.0, 12.0));
+    }
+
+    #[test]
+    fn conversions() {
+        let vec = Vec3::new(0.1, -2.5, 3.0);
+
+        assert_eq!(DVec3::from(vec).as_vec3(), vec);
+        assert_eq!(DVec2::new(-1.5, 2.9).as_ivec2(), IVec2::new(-1, 2));
+        assert_eq!(<[f64; 2]>::from(DVec2::new(1.0, 2.0)), [1.0, 2.0]);
+    }
+}
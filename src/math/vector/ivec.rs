@@ -0,0 +1,166 @@
+//! Integer vectors: `IVec2`/`IVec3` for grid and tile coordinates, `UVec2`/`UVec3` for
+//! sizes such as pixel extents. They are `Eq` and `Hash`, so they work as map keys.
+//!
+//! ```rust
+//! use chaos_engine::math::{IVec2, UVec2, Vec2};
+//!
+//! // Negative positions round down into the cell to their left.
+//! let tile = IVec2::floor(&(Vec2::new(-0.5, 33.0) / 16.0));
+//! assert_eq!(tile, IVec2::new(-1, 2));
+//! assert_eq!(UVec2::from([1920, 1080]).as_vec2(), Vec2::new(1920.0, 1080.0));
+//! ```
+
+use vulkano_macros::BufferContents;
+
+use crate::math::vector::{dvec::DVec2, dvec::DVec3, vec2::Vec2, vec3::Vec3};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, BufferContents)]
+#[repr(C)]
+pub struct IVec2 {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, BufferContents)]
+#[repr(C)]
+pub struct IVec3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, BufferContents)]
+#[repr(C)]
+pub struct UVec2 {
+    pub x: u32,
+    pub y: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, BufferContents)]
+#[repr(C)]
+pub struct UVec3 {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+}
+
+impl_vector_common!(IVec2, i32, 2; x, y);
+impl_vector_common!(IVec3, i32, 3; x, y, z);
+impl_vector_common!(UVec2, u32, 2; x, y);
+impl_vector_common!(UVec3, u32, 3; x, y, z);
+impl_vector_ops!(IVec2, i32; x, y);
+impl_vector_ops!(IVec3, i32; x, y, z);
+impl_vector_ops!(UVec2, u32; x, y);
+impl_vector_ops!(UVec3, u32; x, y, z);
+impl_vector_neg!(IVec2; x, y);
+impl_vector_neg!(IVec3; x, y, z);
+
+impl_vector_cast!(IVec2 => Vec2, as_vec2, f32; x, y);
+impl_vector_cast!(IVec3 => Vec3, as_vec3, f32; x, y, z);
+impl_vector_cast!(IVec2 => UVec2, as_uvec2, u32; x, y);
+impl_vector_cast!(IVec3 => UVec3, as_uvec3, u32; x, y, z);
+impl_vector_cast!(UVec2 => Vec2, as_vec2, f32; x, y);
+impl_vector_cast!(UVec3 => Vec3, as_vec3, f32; x, y, z);
+impl_vector_cast!(UVec2 => IVec2, as_ivec2, i32; x, y);
+impl_vector_cast!(UVec3 => IVec3, as_ivec3, i32; x, y, z);
+impl_vector_cast!(Vec2 => IVec2, as_ivec2, i32; x, y);
+impl_vector_cast!(Vec3 => IVec3, as_ivec3, i32; x, y, z);
+
+impl IVec2 {
+    /// The integer point at or below `point` on both axes, e.g. the cell of a grid with
+    /// cells of size one.
+    pub fn floor(point: &Vec2) -> Self {
+        Self::new(point.x.floor() as i32, point.y.floor() as i32)
+    }
+
+    pub fn abs(&self) -> Self {
+        Self::new(self.x.abs(), self.y.abs())
+    }
+
+    // swizzling methods
+    impl_swizzle2_all!((x, y), IVec2; x, y);
+    impl_swizzle3_all!((x, y), IVec3; x, y);
+}
+
+impl IVec3 {
+    /// The integer point at or below `point` on every axis.
+    pub fn floor(point: &Vec3) -> Self {
+        Self::new(
+            point.x.floor() as i32,
+            point.y.floor() as i32,
+            point.z.floor() as i32,
+        )
+    }
+
+    pub fn abs(&self) -> Self {
+        Self::new(self.x.abs(), self.y.abs(), self.z.abs())
+    }
+
+    // swizzling methods
+    impl_swizzle2_all!((x, y, z), IVec2; x, y, z);
+    impl_swizzle3_all!((x, y, z), IVec3; x, y, z);
+}
+
+impl From<IVec2> for DVec2 {
+    fn from(vec: IVec2) -> Self {
+        Self::new(vec.x as f64, vec.y as f64)
+    }
+}
+
+impl From<IVec3> for DVec3 {
+    fn from(vec: IVec3) -> Self {
+        Self::new(vec.x as f64, vec.y as f64, vec.z as f64)
+    }
+}
+
+impl From<UVec2> for DVec2 {
+    fn from(vec: UVec2) -> Self {
+        Self::new(vec.x as f64, vec.y as f64)
+    }
+}
+
+impl From<UVec3> for DVec3 {
+    fn from(vec: UVec3) -> Self {
+        Self::new(vec.x as f64, vec.y as f64, vec.z as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn integer_arithmetic() {
+        let a = IVec2::new(7, -3);
+
+        assert_eq!(a + IVec2::one(), IVec2::new(8, -2));
+        assert_eq!(a / 2, IVec2::new(3, -1));
+        assert_eq!(a * IVec2::new(2, 3), IVec2::new(14, -9));
+        assert_eq!(-a, IVec2::new(-7, 3));
+        assert_eq!(a.abs(), IVec2::new(7, 3));
+        assert_eq!(IVec2::dot(&a, &a), a.length_squared());
+        assert_eq!(
+            IVec3::clamp(&IVec3::new(-5, 5, 0), &IVec3::zero(), &IVec3::splat(2)),
+            IVec3::new(0, 2, 0)
+        );
+        assert_eq!(UVec2::new(640, 480) / 2, UVec2::new(320, 240));
+
+        let cells: HashSet<IVec2> = [a, IVec2::new(7, -3), IVec2::zero()].into();
+        assert_eq!(cells.len(), 2);
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(IVec2::floor(&Vec2::new(-0.5, 1.5)), IVec2::new(-1, 1));
+        assert_eq!(Vec2::new(-0.5, 1.5).as_ivec2(), IVec2::new(0, 1));
+        assert_eq!(
+            IVec3::floor(&Vec3::new(2.0, -2.0, -2.1)),
+            IVec3::new(2, -2, -3)
+        );
+        assert_eq!(IVec2::new(-1, 2).as_uvec2(), UVec2::new(u32::MAX, 2));
+        assert_eq!(UVec3::new(1, 2, 3).as_vec3(), Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(DVec2::from(IVec2::new(-4, 9)), DVec2::new(-4.0, 9.0));
+        assert_eq!(<[u32; 2]>::from(UVec2::new(800, 600)), [800, 600]);
+    }
+}
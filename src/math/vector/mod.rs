@@ -1,5 +1,16 @@
 macro_rules! impl_vector_reference_ops {
     ($type:ty) => {
+        impl_vector_reference_ops!($type, f32);
+
+        impl ::std::ops::Neg for &$type {
+            type Output = $type;
+
+            fn neg(self) -> Self::Output {
+                -*self
+            }
+        }
+    };
+    ($type:ty, $scalar:ty) => {
         impl ::std::ops::Add<&$type> for $type {
             type Output = Self;
 
@@ -60,10 +71,10 @@ macro_rules! impl_vector_reference_ops {
             }
         }
 
-        impl ::std::ops::Mul<f32> for &$type {
+        impl ::std::ops::Mul<$scalar> for &$type {
             type Output = $type;
 
-            fn mul(self, scalar: f32) -> Self::Output {
+            fn mul(self, scalar: $scalar) -> Self::Output {
                 *self * scalar
             }
         }
@@ -98,10 +109,10 @@ macro_rules! impl_vector_reference_ops {
             }
         }
 
-        impl ::std::ops::Div<f32> for &$type {
+        impl ::std::ops::Div<$scalar> for &$type {
             type Output = $type;
 
-            fn div(self, scalar: f32) -> Self::Output {
+            fn div(self, scalar: $scalar) -> Self::Output {
                 *self / scalar
             }
         }
@@ -135,6 +146,111 @@ macro_rules! impl_vector_reference_ops {
                 *self /= *rhs;
             }
         }
+    };
+}
+
+// Component-wise arithmetic, by value and by reference, for the vector types that are
+// not `f32`.
+macro_rules! impl_vector_ops {
+    ($type:ident, $scalar:ty; $($field:ident),+) => {
+        impl ::std::ops::Add for $type {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self::Output {
+                Self { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl ::std::ops::AddAssign for $type {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl ::std::ops::Sub for $type {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self::Output {
+                Self { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl ::std::ops::SubAssign for $type {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl ::std::ops::Mul<$scalar> for $type {
+            type Output = Self;
+
+            fn mul(self, scalar: $scalar) -> Self::Output {
+                Self { $($field: self.$field * scalar),+ }
+            }
+        }
+
+        impl ::std::ops::MulAssign<$scalar> for $type {
+            fn mul_assign(&mut self, scalar: $scalar) {
+                *self = *self * scalar;
+            }
+        }
+
+        impl ::std::ops::Mul for $type {
+            type Output = Self;
+
+            fn mul(self, other: Self) -> Self::Output {
+                Self { $($field: self.$field * other.$field),+ }
+            }
+        }
+
+        impl ::std::ops::MulAssign for $type {
+            fn mul_assign(&mut self, other: Self) {
+                *self = *self * other;
+            }
+        }
+
+        impl ::std::ops::Div<$scalar> for $type {
+            type Output = Self;
+
+            fn div(self, scalar: $scalar) -> Self::Output {
+                Self { $($field: self.$field / scalar),+ }
+            }
+        }
+
+        impl ::std::ops::DivAssign<$scalar> for $type {
+            fn div_assign(&mut self, scalar: $scalar) {
+                *self = *self / scalar;
+            }
+        }
+
+        impl ::std::ops::Div for $type {
+            type Output = Self;
+
+            fn div(self, other: Self) -> Self::Output {
+                Self { $($field: self.$field / other.$field),+ }
+            }
+        }
+
+        impl ::std::ops::DivAssign for $type {
+            fn div_assign(&mut self, other: Self) {
+                *self = *self / other;
+            }
+        }
+
+        impl_vector_reference_ops!($type, $scalar);
+    };
+}
+
+// Negation for the signed vector types.
+macro_rules! impl_vector_neg {
+    ($type:ident; $($field:ident),+) => {
+        impl ::std::ops::Neg for $type {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self { $($field: -self.$field),+ }
+            }
+        }
 
         impl ::std::ops::Neg for &$type {
             type Output = $type;
@@ -146,6 +262,104 @@ macro_rules! impl_vector_reference_ops {
     };
 }
 
+// Constructors, component-wise helpers and array conversions shared by every vector type.
+macro_rules! impl_vector_common {
+    ($type:ident, $scalar:ty, $len:literal; $($field:ident),+) => {
+        impl $type {
+            pub const fn new($($field: $scalar),+) -> Self {
+                Self { $($field),+ }
+            }
+
+            pub const fn zero() -> Self {
+                Self::splat(0 as $scalar)
+            }
+
+            pub const fn one() -> Self {
+                Self::splat(1 as $scalar)
+            }
+
+            pub const fn splat(value: $scalar) -> Self {
+                Self { $($field: value),+ }
+            }
+
+            pub fn dot(one: &Self, other: &Self) -> $scalar {
+                0 as $scalar $(+ one.$field * other.$field)+
+            }
+
+            pub fn length_squared(&self) -> $scalar {
+                Self::dot(self, self)
+            }
+
+            pub fn min(one: &Self, other: &Self) -> Self {
+                Self { $($field: one.$field.min(other.$field)),+ }
+            }
+
+            pub fn max(one: &Self, other: &Self) -> Self {
+                Self { $($field: one.$field.max(other.$field)),+ }
+            }
+
+            pub fn clamp(value: &Self, min: &Self, max: &Self) -> Self {
+                Self { $($field: value.$field.clamp(min.$field, max.$field)),+ }
+            }
+        }
+
+        impl From<[$scalar; $len]> for $type {
+            fn from([$($field),+]: [$scalar; $len]) -> Self {
+                Self { $($field),+ }
+            }
+        }
+
+        impl From<$type> for [$scalar; $len] {
+            fn from(vec: $type) -> Self {
+                [$(vec.$field),+]
+            }
+        }
+    };
+}
+
+// Lengths, distances and interpolation for the floating point vector types.
+macro_rules! impl_float_vector {
+    ($type:ident, $scalar:ty; $($field:ident),+) => {
+        impl $type {
+            pub fn length(&self) -> $scalar {
+                self.length_squared().sqrt()
+            }
+
+            /// Zero stays zero.
+            pub fn normalized(v: &Self) -> Self {
+                let length = v.length();
+                if length == 0.0 { *v } else { *v / length }
+            }
+
+            pub fn distance(one: &Self, other: &Self) -> $scalar {
+                (*one - *other).length()
+            }
+
+            pub fn distance_squared(one: &Self, other: &Self) -> $scalar {
+                (*one - *other).length_squared()
+            }
+
+            pub fn lerp(one: &Self, other: &Self, t: $scalar) -> Self {
+                Self { $($field: one.$field + (other.$field - one.$field) * t),+ }
+            }
+        }
+    };
+}
+
+// `method` converting to `target` with `as`, so float to integer truncates towards zero
+// and saturates, and integer to float rounds to the nearest representable value.
+macro_rules! impl_vector_cast {
+    ($type:ident => $target:ident, $method:ident, $scalar:ty; $($field:ident),+) => {
+        impl $type {
+            pub fn $method(&self) -> $target {
+                $target { $($field: self.$field as $scalar),+ }
+            }
+        }
+    };
+}
+
+pub mod dvec;
+pub mod ivec;
 pub mod vec2;
 pub mod vec3;
 pub mod vec4;
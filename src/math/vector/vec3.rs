@@ -2,6 +2,7 @@ use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssi
 
 use vulkano_macros::BufferContents;
 
+use crate::math::simd;
 use crate::math::vector::vec2::Vec2;
 use crate::math::vector::vec4::Vec4;
 
@@ -170,7 +171,7 @@ impl Vec3 {
     /// assert_eq!(dot, 32.0);
     /// ```
     pub fn dot(one: &Self, other: &Self) -> f32 {
-        one.x * other.x + one.y * other.y + one.z * other.z
+        simd::dot3((*one).into(), (*other).into())
     }
 
     /// Returns the 3D cross product of two vectors.
@@ -182,11 +183,7 @@ impl Vec3 {
     /// assert_eq!(cross, Vec3::z_axis());
     /// ```
     pub fn cross(one: &Self, other: &Self) -> Self {
-        Self {
-            x: one.y * other.z - one.z * other.y,
-            y: one.z * other.x - one.x * other.z,
-            z: one.x * other.y - one.y * other.x,
-        }
+        simd::cross3((*one).into(), (*other).into()).into()
     }
 
     /// Linearly interpolates from `one` to `other` by `t`.
@@ -567,6 +564,12 @@ impl From<[f32; 3]> for Vec3 {
     }
 }
 
+impl From<Vec3> for [f32; 3] {
+    fn from(vec: Vec3) -> Self {
+        [vec.x, vec.y, vec.z]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,5 +1,6 @@
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
+use crate::math::simd;
 use crate::math::vector::vec2::Vec2;
 use crate::math::vector::vec3::Vec3;
 
@@ -195,7 +196,7 @@ impl Vec4 {
     /// assert_eq!(dot, 70.0);
     /// ```
     pub fn dot(one: &Self, other: &Self) -> f32 {
-        one.x * other.x + one.y * other.y + one.z * other.z + one.w * other.w
+        simd::dot4((*one).into(), (*other).into())
     }
 
     /// Returns the 4D cross product perpendicular to three input vectors.
@@ -467,21 +468,13 @@ impl Add for Vec4 {
     type Output = Self;
 
     fn add(self, other: Self) -> Self::Output {
-        Self {
-            x: self.x + other.x,
-            y: self.y + other.y,
-            z: self.z + other.z,
-            w: self.w + other.w,
-        }
+        simd::add4(self.into(), other.into()).into()
     }
 }
 
 impl AddAssign for Vec4 {
     fn add_assign(&mut self, other: Self) {
-        self.x += other.x;
-        self.y += other.y;
-        self.z += other.z;
-        self.w += other.w;
+        *self = *self + other;
     }
 }
 
@@ -489,21 +482,13 @@ impl Sub for Vec4 {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-            z: self.z - rhs.z,
-            w: self.w - rhs.w,
-        }
+        simd::sub4(self.into(), rhs.into()).into()
     }
 }
 
 impl SubAssign for Vec4 {
     fn sub_assign(&mut self, rhs: Self) {
-        self.x -= rhs.x;
-        self.y -= rhs.y;
-        self.z -= rhs.z;
-        self.w -= rhs.w;
+        *self = *self - rhs;
     }
 }
 
@@ -511,21 +496,13 @@ impl Mul<f32> for Vec4 {
     type Output = Self;
 
     fn mul(self, scalar: f32) -> Self::Output {
-        Self {
-            x: self.x * scalar,
-            y: self.y * scalar,
-            z: self.z * scalar,
-            w: self.w * scalar,
-        }
+        simd::scale4(self.into(), scalar).into()
     }
 }
 
 impl MulAssign<f32> for Vec4 {
     fn mul_assign(&mut self, scalar: f32) {
-        self.x *= scalar;
-        self.y *= scalar;
-        self.z *= scalar;
-        self.w *= scalar;
+        *self = *self * scalar;
     }
 }
 
@@ -533,21 +510,13 @@ impl Mul<Vec4> for Vec4 {
     type Output = Self;
 
     fn mul(self, other: Vec4) -> Self::Output {
-        Self {
-            x: self.x * other.x,
-            y: self.y * other.y,
-            z: self.z * other.z,
-            w: self.w * other.w,
-        }
+        simd::mul4(self.into(), other.into()).into()
     }
 }
 
 impl MulAssign<Vec4> for Vec4 {
     fn mul_assign(&mut self, other: Vec4) {
-        self.x *= other.x;
-        self.y *= other.y;
-        self.z *= other.z;
-        self.w *= other.w;
+        *self = *self * other;
     }
 }
 
@@ -555,21 +524,13 @@ impl Div<f32> for Vec4 {
     type Output = Self;
 
     fn div(self, scalar: f32) -> Self::Output {
-        Self {
-            x: self.x / scalar,
-            y: self.y / scalar,
-            z: self.z / scalar,
-            w: self.w / scalar,
-        }
+        simd::div4(self.into(), [scalar; 4]).into()
     }
 }
 
 impl DivAssign<f32> for Vec4 {
     fn div_assign(&mut self, scalar: f32) {
-        self.x /= scalar;
-        self.y /= scalar;
-        self.z /= scalar;
-        self.w /= scalar;
+        *self = *self / scalar;
     }
 }
 
@@ -577,21 +538,13 @@ impl Div<Vec4> for Vec4 {
     type Output = Self;
 
     fn div(self, other: Vec4) -> Self::Output {
-        Self {
-            x: self.x / other.x,
-            y: self.y / other.y,
-            z: self.z / other.z,
-            w: self.w / other.w,
-        }
+        simd::div4(self.into(), other.into()).into()
     }
 }
 
 impl DivAssign<Vec4> for Vec4 {
     fn div_assign(&mut self, other: Vec4) {
-        self.x /= other.x;
-        self.y /= other.y;
-        self.z /= other.z;
-        self.w /= other.w;
+        *self = *self / other;
     }
 }
 
@@ -621,6 +574,12 @@ impl From<[f32; 4]> for Vec4 {
     }
 }
 
+impl From<Vec4> for [f32; 4] {
+    fn from(vec: Vec4) -> Self {
+        [vec.x, vec.y, vec.z, vec.w]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,180 @@
+use crate::math::Vec2;
+
+// Rotations follow `Mat3::rotation`, which turns positive angles clockwise, so angular
+// velocities and torques are positive clockwise as well.
+//
+// Velocity of a point at offset `offset` from the center of a body spinning at
+// `angular_velocity`.
+pub(crate) fn spin_velocity(angular_velocity: f32, offset: Vec2) -> Vec2 {
+    Vec2::new(angular_velocity * offset.y, -angular_velocity * offset.x)
+}
+
+// Torque (or angular impulse) of a force (or impulse) applied at `offset`.
+pub(crate) fn torque(offset: Vec2, force: Vec2) -> f32 {
+    -Vec2::cross(&offset, &force)
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BodyType {
+    /// Moved by forces, impulses and collisions.
+    #[default]
+    Dynamic,
+    /// Never moves; other bodies bounce off it.
+    Static,
+    /// Moves with its velocity only, pushing dynamic bodies without being pushed back.
+    Kinematic,
+}
+
+/// Component simulated by the `PhysicsSystem`. The body owns its pose (`position` and
+/// `rotation` around it, in world space); the system copies it to the entity's `Transform`,
+/// so keep bodies on entities without a `Parent`.
+/// Collides if the entity also has a `Collider2D`.
+///
+/// ```rust
+/// use chaos_engine::{math::Vec2, physics::body::RigidBody2D};
+///
+/// let ball = RigidBody2D::dynamic(2.0)
+///     .with_position(Vec2::new(0.0, 10.0))
+///     .with_velocity(Vec2::new(3.0, 0.0))
+///     .with_restitution(0.8);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RigidBody2D {
+    pub body_type: BodyType,
+    pub position: Vec2,
+    pub rotation: f32,
+    pub linear_velocity: Vec2,
+    pub angular_velocity: f32,
+    pub mass: f32,
+    // Moment of inertia around the position, computed from the collider (or 1.0 without
+    // one) when `None`.
+    pub inertia: Option<f32>,
+    // Bounciness, from 0.0 (none) to 1.0 (elastic). The larger value of two bodies is used.
+    pub restitution: f32,
+    pub friction: f32,
+    // Fraction of the velocity lost per second.
+    pub linear_damping: f32,
+    pub angular_damping: f32,
+    pub gravity_scale: f32,
+    pub(crate) force: Vec2,
+    pub(crate) torque: f32,
+    pub(crate) impulse: Vec2,
+    pub(crate) angular_impulse: f32,
+}
+
+impl RigidBody2D {
+    pub fn dynamic(mass: f32) -> Self {
+        Self::new(BodyType::Dynamic, mass)
+    }
+
+    pub fn fixed() -> Self {
+        Self::new(BodyType::Static, 0.0)
+    }
+
+    pub fn kinematic() -> Self {
+        Self::new(BodyType::Kinematic, 0.0)
+    }
+
+    fn new(body_type: BodyType, mass: f32) -> Self {
+        Self {
+            body_type,
+            position: Vec2::zero(),
+            rotation: 0.0,
+            linear_velocity: Vec2::zero(),
+            angular_velocity: 0.0,
+            mass: mass.max(0.0),
+            inertia: None,
+            restitution: 0.0,
+            friction: 0.5,
+            linear_damping: 0.0,
+            angular_damping: 0.0,
+            gravity_scale: 1.0,
+            force: Vec2::zero(),
+            torque: 0.0,
+            impulse: Vec2::zero(),
+            angular_impulse: 0.0,
+        }
+    }
+
+    pub fn with_position(mut self, position: Vec2) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_velocity(mut self, velocity: Vec2) -> Self {
+        self.linear_velocity = velocity;
+        self
+    }
+
+    pub fn with_angular_velocity(mut self, angular_velocity: f32) -> Self {
+        self.angular_velocity = angular_velocity;
+        self
+    }
+
+    pub fn with_inertia(mut self, inertia: f32) -> Self {
+        self.inertia = Some(inertia.max(0.0));
+        self
+    }
+
+    pub fn with_restitution(mut self, restitution: f32) -> Self {
+        self.restitution = restitution.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_friction(mut self, friction: f32) -> Self {
+        self.friction = friction.max(0.0);
+        self
+    }
+
+    pub fn with_damping(mut self, linear: f32, angular: f32) -> Self {
+        self.linear_damping = linear.max(0.0);
+        self.angular_damping = angular.max(0.0);
+        self
+    }
+
+    pub fn with_gravity_scale(mut self, gravity_scale: f32) -> Self {
+        self.gravity_scale = gravity_scale;
+        self
+    }
+
+    pub fn is_dynamic(&self) -> bool {
+        self.body_type == BodyType::Dynamic
+    }
+
+    /// Zero for static and kinematic bodies and for dynamic bodies without mass.
+    pub fn inverse_mass(&self) -> f32 {
+        if self.is_dynamic() && self.mass > 0.0 {
+            1.0 / self.mass
+        } else {
+            0.0
+        }
+    }
+
+    /// Applied during the next physics step, then cleared.
+    pub fn apply_force(&mut self, force: Vec2) {
+        self.force += force;
+    }
+
+    /// Applied during the next physics step, then cleared.
+    pub fn apply_torque(&mut self, torque: f32) {
+        self.torque += torque;
+    }
+
+    /// Changes the velocity at the start of the next physics step, as if hit at `point` (in
+    /// world space). Unlike a force it doesn't scale with the step. It waits for the step
+    /// because the inertia may come from the entity's `Collider2D`.
+    pub fn apply_impulse(&mut self, impulse: Vec2, point: Vec2) {
+        self.impulse += impulse;
+        self.angular_impulse += torque(point - self.position, impulse);
+    }
+
+    /// Velocity of the body at `point` (in world space), including its spin.
+    pub fn velocity_at(&self, point: Vec2) -> Vec2 {
+        self.linear_velocity + spin_velocity(self.angular_velocity, point - self.position)
+    }
+}
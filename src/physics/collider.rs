@@ -0,0 +1,199 @@
+use std::{f32::consts::PI, fmt::Display};
+
+use crate::math::{
+    Vec2,
+    matrix::Mat3,
+    shape::{
+        aabb::Aabb2, capsule::Capsule2, circle::Circle, polygon::ConvexPolygon2, shape2d::Shape2D,
+        triangle::Triangle2D,
+    },
+};
+
+#[derive(Debug, PartialEq)]
+pub enum ColliderError {
+    /// Polygon colliders need at least three points that don't lie on one line.
+    DegeneratePolygon,
+}
+
+impl Display for ColliderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColliderError::DegeneratePolygon => {
+                write!(
+                    f,
+                    "Polygon collider needs three points that aren't on one line"
+                )
+            }
+        }
+    }
+}
+
+/// Collision shape of a `RigidBody2D`, in the body's local space (around its position).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Collider2D {
+    pub shape: Shape2D,
+    // Sensors report collision events but don't push bodies apart.
+    pub is_sensor: bool,
+}
+
+impl Collider2D {
+    pub fn new(shape: impl Into<Shape2D>) -> Self {
+        Self {
+            shape: shape.into(),
+            is_sensor: false,
+        }
+    }
+
+    pub fn circle(radius: f32) -> Self {
+        Self::new(Circle::new(Vec2::zero(), radius))
+    }
+
+    /// Box with the given half width and half height.
+    pub fn rectangle(half_extents: Vec2) -> Self {
+        Self::new(Aabb2::from_center(Vec2::zero(), half_extents))
+    }
+
+    pub fn capsule(start: Vec2, end: Vec2, radius: f32) -> Self {
+        Self::new(Capsule2::new(start, end, radius))
+    }
+
+    /// The convex hull of `points`, so concave outlines (e.g. the triangles of a
+    /// triangulated shape) collide as their hull.
+    pub fn convex_hull(points: &[Vec2]) -> Result<Self, ColliderError> {
+        ConvexPolygon2::from_points(points)
+            .map(Self::new)
+            .ok_or(ColliderError::DegeneratePolygon)
+    }
+
+    pub fn from_triangles(triangles: &[Triangle2D]) -> Result<Self, ColliderError> {
+        let points: Vec<Vec2> = triangles
+            .iter()
+            .flat_map(|triangle| [triangle.a, triangle.b, triangle.c])
+            .collect();
+        Self::convex_hull(&points)
+    }
+
+    pub fn as_sensor(mut self) -> Self {
+        self.is_sensor = true;
+        self
+    }
+
+    /// Distance from the body's position to the farthest point of the shape.
+    pub fn bounding_radius(&self) -> f32 {
+        let (points, radius) = self.shape.core();
+        points
+            .iter()
+            .map(Vec2::length_squared)
+            .fold(0.0f32, f32::max)
+            .sqrt()
+            + radius
+    }
+
+    /// Moment of inertia around the body's position for a uniform density.
+    pub fn inertia(&self, mass: f32) -> f32 {
+        match &self.shape {
+            Shape2D::Circle(circle) => {
+                mass * (0.5 * circle.radius * circle.radius + circle.center.length_squared())
+            }
+            Shape2D::Capsule(capsule) => {
+                // A box along the segment plus a disc split over its ends, each around the
+                // segment's middle.
+                let (length, radius) =
+                    (Vec2::distance(&capsule.start, &capsule.end), capsule.radius);
+                let box_area = 2.0 * radius * length;
+                let disc_area = PI * radius * radius;
+                let box_inertia = box_area * (length * length + 4.0 * radius * radius) / 12.0;
+                let disc_inertia = disc_area * (0.5 * radius * radius + 0.25 * length * length);
+                let area = box_area + disc_area;
+                if area <= 0.0 {
+                    return 0.0;
+                }
+                let middle = (capsule.start + capsule.end) * 0.5;
+                mass * ((box_inertia + disc_inertia) / area + middle.length_squared())
+            }
+            _ => {
+                // Sum over the triangles fanned out from the body's position.
+                let (points, _) = self.shape.core();
+                let mut numerator = 0.0;
+                let mut denominator = 0.0;
+                for (index, a) in points.iter().enumerate() {
+                    let b = &points[(index + 1) % points.len()];
+                    let cross = Vec2::cross(a, b).abs();
+                    numerator += cross * (Vec2::dot(a, a) + Vec2::dot(a, b) + Vec2::dot(b, b));
+                    denominator += cross;
+                }
+                if denominator > 0.0 {
+                    mass * numerator / (6.0 * denominator)
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    /// The shape placed at a body's position and rotation.
+    pub fn to_world(&self, position: Vec2, rotation: f32) -> Shape2D {
+        self.shape
+            .transformed(&(Mat3::rotation(rotation) * Mat3::translation(position.x, position.y)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convex_hull_drops_inner_and_collinear_points() {
+        let collider = Collider2D::convex_hull(&[
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, 0.5),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(0.0, 2.0),
+        ])
+        .unwrap();
+
+        let Shape2D::Polygon(polygon) = &collider.shape else {
+            panic!("expected a polygon, got {:?}", collider.shape);
+        };
+        assert_eq!(
+            polygon.points(),
+            [
+                Vec2::new(0.0, 0.0),
+                Vec2::new(2.0, 0.0),
+                Vec2::new(2.0, 2.0),
+                Vec2::new(0.0, 2.0),
+            ]
+        );
+        assert_eq!(
+            Collider2D::convex_hull(&[Vec2::zero(), Vec2::one(), Vec2::one() * 2.0]),
+            Err(ColliderError::DegeneratePolygon)
+        );
+    }
+
+    #[test]
+    fn inertia_matches_known_shapes() {
+        let square = Collider2D::rectangle(Vec2::new(1.0, 1.0));
+        // A square of side s has inertia m * s^2 / 6 around its center.
+        assert!((square.inertia(3.0) - 3.0 * 4.0 / 6.0).abs() < 1e-5);
+        assert_eq!(Collider2D::circle(2.0).inertia(1.0), 2.0);
+        assert!((square.bounding_radius() - 2f32.sqrt()).abs() < 1e-6);
+
+        // Without a segment a capsule is a circle.
+        let capsule = Collider2D::capsule(Vec2::zero(), Vec2::zero(), 2.0);
+        assert!((capsule.inertia(1.0) - 2.0).abs() < 1e-5);
+        let capsule = Collider2D::capsule(Vec2::new(-1.0, 0.0), Vec2::new(1.0, 0.0), 0.5);
+        assert!((capsule.bounding_radius() - 1.5).abs() < 1e-6);
+        assert!(capsule.inertia(1.0) > Collider2D::circle(0.5).inertia(1.0));
+    }
+
+    #[test]
+    fn to_world_moves_and_turns_the_shape() {
+        let collider = Collider2D::rectangle(Vec2::new(2.0, 0.5));
+        let shape = collider.to_world(Vec2::new(5.0, 0.0), std::f32::consts::FRAC_PI_2);
+
+        assert!(shape.contains_point(Vec2::new(5.0, 1.9)));
+        assert!(!shape.contains_point(Vec2::new(6.0, 0.0)));
+    }
+}
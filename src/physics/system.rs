@@ -0,0 +1,644 @@
+use std::collections::{HashMap, HashSet};
+
+use chaos_communicator::message::ChaosMessageBuilder;
+
+use crate::{
+    ecs::{EntityID, system::ChaosSystem, world::ChaosWorld},
+    math::{Vec2, shape::convex::Contact2D},
+    physics::{
+        body::{BodyType, RigidBody2D, spin_velocity, torque},
+        collider::Collider2D,
+    },
+    spatial::index::{SpatialBounds, SpatialIndex},
+    transform::component::Transform,
+    triggers::trigger_event_key::TriggerEventKey,
+};
+
+/// Sent by the `PhysicsSystem` when two colliders start or stop touching, with the entities
+/// as the `entity_a` and `entity_b` params (the lower id first). `Started` also has the
+/// contact `normal` (a `Vec2` pointing from `entity_a` to `entity_b`). Receive them with
+/// `ChaosWorld::register_for_trigger(CollisionEvent::Started)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CollisionEvent {
+    Started,
+    Ended,
+}
+
+// Contacts closer than this don't bounce, so resting bodies settle.
+const RESTITUTION_THRESHOLD: f32 = 0.5;
+// Overlap left alone by position correction, to keep contacts from jittering.
+const PENETRATION_SLOP: f32 = 0.01;
+// Fraction of the remaining overlap corrected per step.
+const PENETRATION_CORRECTION: f32 = 0.8;
+
+struct Body {
+    entity: EntityID,
+    body: RigidBody2D,
+    collider: Option<Collider2D>,
+    inverse_mass: f32,
+    inverse_inertia: f32,
+}
+
+struct ContactConstraint {
+    a: usize,
+    b: usize,
+    normal: Vec2,
+    offset_a: Vec2,
+    offset_b: Vec2,
+    normal_mass: f32,
+    tangent_mass: f32,
+    bounce: f32,
+    friction: f32,
+    normal_impulse: f32,
+    tangent_impulse: f32,
+}
+
+/// Simulates `RigidBody2D`s in fixed steps of world time: applies gravity and forces, finds
+/// contacts between `Collider2D`s (broad phase through a [`SpatialIndex`]), resolves them
+/// with impulses and pushes overlapping bodies apart. Sends [`CollisionEvent`]s and copies
+/// each body's pose to the entity's [`Transform`], if it has one.
+pub struct PhysicsSystem {
+    time_step: f32,
+    max_steps: u32,
+    velocity_iterations: u32,
+    gravity: Vec2,
+    accumulator: f32,
+    broad_phase: SpatialIndex,
+    touching: HashSet<(EntityID, EntityID)>,
+}
+
+impl PhysicsSystem {
+    pub fn new() -> Self {
+        Self {
+            time_step: 1.0 / 60.0,
+            max_steps: 8,
+            velocity_iterations: 8,
+            gravity: Vec2::zero(),
+            accumulator: 0.0,
+            broad_phase: SpatialIndex::new(4.0),
+            touching: HashSet::new(),
+        }
+    }
+
+    pub fn with_gravity(mut self, gravity: Vec2) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    /// Seconds simulated per step. Slow frames run several steps, at most `max_steps`; the
+    /// rest of the time is dropped, so the simulation slows down instead of stalling.
+    pub fn with_time_step(mut self, time_step: f32, max_steps: u32) -> Self {
+        self.time_step = time_step.max(f32::EPSILON);
+        self.max_steps = max_steps.max(1);
+        self
+    }
+
+    /// More iterations make stacks and chains of bodies stiffer, at a cost.
+    pub fn with_velocity_iterations(mut self, iterations: u32) -> Self {
+        self.velocity_iterations = iterations.max(1);
+        self
+    }
+
+    /// Cell size of the broad phase grid, around the size of typical bodies.
+    pub fn with_cell_size(mut self, cell_size: f32) -> Self {
+        self.broad_phase = SpatialIndex::new(cell_size);
+        self
+    }
+
+    pub(crate) fn advance(
+        &mut self,
+        world: &mut ChaosWorld,
+        delta_time: f32,
+    ) -> Result<(), &'static str> {
+        self.accumulator += delta_time;
+        let mut steps = 0;
+        while self.accumulator >= self.time_step {
+            if steps == self.max_steps {
+                self.accumulator = 0.0;
+                break;
+            }
+            self.step(world)?;
+            self.accumulator -= self.time_step;
+            steps += 1;
+        }
+
+        for (_, (body, transform)) in world
+            .query::<(&RigidBody2D, &mut Transform)>()
+            .map_err(|_| "Failed to query body transforms")?
+        {
+            transform.translation = body.position;
+            transform.rotation = body.rotation;
+        }
+        Ok(())
+    }
+
+    fn step(&mut self, world: &mut ChaosWorld) -> Result<(), &'static str> {
+        let mut bodies = Vec::new();
+        for (entity, (body,)) in world
+            .query::<(&mut RigidBody2D,)>()
+            .map_err(|_| "Failed to query rigid bodies")?
+        {
+            bodies.push((entity, body.clone()));
+            body.force = Vec2::zero();
+            body.torque = 0.0;
+            body.impulse = Vec2::zero();
+            body.angular_impulse = 0.0;
+        }
+        let mut bodies: Vec<Body> = bodies
+            .into_iter()
+            .map(|(entity, body)| {
+                let collider = world.get_component::<Collider2D>(entity).cloned();
+                let inverse_mass = body.inverse_mass();
+                let inertia = match (body.inertia, &collider) {
+                    (Some(inertia), _) => inertia,
+                    (None, Some(collider)) => collider.inertia(body.mass),
+                    (None, None) => 1.0,
+                };
+                let inverse_inertia = if inverse_mass > 0.0 && inertia > 0.0 {
+                    1.0 / inertia
+                } else {
+                    0.0
+                };
+                Body {
+                    entity,
+                    body,
+                    collider,
+                    inverse_mass,
+                    inverse_inertia,
+                }
+            })
+            .collect();
+        // Solve in a fixed order, so the result doesn't depend on component storage order.
+        bodies.sort_by_key(|body| body.entity);
+
+        let dt = self.time_step;
+        for body in bodies.iter_mut().filter(|body| body.inverse_mass > 0.0) {
+            let state = &mut body.body;
+            state.linear_velocity += state.impulse * body.inverse_mass;
+            state.angular_velocity += state.angular_impulse * body.inverse_inertia;
+            let acceleration = self.gravity * state.gravity_scale + state.force * body.inverse_mass;
+            state.linear_velocity += acceleration * dt;
+            state.angular_velocity += state.torque * body.inverse_inertia * dt;
+            state.linear_velocity *= 1.0 / (1.0 + state.linear_damping * dt);
+            state.angular_velocity *= 1.0 / (1.0 + state.angular_damping * dt);
+        }
+
+        let touching = self.find_contacts(&bodies);
+        let solid: Vec<_> = touching
+            .iter()
+            .filter(|(a, b, _)| {
+                !bodies[*a].collider.as_ref().is_some_and(|c| c.is_sensor)
+                    && !bodies[*b].collider.as_ref().is_some_and(|c| c.is_sensor)
+                    && bodies[*a].inverse_mass + bodies[*b].inverse_mass > 0.0
+            })
+            .collect();
+        let mut constraints: Vec<ContactConstraint> = solid
+            .iter()
+            .flat_map(|(a, b, contact)| {
+                contact
+                    .points
+                    .iter()
+                    .map(|point| contact_constraint(&bodies, *a, *b, contact.normal, point.point))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        for _ in 0..self.velocity_iterations {
+            for constraint in &mut constraints {
+                solve(&mut bodies, constraint);
+            }
+        }
+
+        for body in bodies
+            .iter_mut()
+            .filter(|body| body.body.body_type != BodyType::Static)
+        {
+            let state = &mut body.body;
+            state.position += state.linear_velocity * dt;
+            state.rotation += state.angular_velocity * dt;
+        }
+
+        // Once per pair rather than per contact point, so manifolds with two points aren't
+        // pushed apart twice.
+        for (a, b, contact) in solid {
+            let depth = contact
+                .points
+                .iter()
+                .map(|point| point.depth)
+                .fold(0.0, f32::max);
+            let (inverse_a, inverse_b) = (bodies[*a].inverse_mass, bodies[*b].inverse_mass);
+            let correction = contact.normal
+                * ((depth - PENETRATION_SLOP).max(0.0) * PENETRATION_CORRECTION
+                    / (inverse_a + inverse_b));
+            bodies[*a].body.position -= correction * inverse_a;
+            bodies[*b].body.position += correction * inverse_b;
+        }
+
+        for body in &bodies {
+            if let Some(stored) = world.get_component_mut::<RigidBody2D>(body.entity) {
+                stored.position = body.body.position;
+                stored.rotation = body.body.rotation;
+                stored.linear_velocity = body.body.linear_velocity;
+                stored.angular_velocity = body.body.angular_velocity;
+            }
+        }
+
+        let started: HashMap<(EntityID, EntityID), Vec2> = touching
+            .iter()
+            .map(|(a, b, contact)| {
+                let (a, b) = (bodies[*a].entity, bodies[*b].entity);
+                if a < b {
+                    ((a, b), contact.normal)
+                } else {
+                    ((b, a), -contact.normal)
+                }
+            })
+            .collect();
+        self.send_events(world, started);
+        Ok(())
+    }
+
+    // Pairs of colliding bodies (indices into `bodies`) with their contacts.
+    fn find_contacts(&mut self, bodies: &[Body]) -> Vec<(usize, usize, Contact2D)> {
+        let mut indices = HashMap::new();
+        let mut shapes = Vec::with_capacity(bodies.len());
+        for (index, body) in bodies.iter().enumerate() {
+            shapes.push(
+                body.collider
+                    .as_ref()
+                    .map(|collider| collider.to_world(body.body.position, body.body.rotation)),
+            );
+            match &body.collider {
+                Some(collider) => {
+                    indices.insert(body.entity, index);
+                    self.broad_phase.insert(
+                        body.entity,
+                        SpatialBounds::new(body.body.position, collider.bounding_radius()),
+                    );
+                }
+                None => {
+                    self.broad_phase.remove(body.entity);
+                }
+            }
+        }
+        let gone: Vec<_> = self
+            .broad_phase
+            .entities()
+            .filter(|entity| !indices.contains_key(entity))
+            .collect();
+        for entity in gone {
+            self.broad_phase.remove(entity);
+        }
+
+        self.broad_phase
+            .overlapping_pairs()
+            .into_iter()
+            .filter_map(|(a, b)| {
+                let (a, b) = (indices[&a], indices[&b]);
+                // Static and kinematic bodies don't collide with each other.
+                if !bodies[a].body.is_dynamic() && !bodies[b].body.is_dynamic() {
+                    return None;
+                }
+                let contact = shapes[a].as_ref()?.contact(shapes[b].as_ref()?)?;
+                Some((a, b, contact))
+            })
+            .collect()
+    }
+
+    fn send_events(
+        &mut self,
+        world: &mut ChaosWorld,
+        touching: HashMap<(EntityID, EntityID), Vec2>,
+    ) {
+        let mut started: Vec<_> = touching
+            .iter()
+            .filter(|(pair, _)| !self.touching.contains(pair))
+            .collect();
+        started.sort_by_key(|(pair, _)| **pair);
+        let mut ended: Vec<_> = self
+            .touching
+            .iter()
+            .filter(|pair| !touching.contains_key(pair))
+            .copied()
+            .collect();
+        ended.sort();
+
+        for ((a, b), normal) in started {
+            let message = ChaosMessageBuilder::new()
+                .with_param("entity_a", *a)
+                .with_param("entity_b", *b)
+                .with_param("normal", *normal)
+                .build_for_event(TriggerEventKey::new(&CollisionEvent::Started));
+            if let Err(error) = world.try_send_message(message) {
+                log::debug!("Collision start was not delivered: {}", error);
+            }
+        }
+        for (a, b) in ended {
+            let message = ChaosMessageBuilder::new()
+                .with_param("entity_a", a)
+                .with_param("entity_b", b)
+                .build_for_event(TriggerEventKey::new(&CollisionEvent::Ended));
+            if let Err(error) = world.try_send_message(message) {
+                log::debug!("Collision end was not delivered: {}", error);
+            }
+        }
+        self.touching = touching.into_keys().collect();
+    }
+}
+
+fn contact_constraint(
+    bodies: &[Body],
+    a: usize,
+    b: usize,
+    normal: Vec2,
+    point: Vec2,
+) -> ContactConstraint {
+    let (body_a, body_b) = (&bodies[a], &bodies[b]);
+    let offset_a = point - body_a.body.position;
+    let offset_b = point - body_b.body.position;
+    let tangent = normal.perpendicular();
+    let effective_mass = |direction: Vec2| {
+        let k = body_a.inverse_mass
+            + body_b.inverse_mass
+            + body_a.inverse_inertia * Vec2::cross(&offset_a, &direction).powi(2)
+            + body_b.inverse_inertia * Vec2::cross(&offset_b, &direction).powi(2);
+        if k > 0.0 { 1.0 / k } else { 0.0 }
+    };
+
+    let relative_velocity = relative_velocity(body_a, body_b, offset_a, offset_b);
+    let approach = Vec2::dot(&relative_velocity, &normal);
+    let restitution = body_a.body.restitution.max(body_b.body.restitution);
+    ContactConstraint {
+        a,
+        b,
+        normal,
+        offset_a,
+        offset_b,
+        normal_mass: effective_mass(normal),
+        tangent_mass: effective_mass(tangent),
+        bounce: if approach < -RESTITUTION_THRESHOLD {
+            -restitution * approach
+        } else {
+            0.0
+        },
+        friction: (body_a.body.friction * body_b.body.friction).sqrt(),
+        normal_impulse: 0.0,
+        tangent_impulse: 0.0,
+    }
+}
+
+fn relative_velocity(a: &Body, b: &Body, offset_a: Vec2, offset_b: Vec2) -> Vec2 {
+    (b.body.linear_velocity + spin_velocity(b.body.angular_velocity, offset_b))
+        - (a.body.linear_velocity + spin_velocity(a.body.angular_velocity, offset_a))
+}
+
+fn apply_impulse(bodies: &mut [Body], constraint: &ContactConstraint, impulse: Vec2) {
+    let a = &mut bodies[constraint.a];
+    a.body.linear_velocity -= impulse * a.inverse_mass;
+    a.body.angular_velocity -= torque(constraint.offset_a, impulse) * a.inverse_inertia;
+    let b = &mut bodies[constraint.b];
+    b.body.linear_velocity += impulse * b.inverse_mass;
+    b.body.angular_velocity += torque(constraint.offset_b, impulse) * b.inverse_inertia;
+}
+
+// One sequential impulse iteration; the accumulated impulses are clamped so contacts only
+// push, and friction stays within the friction cone.
+fn solve(bodies: &mut [Body], constraint: &mut ContactConstraint) {
+    let tangent = constraint.normal.perpendicular();
+    let velocity = relative_velocity(
+        &bodies[constraint.a],
+        &bodies[constraint.b],
+        constraint.offset_a,
+        constraint.offset_b,
+    );
+    let tangent_delta = -Vec2::dot(&velocity, &tangent) * constraint.tangent_mass;
+    let max_friction = constraint.friction * constraint.normal_impulse;
+    let tangent_impulse =
+        (constraint.tangent_impulse + tangent_delta).clamp(-max_friction, max_friction);
+    let tangent_delta = tangent_impulse - constraint.tangent_impulse;
+    constraint.tangent_impulse = tangent_impulse;
+    apply_impulse(bodies, constraint, tangent * tangent_delta);
+
+    let velocity = relative_velocity(
+        &bodies[constraint.a],
+        &bodies[constraint.b],
+        constraint.offset_a,
+        constraint.offset_b,
+    );
+    let normal_delta =
+        (constraint.bounce - Vec2::dot(&velocity, &constraint.normal)) * constraint.normal_mass;
+    let normal_impulse = (constraint.normal_impulse + normal_delta).max(0.0);
+    let normal_delta = normal_impulse - constraint.normal_impulse;
+    constraint.normal_impulse = normal_impulse;
+    apply_impulse(bodies, constraint, constraint.normal * normal_delta);
+}
+
+impl Default for PhysicsSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChaosSystem for PhysicsSystem {
+    fn initialize(&mut self, _world: &mut ChaosWorld) -> Result<(), &'static str> {
+        Ok(())
+    }
+
+    fn update(&mut self, world: &mut ChaosWorld) -> Result<(), &'static str> {
+        let delta_time = world.get_time().delta_time();
+        self.advance(world, delta_time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body(world: &ChaosWorld, entity: EntityID) -> &RigidBody2D {
+        world.get_component::<RigidBody2D>(entity).unwrap()
+    }
+
+    fn run(system: &mut PhysicsSystem, world: &mut ChaosWorld, seconds: f32) {
+        let steps = (seconds / system.time_step).round() as u32;
+        for _ in 0..steps {
+            system.advance(world, system.time_step).unwrap();
+        }
+    }
+
+    #[test]
+    fn bodies_come_to_rest_on_static_ground() {
+        let mut world = ChaosWorld::new();
+        let mut system = PhysicsSystem::new().with_gravity(Vec2::new(0.0, -10.0));
+        world
+            .spawn()
+            .with(RigidBody2D::fixed())
+            .with(Collider2D::rectangle(Vec2::new(10.0, 1.0)))
+            .build();
+        let ball = world
+            .spawn()
+            .with(RigidBody2D::dynamic(1.0).with_position(Vec2::new(0.0, 4.0)))
+            .with(Collider2D::circle(0.5))
+            .build();
+        let crate_box = world
+            .spawn()
+            .with(RigidBody2D::dynamic(2.0).with_position(Vec2::new(3.0, 3.0)))
+            .with(Collider2D::rectangle(Vec2::new(0.5, 0.5)))
+            .build();
+
+        run(&mut system, &mut world, 3.0);
+
+        for (entity, height) in [(ball, 1.5), (crate_box, 1.5)] {
+            let body = body(&world, entity);
+            assert!(
+                (body.position.y - height).abs() < 0.05,
+                "{entity} rests at {:?}",
+                body.position
+            );
+            assert!(body.linear_velocity.length() < 0.05);
+        }
+    }
+
+    #[test]
+    fn penetration_is_corrected_once_per_manifold() {
+        let mut world = ChaosWorld::new();
+        let mut system = PhysicsSystem::new().with_gravity(Vec2::zero());
+        world
+            .spawn()
+            .with(RigidBody2D::fixed())
+            .with(Collider2D::rectangle(Vec2::new(10.0, 1.0)))
+            .build();
+        // Sinks 0.2 into the ground, touching it at both bottom corners.
+        let crate_box = world
+            .spawn()
+            .with(RigidBody2D::dynamic(1.0).with_position(Vec2::new(0.0, 1.3)))
+            .with(Collider2D::rectangle(Vec2::new(0.5, 0.5)))
+            .build();
+
+        system.advance(&mut world, system.time_step).unwrap();
+
+        let expected = 1.3 + (0.2 - PENETRATION_SLOP) * PENETRATION_CORRECTION;
+        let position = body(&world, crate_box).position;
+        assert!((position.y - expected).abs() < 1e-4, "{position:?}");
+    }
+
+    #[test]
+    fn impulses_use_the_collider_inertia() {
+        let mut world = ChaosWorld::new();
+        let mut system = PhysicsSystem::new().with_gravity(Vec2::zero());
+        let wheel = world
+            .spawn()
+            .with(RigidBody2D::dynamic(1.0))
+            .with(Collider2D::circle(2.0))
+            .build();
+        world
+            .get_component_mut::<RigidBody2D>(wheel)
+            .unwrap()
+            .apply_impulse(Vec2::new(0.0, 3.0), Vec2::new(1.0, 0.0));
+
+        system.advance(&mut world, system.time_step).unwrap();
+        // The circle's inertia is 2; pushing up on its right side turns it counter-clockwise.
+        let wheel = body(&world, wheel);
+        assert_eq!(wheel.linear_velocity, Vec2::new(0.0, 3.0));
+        assert!((wheel.angular_velocity + 1.5).abs() < 1e-5);
+        assert_eq!(wheel.impulse, Vec2::zero());
+    }
+
+    #[test]
+    fn elastic_head_on_collision_swaps_velocities() {
+        let mut world = ChaosWorld::new();
+        let mut system = PhysicsSystem::new();
+        let ball = |x: f32, velocity: f32| {
+            RigidBody2D::dynamic(1.0)
+                .with_position(Vec2::new(x, 0.0))
+                .with_velocity(Vec2::new(velocity, 0.0))
+                .with_restitution(1.0)
+        };
+        let left = world
+            .spawn()
+            .with(ball(-2.0, 4.0))
+            .with(Collider2D::circle(0.5))
+            .build();
+        let right = world
+            .spawn()
+            .with(ball(2.0, 0.0))
+            .with(Collider2D::circle(0.5))
+            .build();
+
+        run(&mut system, &mut world, 1.0);
+
+        assert!(body(&world, left).linear_velocity.x.abs() < 0.05);
+        assert!((body(&world, right).linear_velocity.x - 4.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn collision_events_are_sent_when_contact_starts_and_ends() {
+        let mut world = ChaosWorld::new();
+        let mut system = PhysicsSystem::new();
+        let mut started = world.register_for_trigger(CollisionEvent::Started);
+        let mut ended = world.register_for_trigger(CollisionEvent::Ended);
+        let mover = world
+            .spawn()
+            .with(RigidBody2D::kinematic().with_velocity(Vec2::new(2.0, 0.0)))
+            .with(Collider2D::circle(0.5))
+            .build();
+        let sensor = world
+            .spawn()
+            .with(RigidBody2D::dynamic(1.0).with_position(Vec2::new(2.0, 0.0)))
+            .with(Collider2D::circle(0.5).as_sensor())
+            .build();
+
+        run(&mut system, &mut world, 0.25);
+        assert!(started.receive().is_none());
+
+        run(&mut system, &mut world, 0.5);
+        let message = started.receive().unwrap();
+        assert_eq!(message.get::<EntityID>("entity_a"), Some(mover));
+        assert_eq!(message.get::<EntityID>("entity_b"), Some(sensor));
+        assert_eq!(message.get::<Vec2>("normal"), Some(Vec2::x_axis()));
+        assert!(started.receive().is_none());
+        // Sensors don't push.
+        assert_eq!(body(&world, sensor).position, Vec2::new(2.0, 0.0));
+
+        run(&mut system, &mut world, 1.0);
+        let message = ended.receive().unwrap();
+        assert_eq!(message.get::<EntityID>("entity_b"), Some(sensor));
+    }
+
+    #[test]
+    fn time_is_simulated_in_fixed_steps() {
+        let mut world = ChaosWorld::new();
+        let mut system = PhysicsSystem::new().with_time_step(0.1, 2);
+        let mover = world
+            .spawn()
+            .with(RigidBody2D::dynamic(1.0).with_velocity(Vec2::new(1.0, 0.0)))
+            .build();
+
+        system.advance(&mut world, 0.05).unwrap();
+        assert_eq!(body(&world, mover).position.x, 0.0);
+        system.advance(&mut world, 0.05).unwrap();
+        assert!((body(&world, mover).position.x - 0.1).abs() < 1e-6);
+        // A long frame runs at most two steps.
+        system.advance(&mut world, 1.0).unwrap();
+        assert!((body(&world, mover).position.x - 0.3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn bodies_move_their_transforms() {
+        let mut world = ChaosWorld::new();
+        let mut system = PhysicsSystem::new();
+        let ship = world
+            .spawn()
+            .with(
+                RigidBody2D::dynamic(1.0)
+                    .with_position(Vec2::new(1.0, 2.0))
+                    .with_rotation(0.5),
+            )
+            .with(Transform::default())
+            .build();
+
+        system.advance(&mut world, system.time_step).unwrap();
+        let transform = world.get_component::<Transform>(ship).unwrap();
+        assert_eq!(transform.translation, Vec2::new(1.0, 2.0));
+        assert_eq!(transform.rotation, 0.5);
+    }
+}
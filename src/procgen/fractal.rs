@@ -0,0 +1,167 @@
+use crate::procgen::noise::Noise;
+
+// Shifts octaves and warp axes apart, so they don't all share the lattice point at the
+// origin.
+const OFFSET: f32 = 17.31;
+
+/// Fractal Brownian motion: octaves of a noise summed at rising frequencies and falling
+/// amplitudes, for terrain, clouds and rough outlines. The sum is divided by the total
+/// amplitude, so it stays in the range of the noise.
+///
+/// ```rust
+/// use chaos_engine::{math::Vec2, procgen::{fractal::Fbm, noise::{Noise, Perlin}}};
+///
+/// let terrain = Fbm::new(Perlin::new(3)).with_octaves(5).with_frequency(0.01);
+/// let height = terrain.sample2(Vec2::new(120.0, 48.0));
+/// assert!((-1.0..=1.0).contains(&height));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fbm<N: Noise> {
+    pub noise: N,
+    pub octaves: u32,
+    pub frequency: f32,
+    // Frequency multiplier from one octave to the next.
+    pub lacunarity: f32,
+    // Amplitude multiplier from one octave to the next.
+    pub gain: f32,
+}
+
+impl<N: Noise> Fbm<N> {
+    /// Four octaves, each at twice the frequency and half the amplitude of the last.
+    pub fn new(noise: N) -> Self {
+        Self {
+            noise,
+            octaves: 4,
+            frequency: 1.0,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+
+    pub fn with_octaves(mut self, octaves: u32) -> Self {
+        self.octaves = octaves;
+        self
+    }
+
+    /// The frequency of the first octave.
+    pub fn with_frequency(mut self, frequency: f32) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    pub fn with_lacunarity(mut self, lacunarity: f32) -> Self {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    pub fn with_gain(mut self, gain: f32) -> Self {
+        self.gain = gain;
+        self
+    }
+}
+
+impl<N: Noise> Noise for Fbm<N> {
+    fn sample<const D: usize>(&self, point: [f32; D]) -> f32 {
+        let mut value = 0.0;
+        let mut total_amplitude = 0.0;
+        let mut frequency = self.frequency;
+        let mut amplitude = 1.0;
+        for octave in 0..self.octaves {
+            let shift = octave as f32 * OFFSET;
+            value += amplitude
+                * self
+                    .noise
+                    .sample(point.map(|coordinate| coordinate * frequency + shift));
+            total_amplitude += amplitude;
+            frequency *= self.lacunarity;
+            amplitude *= self.gain;
+        }
+        if total_amplitude == 0.0 {
+            0.0
+        } else {
+            value / total_amplitude
+        }
+    }
+}
+
+/// Moves every point by another noise before sampling, which bends straight features
+/// into swirls, e.g. for marble or rivers.
+///
+/// ```rust
+/// use chaos_engine::{
+///     math::Vec2,
+///     procgen::{fractal::{DomainWarp, Fbm}, noise::{Noise, Simplex}},
+/// };
+///
+/// let marble = DomainWarp::new(Simplex::new(1), Fbm::new(Simplex::new(2)), 4.0);
+/// let value = marble.sample2(Vec2::new(0.3, 0.7));
+/// assert_eq!(value, marble.sample2(Vec2::new(0.3, 0.7)));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DomainWarp<N: Noise, W: Noise> {
+    pub noise: N,
+    pub warp: W,
+    // How far, in the noise's units, a point moves at most along each axis.
+    pub strength: f32,
+}
+
+impl<N: Noise, W: Noise> DomainWarp<N, W> {
+    pub fn new(noise: N, warp: W, strength: f32) -> Self {
+        Self {
+            noise,
+            warp,
+            strength,
+        }
+    }
+}
+
+impl<N: Noise, W: Noise> Noise for DomainWarp<N, W> {
+    fn sample<const D: usize>(&self, point: [f32; D]) -> f32 {
+        // Every axis moves by its own, unrelated slice of the warp noise.
+        let warped: [f32; D] = std::array::from_fn(|axis| {
+            let shift = (axis + 1) as f32 * OFFSET;
+            let displacement = self.warp.sample(point.map(|coordinate| coordinate + shift));
+            point[axis] + displacement * self.strength
+        });
+        self.noise.sample(warped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        math::Vec2,
+        procgen::noise::{Perlin, Simplex},
+    };
+
+    #[test]
+    fn fbm_adds_detail_within_range() {
+        let base = Perlin::new(8);
+        let single = Fbm::new(base).with_octaves(1).with_frequency(0.5);
+        let point = Vec2::new(3.7, 1.2);
+        assert_eq!(single.sample2(point), base.sample2(point * 0.5));
+
+        let detailed = single.with_octaves(6).with_gain(0.6);
+        let mut differs = false;
+        for i in 0..500 {
+            let point = Vec2::new(i as f32 * 0.31, i as f32 * -0.17);
+            let value = detailed.sample2(point);
+            assert!((-1.0..=1.0).contains(&value));
+            differs |= value != single.sample2(point);
+        }
+        assert!(differs);
+        assert_eq!(Fbm::new(base).with_octaves(0).sample1(1.5), 0.0);
+    }
+
+    #[test]
+    fn domain_warp_moves_points() {
+        let noise = Simplex::new(4);
+        let unwarped = DomainWarp::new(noise, Perlin::new(5), 0.0);
+        let warped = DomainWarp::new(noise, Perlin::new(5), 3.0);
+        let point = Vec2::new(2.3, -4.1);
+
+        assert_eq!(unwarped.sample2(point), noise.sample2(point));
+        assert_ne!(warped.sample2(point), noise.sample2(point));
+    }
+}
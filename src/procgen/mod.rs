@@ -0,0 +1,4 @@
+pub mod fractal;
+pub mod noise;
+pub mod polygon;
+pub mod sampling;
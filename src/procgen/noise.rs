@@ -0,0 +1,339 @@
+use std::f32::consts::FRAC_1_SQRT_2;
+
+use crate::{
+    math::{Vec2, Vec3, Vec4},
+    random::rng::ChaosRng,
+};
+
+/// Coherent noise: nearby points get similar values. The same seed always gives the same
+/// values, on every platform.
+///
+/// [`Perlin`] and [`Simplex`] return values in `-1.0..=1.0`; [`Worley`] returns the
+/// distance to the nearest feature point, mostly in `0.0..=1.0`.
+pub trait Noise {
+    /// The noise at a point with `D` dimensions; the sample methods cover one to four.
+    fn sample<const D: usize>(&self, point: [f32; D]) -> f32;
+
+    fn sample1(&self, x: f32) -> f32 {
+        self.sample([x])
+    }
+
+    fn sample2(&self, point: Vec2) -> f32 {
+        self.sample([point.x, point.y])
+    }
+
+    fn sample3(&self, point: Vec3) -> f32 {
+        self.sample([point.x, point.y, point.z])
+    }
+
+    fn sample4(&self, point: Vec4) -> f32 {
+        self.sample([point.x, point.y, point.z, point.w])
+    }
+}
+
+// Spreads the seed so that neighbouring seeds give unrelated noise.
+fn mix_seed(seed: u64) -> u32 {
+    ChaosRng::new(seed).next_u32()
+}
+
+fn mix(value: u32) -> u32 {
+    let mut value = value.wrapping_mul(0x9E37_79B1);
+    value ^= value >> 15;
+    value = value.wrapping_mul(0x85EB_CA77);
+    value ^ (value >> 13)
+}
+
+// A hash of a lattice point, for picking its gradient or feature point.
+fn hash<const D: usize>(seed: u32, cell: [i32; D]) -> u32 {
+    cell.iter()
+        .fold(seed, |hash, coordinate| mix(hash ^ *coordinate as u32))
+}
+
+// Eight unit directions around the circle, spelled out so that no platform's trig goes
+// into the noise.
+const GRADIENTS_2D: [[f32; 2]; 8] = [
+    [1.0, 0.0],
+    [FRAC_1_SQRT_2, FRAC_1_SQRT_2],
+    [0.0, 1.0],
+    [-FRAC_1_SQRT_2, FRAC_1_SQRT_2],
+    [-1.0, 0.0],
+    [-FRAC_1_SQRT_2, -FRAC_1_SQRT_2],
+    [0.0, -1.0],
+    [FRAC_1_SQRT_2, -FRAC_1_SQRT_2],
+];
+
+// Gradients for the lattice points: eight directions in 2D, and the edge midpoints of the
+// hypercube (one zero component) from 3D on, as in improved Perlin noise.
+fn gradient<const D: usize>(hash: u32) -> [f32; D] {
+    let mut gradient = [0.0; D];
+    match D {
+        1 => gradient[0] = (1 + (hash >> 1) % 4) as f32 / 4.0 * sign(hash, 0),
+        2 => gradient[..2].copy_from_slice(&GRADIENTS_2D[hash as usize % 8]),
+        _ => {
+            let zero = (hash >> D) as usize % D;
+            for (axis, component) in gradient.iter_mut().enumerate() {
+                if axis != zero {
+                    *component = sign(hash, axis);
+                }
+            }
+        }
+    }
+    gradient
+}
+
+fn sign(hash: u32, bit: usize) -> f32 {
+    if hash >> bit & 1 == 0 { 1.0 } else { -1.0 }
+}
+
+fn dot<const D: usize>(one: &[f32; D], other: &[f32; D]) -> f32 {
+    one.iter().zip(other).map(|(a, b)| a * b).sum()
+}
+
+/// Classic gradient noise on a square lattice, with the quintic fade of improved Perlin
+/// noise. Cheap in low dimensions; prefer [`Simplex`] in 3D and 4D.
+///
+/// ```rust
+/// use chaos_engine::{math::Vec2, procgen::noise::{Noise, Perlin}};
+///
+/// let noise = Perlin::new(7);
+/// let height = noise.sample2(Vec2::new(3.2, -1.5));
+/// assert!((-1.0..=1.0).contains(&height));
+/// assert_eq!(height, Perlin::new(7).sample2(Vec2::new(3.2, -1.5)));
+/// // Zero on the lattice points.
+/// assert_eq!(noise.sample2(Vec2::new(3.0, -1.0)), 0.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Perlin {
+    seed: u32,
+}
+
+impl Perlin {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed: mix_seed(seed),
+        }
+    }
+}
+
+impl Noise for Perlin {
+    fn sample<const D: usize>(&self, point: [f32; D]) -> f32 {
+        let cell = point.map(|coordinate| coordinate.floor());
+        let offset: [f32; D] = std::array::from_fn(|axis| point[axis] - cell[axis]);
+        let fade = offset.map(|t| t * t * t * (t * (t * 6.0 - 15.0) + 10.0));
+
+        // Blends the corners' ramps with weights that multiply out to the n-linear
+        // interpolation.
+        let mut value = 0.0;
+        for corner in 0..1usize << D {
+            let mut weight = 1.0;
+            let mut corner_cell = [0; D];
+            let mut corner_offset = offset;
+            for axis in 0..D {
+                let upper = corner >> axis & 1 == 1;
+                corner_cell[axis] = cell[axis] as i32 + upper as i32;
+                if upper {
+                    corner_offset[axis] -= 1.0;
+                    weight *= fade[axis];
+                } else {
+                    weight *= 1.0 - fade[axis];
+                }
+            }
+            let gradient = gradient::<D>(hash(self.seed, corner_cell));
+            value += weight * dot(&gradient, &corner_offset);
+        }
+
+        // Scales the largest possible value to about one.
+        let scale = match D {
+            1 => 2.0,
+            2 => std::f32::consts::SQRT_2,
+            3 => 1.0,
+            _ => 0.9,
+        };
+        (value * scale).clamp(-1.0, 1.0)
+    }
+}
+
+/// Gradient noise on a lattice of simplices (triangles in 2D, tetrahedra in 3D): no square
+/// artifacts and `D + 1` instead of `2^D` corners per sample.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Simplex {
+    seed: u32,
+}
+
+impl Simplex {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed: mix_seed(seed),
+        }
+    }
+}
+
+impl Noise for Simplex {
+    fn sample<const D: usize>(&self, point: [f32; D]) -> f32 {
+        let dimensions = D as f32;
+        let skew = ((dimensions + 1.0).sqrt() - 1.0) / dimensions;
+        let unskew = (1.0 - 1.0 / (dimensions + 1.0).sqrt()) / dimensions;
+
+        // The cell of the skewed lattice, and the offset from its origin corner.
+        let skewed = point.iter().sum::<f32>() * skew;
+        let cell = point.map(|coordinate| (coordinate + skewed).floor() as i32);
+        let unskewed = cell.iter().sum::<i32>() as f32 * unskew;
+        let offset: [f32; D] =
+            std::array::from_fn(|axis| point[axis] - (cell[axis] as f32 - unskewed));
+
+        // The simplex's corners step along the axes in order of the largest offset.
+        let mut order: [usize; D] = std::array::from_fn(|axis| axis);
+        order.sort_by(|a, b| offset[*b].total_cmp(&offset[*a]));
+
+        let mut value = 0.0;
+        let mut corner_cell = cell;
+        let mut steps = [0.0; D];
+        for corner in 0..=D {
+            if corner > 0 {
+                corner_cell[order[corner - 1]] += 1;
+                steps[order[corner - 1]] = 1.0;
+            }
+            let corner_offset: [f32; D] =
+                std::array::from_fn(|axis| offset[axis] - steps[axis] + corner as f32 * unskew);
+            let falloff = 0.5 - dot(&corner_offset, &corner_offset);
+            if falloff > 0.0 {
+                let gradient = gradient::<D>(hash(self.seed, corner_cell));
+                let falloff = falloff * falloff;
+                value += falloff * falloff * dot(&gradient, &corner_offset);
+            }
+        }
+
+        let scale = match D {
+            1 => 70.0,
+            2 => 98.0,
+            3 => 76.0,
+            _ => 62.0,
+        };
+        (value * scale).clamp(-1.0, 1.0)
+    }
+}
+
+/// Cellular noise: the distance from the point to the nearest of a set of feature points,
+/// one in every lattice cell. Gives cell, crack and scale patterns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Worley {
+    seed: u32,
+}
+
+impl Worley {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed: mix_seed(seed),
+        }
+    }
+
+    fn feature_point<const D: usize>(&self, cell: [i32; D]) -> [f32; D] {
+        let mut hash = hash(self.seed, cell);
+        std::array::from_fn(|axis| {
+            hash = mix(hash);
+            cell[axis] as f32 + (hash >> 8) as f32 / (1u32 << 24) as f32
+        })
+    }
+}
+
+impl Noise for Worley {
+    fn sample<const D: usize>(&self, point: [f32; D]) -> f32 {
+        let cell = point.map(|coordinate| coordinate.floor() as i32);
+
+        let mut nearest = f32::MAX;
+        for neighbour in 0..3usize.pow(D as u32) {
+            let mut neighbour_cell = cell;
+            let mut rest = neighbour;
+            for coordinate in neighbour_cell.iter_mut() {
+                *coordinate += (rest % 3) as i32 - 1;
+                rest /= 3;
+            }
+            let feature = self.feature_point(neighbour_cell);
+            let distance: f32 = point
+                .iter()
+                .zip(&feature)
+                .map(|(a, b)| (a - b) * (a - b))
+                .sum();
+            nearest = nearest.min(distance);
+        }
+        nearest.sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples() -> impl Iterator<Item = [f32; 4]> {
+        (0..4000).map(|i| {
+            let i = i as f32;
+            [
+                i * 0.137,
+                i * -0.071 + 3.3,
+                (i * 0.29).sin() * 40.0,
+                i * 0.011,
+            ]
+        })
+    }
+
+    fn check_range<N: Noise>(noise: &N, range: std::ops::RangeInclusive<f32>) {
+        for [x, y, z, w] in samples() {
+            for value in [
+                noise.sample([x]),
+                noise.sample([x, y]),
+                noise.sample([x, y, z]),
+                noise.sample([x, y, z, w]),
+            ] {
+                assert!(range.contains(&value), "{value} at {x} {y} {z} {w}");
+            }
+        }
+    }
+
+    #[test]
+    fn noise_is_deterministic_per_seed() {
+        let point = Vec3::new(1.3, -7.7, 2.1);
+        assert_eq!(Perlin::new(1).sample3(point), Perlin::new(1).sample3(point));
+        assert_ne!(Perlin::new(1).sample3(point), Perlin::new(2).sample3(point));
+        assert_eq!(
+            Simplex::new(5).sample3(point),
+            Simplex::new(5).sample3(point)
+        );
+        assert_ne!(
+            Simplex::new(5).sample3(point),
+            Simplex::new(6).sample3(point)
+        );
+        assert_eq!(Worley::new(9).sample3(point), Worley::new(9).sample3(point));
+        assert_ne!(
+            Worley::new(9).sample3(point),
+            Worley::new(10).sample3(point)
+        );
+    }
+
+    #[test]
+    fn noise_stays_in_range_and_varies() {
+        check_range(&Perlin::new(3), -1.0..=1.0);
+        check_range(&Simplex::new(3), -1.0..=1.0);
+        check_range(&Worley::new(3), 0.0..=2.0);
+
+        // The scales bring the output close to the full range.
+        let spread = |noise: &dyn Fn(Vec2) -> f32| {
+            samples().fold(0.0f32, |max, [x, y, ..]| {
+                max.max(noise(Vec2::new(x, y)).abs())
+            })
+        };
+        assert!(spread(&|point| Perlin::new(4).sample2(point)) > 0.6);
+        assert!(spread(&|point| Simplex::new(4).sample2(point)) > 0.6);
+    }
+
+    #[test]
+    fn noise_is_continuous() {
+        let noise = Simplex::new(12);
+        for [x, y, z, _] in samples().take(500) {
+            let point = Vec3::new(x, y, z);
+            let nudged = point + Vec3::splat(1e-3);
+            assert!((noise.sample3(point) - noise.sample3(nudged)).abs() < 0.05);
+        }
+        let worley = Worley::new(12);
+        assert!(worley.sample2(Vec2::new(0.5, 0.5)) < 2.0f32.sqrt());
+    }
+}
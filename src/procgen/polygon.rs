@@ -0,0 +1,129 @@
+use std::f32::consts::TAU;
+
+use crate::{
+    math::{Vec2, shape::polygon2d::Polygon2D},
+    procgen::noise::{Noise, Perlin},
+    random::rng::ChaosRng,
+};
+
+/// Generates star-shaped polygons around the origin, e.g. rocks, islands and debris:
+/// points at increasing angles, so the outline never crosses itself, and at varying
+/// distances from the center.
+///
+/// The same `rng` state gives the same polygon on one platform; the directions come from
+/// `cos` and `sin`, whose last bits may differ between platforms.
+///
+/// ```rust
+/// use chaos_engine::{procgen::polygon::RandomPolygon, random::rng::ChaosRng};
+///
+/// let rock = RandomPolygon::new(12, 2.0)
+///     .with_irregularity(0.4)
+///     .with_spikiness(0.3)
+///     .generate(&mut ChaosRng::new(5));
+/// assert_eq!(rock.points.len(), 12);
+/// assert!(rock.is_ccw());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RandomPolygon {
+    pub vertices: usize,
+    pub radius: f32,
+    // 0 spaces the points evenly around the center, 1 lets the gaps vary a lot.
+    pub irregularity: f32,
+    // 0 keeps every point at `radius`, 1 lets the distance vary between 0 and twice that.
+    pub spikiness: f32,
+    // Scales the radius by a noise running around the outline, in 0..=1.
+    pub roughness: f32,
+}
+
+impl RandomPolygon {
+    pub fn new(vertices: usize, radius: f32) -> Self {
+        Self {
+            vertices,
+            radius,
+            irregularity: 0.0,
+            spikiness: 0.0,
+            roughness: 0.0,
+        }
+    }
+
+    pub fn with_irregularity(mut self, irregularity: f32) -> Self {
+        self.irregularity = irregularity.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_spikiness(mut self, spikiness: f32) -> Self {
+        self.spikiness = spikiness.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Unlike spikiness, roughness changes smoothly from one point to the next, which
+    /// gives bays and bulges instead of spikes.
+    pub fn with_roughness(mut self, roughness: f32) -> Self {
+        self.roughness = roughness.clamp(0.0, 1.0);
+        self
+    }
+
+    /// A counter-clockwise polygon; empty with fewer than three vertices.
+    pub fn generate(&self, rng: &mut ChaosRng) -> Polygon2D {
+        if self.vertices < 3 {
+            return Polygon2D::default();
+        }
+
+        // Random gaps between the angles, scaled to add up to a full turn.
+        let step = TAU / self.vertices as f32;
+        let gaps: Vec<f32> = (0..self.vertices)
+            .map(|_| step * (1.0 + self.irregularity * rng.range(-0.9..0.9)))
+            .collect();
+        let turn = TAU / gaps.iter().sum::<f32>();
+
+        let noise = Perlin::new(rng.next_u64());
+        let mut angle = rng.range(0.0..TAU);
+        let points = gaps
+            .iter()
+            .map(|gap| {
+                let direction = Vec2::new(angle.cos(), angle.sin());
+                let spike = 1.0 + self.spikiness * rng.range(-1.0..1.0);
+                let bulge = 1.0 + self.roughness * noise.sample2(direction * 1.5);
+                angle += gap * turn;
+                direction * (self.radius * spike * bulge).max(self.radius * 0.05)
+            })
+            .collect();
+        Polygon2D::new(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polygons_are_simple_and_reproducible() {
+        let generator = RandomPolygon::new(24, 3.0)
+            .with_irregularity(1.0)
+            .with_spikiness(1.0)
+            .with_roughness(0.5);
+        for seed in 0..20 {
+            let polygon = generator.generate(&mut ChaosRng::new(seed));
+            assert_eq!(polygon.points.len(), 24);
+            assert!(polygon.is_ccw());
+            // A simple polygon triangulates into n - 2 triangles.
+            assert_eq!(polygon.triangulate().len(), 22);
+            assert!(polygon.points.iter().all(|point| point.length() <= 9.0));
+            assert_eq!(polygon, generator.generate(&mut ChaosRng::new(seed)));
+        }
+
+        let circle = RandomPolygon::new(8, 2.0).generate(&mut ChaosRng::new(1));
+        assert!(
+            circle
+                .points
+                .iter()
+                .all(|point| (point.length() - 2.0).abs() < 1e-5)
+        );
+        assert!(
+            RandomPolygon::new(2, 1.0)
+                .generate(&mut ChaosRng::new(1))
+                .points
+                .is_empty()
+        );
+    }
+}
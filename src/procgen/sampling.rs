@@ -0,0 +1,129 @@
+use crate::{math::Vec2, random::rng::ChaosRng};
+
+// Candidates tried around a point before it stops spawning new ones.
+const ATTEMPTS: usize = 30;
+
+/// Points spread over the rectangle from `min` to `max`, none closer than `radius` to
+/// another, and no gap much wider than that: an even but natural looking scatter for
+/// trees, stars or spawn points. Bridson's algorithm; the same `rng` state gives the same
+/// points.
+///
+/// Returns no points for a radius that isn't positive and finite, bounds that aren't
+/// finite, or when the radius is so small for the area that the lookup grid can't be
+/// allocated.
+///
+/// ```rust
+/// use chaos_engine::{math::Vec2, procgen::sampling::poisson_disc, random::rng::ChaosRng};
+///
+/// let stars = poisson_disc(&mut ChaosRng::new(3), Vec2::zero(), Vec2::new(100.0, 50.0), 5.0);
+/// assert!(stars.len() > 50);
+/// ```
+pub fn poisson_disc(rng: &mut ChaosRng, min: Vec2, max: Vec2, radius: f32) -> Vec<Vec2> {
+    let size = max - min;
+    let finite = radius.is_finite() && size.x.is_finite() && size.y.is_finite();
+    if !finite || radius <= 0.0 || size.x < 0.0 || size.y < 0.0 {
+        return Vec::new();
+    }
+
+    // Cells small enough to hold at most one point each.
+    let cell_size = radius / std::f32::consts::SQRT_2;
+    let columns = (size.x / cell_size).ceil().max(1.0) as usize;
+    let rows = (size.y / cell_size).ceil().max(1.0) as usize;
+    let mut grid: Vec<Option<usize>> = Vec::new();
+    let Some(cells) = columns.checked_mul(rows) else {
+        return Vec::new();
+    };
+    if grid.try_reserve_exact(cells).is_err() {
+        return Vec::new();
+    }
+    grid.resize(cells, None);
+    let cell_of = |point: Vec2| {
+        let cell = (point - min) / cell_size;
+        (
+            (cell.x as usize).min(columns - 1),
+            (cell.y as usize).min(rows - 1),
+        )
+    };
+
+    let first = rng.in_rect(min, max);
+    let (column, row) = cell_of(first);
+    grid[row * columns + column] = Some(0);
+    let mut points = vec![first];
+    let mut active = vec![0];
+
+    while !active.is_empty() {
+        let active_index = rng.range(0..active.len());
+        let center = points[active[active_index]];
+
+        let candidate = (0..ATTEMPTS).find_map(|_| {
+            let candidate = center + rng.unit_vec2() * rng.range(radius..2.0 * radius);
+            let outside = candidate.x < min.x
+                || candidate.y < min.y
+                || candidate.x > max.x
+                || candidate.y > max.y;
+            if outside {
+                return None;
+            }
+            let (column, row) = cell_of(candidate);
+            let too_close = (row.saturating_sub(2)..(row + 3).min(rows)).any(|row| {
+                (column.saturating_sub(2)..(column + 3).min(columns)).any(|column| {
+                    grid[row * columns + column].is_some_and(|other| {
+                        Vec2::distance_squared(&points[other], &candidate) < radius * radius
+                    })
+                })
+            });
+            (!too_close).then_some(candidate)
+        });
+
+        match candidate {
+            Some(candidate) => {
+                let (column, row) = cell_of(candidate);
+                grid[row * columns + column] = Some(points.len());
+                active.push(points.len());
+                points.push(candidate);
+            }
+            None => {
+                active.swap_remove(active_index);
+            }
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_keep_their_distance_and_fill_the_area() {
+        let (min, max) = (Vec2::new(-10.0, 5.0), Vec2::new(30.0, 25.0));
+        let points = poisson_disc(&mut ChaosRng::new(21), min, max, 2.0);
+
+        for (i, a) in points.iter().enumerate() {
+            assert!(a.x >= min.x && a.y >= min.y && a.x <= max.x && a.y <= max.y);
+            for b in &points[i + 1..] {
+                assert!(Vec2::distance(a, b) >= 2.0);
+            }
+        }
+        // Every spot of the area is within two radii of a point.
+        for x in 0..40 {
+            for y in 0..20 {
+                let spot = min + Vec2::new(x as f32, y as f32);
+                assert!(
+                    points
+                        .iter()
+                        .any(|point| Vec2::distance(point, &spot) < 4.0)
+                );
+            }
+        }
+
+        assert_eq!(points, poisson_disc(&mut ChaosRng::new(21), min, max, 2.0));
+        assert!(poisson_disc(&mut ChaosRng::new(21), max, min, 2.0).is_empty());
+        assert!(poisson_disc(&mut ChaosRng::new(21), min, max, f32::NAN).is_empty());
+        assert!(poisson_disc(&mut ChaosRng::new(21), min, max, f32::INFINITY).is_empty());
+        let infinite = Vec2::new(f32::INFINITY, 25.0);
+        assert!(poisson_disc(&mut ChaosRng::new(21), min, infinite, 2.0).is_empty());
+        let huge = Vec2::splat(1e6);
+        assert!(poisson_disc(&mut ChaosRng::new(21), Vec2::zero(), huge, 1e-4).is_empty());
+    }
+}
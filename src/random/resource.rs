@@ -0,0 +1,158 @@
+use std::{
+    collections::HashMap,
+    hash::{Hash, Hasher},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{ecs::EntityID, random::rng::ChaosRng};
+
+/// World resource handing out random number streams derived from one master seed.
+///
+/// Every stream is identified by a key and only advances when it is used, so the numbers a
+/// system or entity gets don't depend on the order in which others draw theirs. Recording
+/// the seed is enough to replay a session or keep lockstep peers in sync.
+///
+/// ```rust
+/// use chaos_engine::{ecs::world::ChaosWorld, random::resource::ChaosRandom};
+///
+/// let mut world = ChaosWorld::new();
+/// world.insert_resource(ChaosRandom::new(1234));
+///
+/// let random = world.get_resource_mut::<ChaosRandom>().unwrap();
+/// let spawn_count = random.stream("asteroids").range(5..10);
+/// ```
+pub struct ChaosRandom {
+    seed: u64,
+    streams: HashMap<u64, ChaosRng>,
+}
+
+impl ChaosRandom {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            streams: HashMap::new(),
+        }
+    }
+
+    /// Seeded from the clock, for sessions that don't need to be reproduced. The seed can
+    /// still be read with [`ChaosRandom::seed`] and recorded.
+    pub fn from_time() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_nanos() as u64);
+        Self::new(nanos)
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Starts over with a new master seed, dropping all streams.
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.streams.clear();
+    }
+
+    /// The stream for `key`, created on first use.
+    pub fn stream<K: Hash>(&mut self, key: K) -> &mut ChaosRng {
+        self.stream_for(stream_key(&key))
+    }
+
+    /// The stream of the system called `name`. The name is given by hand rather than taken
+    /// from the type, because `type_name` may change between compiler versions.
+    pub fn system_stream(&mut self, name: &str) -> &mut ChaosRng {
+        self.stream(("system", name))
+    }
+
+    /// The stream of `entity`. Remove it with [`ChaosRandom::remove_entity_stream`] when the
+    /// entity is despawned.
+    pub fn entity_stream(&mut self, entity: EntityID) -> &mut ChaosRng {
+        self.stream(("entity", entity))
+    }
+
+    pub fn remove_stream<K: Hash>(&mut self, key: K) -> bool {
+        self.streams.remove(&stream_key(&key)).is_some()
+    }
+
+    pub fn remove_entity_stream(&mut self, entity: EntityID) -> bool {
+        self.remove_stream(("entity", entity))
+    }
+
+    /// A generator derived from the master seed and `key` that isn't kept by the resource,
+    /// e.g. to generate the same asteroid shape again.
+    pub fn fork<K: Hash>(&self, key: K) -> ChaosRng {
+        ChaosRng::new(self.seed ^ stream_key(&key))
+    }
+
+    fn stream_for(&mut self, key: u64) -> &mut ChaosRng {
+        let seed = self.seed;
+        self.streams
+            .entry(key)
+            .or_insert_with(|| ChaosRng::new(seed ^ key))
+    }
+}
+
+// Stream keys must be the same in every run and on every machine, which `DefaultHasher`
+// doesn't promise across Rust versions; FNV-1a does.
+fn stream_key<K: Hash>(key: &K) -> u64 {
+    let mut hasher = StreamHasher(0xCBF2_9CE4_8422_2325);
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+struct StreamHasher(u64);
+
+impl Hasher for StreamHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01B3);
+        }
+    }
+
+    // Lengths and `usize` values are hashed as 8 bytes, so keys match between 32 and 64 bit
+    // platforms.
+    fn write_usize(&mut self, value: usize) {
+        self.write(&(value as u64).to_le_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streams_do_not_depend_on_draw_order() {
+        let mut one = ChaosRandom::new(99);
+        let first = one.stream("spawns").next_u32();
+        let entity = one.entity_stream(4).next_u32();
+        let system = one.system_stream("mover").next_u32();
+
+        let mut other = ChaosRandom::new(99);
+        assert_eq!(other.system_stream("mover").next_u32(), system);
+        for _ in 0..10 {
+            other.stream("particles").next_u32();
+        }
+        assert_eq!(other.entity_stream(4).next_u32(), entity);
+        assert_eq!(other.stream("spawns").next_u32(), first);
+
+        assert_ne!(one.entity_stream(5).next_u32(), entity);
+    }
+
+    #[test]
+    fn streams_continue_and_reseeding_restarts_them() {
+        let mut random = ChaosRandom::new(5);
+        let first = random.stream("loot").next_u64();
+        assert_ne!(random.stream("loot").next_u64(), first);
+        assert_eq!(random.fork("loot").next_u64(), first);
+
+        random.reseed(5);
+        assert_eq!(random.stream("loot").next_u64(), first);
+        assert!(random.remove_stream("loot"));
+        assert!(!random.remove_entity_stream(1));
+    }
+}
@@ -0,0 +1,297 @@
+use std::ops::{Range, RangeInclusive};
+
+use crate::math::{Vec2, Vec3};
+
+/// Small, fast random number generator (xoshiro128++) whose output only depends on its
+/// seed, on every platform. Not suitable for cryptography.
+///
+/// ```rust
+/// use chaos_engine::random::rng::ChaosRng;
+///
+/// let mut one = ChaosRng::new(42);
+/// let mut other = ChaosRng::new(42);
+/// assert_eq!(one.range(0..100), other.range(0..100));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChaosRng {
+    state: [u32; 4],
+}
+
+impl ChaosRng {
+    pub fn new(seed: u64) -> Self {
+        // SplitMix64 spreads the seed over the whole state, so similar seeds give unrelated
+        // sequences and the state is never all zeros.
+        let mut seed = seed;
+        let mut split_mix = || {
+            seed = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        };
+        let (a, b) = (split_mix(), split_mix());
+        Self {
+            state: [a as u32, (a >> 32) as u32, b as u32, (b >> 32) as u32],
+        }
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let [s0, s1, s2, s3] = &mut self.state;
+        let result = s0.wrapping_add(*s3).rotate_left(7).wrapping_add(*s0);
+        let t = *s1 << 9;
+        *s2 ^= *s0;
+        *s3 ^= *s1;
+        *s1 ^= *s2;
+        *s0 ^= *s3;
+        *s2 ^= t;
+        *s3 = s3.rotate_left(11);
+        result
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        ((self.next_u32() as u64) << 32) | self.next_u32() as u64
+    }
+
+    /// A value in `0.0..1.0`.
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    /// A value in `range`, e.g. `rng.range(0..10)` or `rng.range(-1.0..=1.0)`.
+    pub fn range<T, R: RandomRange<T>>(&mut self, range: R) -> T {
+        range.sample(self)
+    }
+
+    /// `true` with the given probability.
+    pub fn chance(&mut self, probability: f32) -> bool {
+        self.next_f32() < probability
+    }
+
+    /// A random element of `items`, `None` if it is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+        items.get(self.range(0..items.len()))
+    }
+
+    /// A random index into `weights`, each index picked in proportion to its weight. `None`
+    /// if no weight is positive; negative weights count as zero.
+    pub fn weighted_index(&mut self, weights: &[f32]) -> Option<usize> {
+        let total: f32 = weights.iter().map(|weight| weight.max(0.0)).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let mut target = self.next_f32() * total;
+        let mut last = None;
+        for (index, weight) in weights.iter().enumerate() {
+            if *weight <= 0.0 {
+                continue;
+            }
+            if target < *weight {
+                return Some(index);
+            }
+            target -= weight;
+            last = Some(index);
+        }
+        // Rounding can leave a tiny remainder after the last positive weight.
+        last
+    }
+
+    /// A random item of `(item, weight)` pairs, see [`ChaosRng::weighted_index`].
+    pub fn weighted_choice<'a, T>(&mut self, items: &'a [(T, f32)]) -> Option<&'a T> {
+        let weights: Vec<f32> = items.iter().map(|(_, weight)| *weight).collect();
+        self.weighted_index(&weights).map(|index| &items[index].0)
+    }
+
+    /// Shuffles `items` in place (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.range(0..=index));
+        }
+    }
+
+    /// A random direction of length one.
+    pub fn unit_vec2(&mut self) -> Vec2 {
+        let angle = self.range(0.0..std::f32::consts::TAU);
+        Vec2::new(angle.cos(), angle.sin())
+    }
+
+    /// A random direction of length one, uniformly distributed over the sphere.
+    pub fn unit_vec3(&mut self) -> Vec3 {
+        let z = self.range(-1.0..=1.0f32);
+        let angle = self.range(0.0..std::f32::consts::TAU);
+        let radius = (1.0 - z * z).max(0.0).sqrt();
+        Vec3::new(radius * angle.cos(), radius * angle.sin(), z)
+    }
+
+    /// A uniformly distributed point inside the circle around the origin.
+    pub fn in_circle(&mut self, radius: f32) -> Vec2 {
+        self.unit_vec2() * (radius * self.next_f32().sqrt())
+    }
+
+    /// A uniformly distributed point inside the sphere around the origin.
+    pub fn in_sphere(&mut self, radius: f32) -> Vec3 {
+        self.unit_vec3() * (radius * self.next_f32().cbrt())
+    }
+
+    /// A uniformly distributed point inside the rectangle spanned by `min` and `max`.
+    pub fn in_rect(&mut self, min: Vec2, max: Vec2) -> Vec2 {
+        Vec2::new(
+            lerp(min.x, max.x, self.next_f32()),
+            lerp(min.y, max.y, self.next_f32()),
+        )
+    }
+
+    /// A uniformly distributed point inside the box spanned by `min` and `max`.
+    pub fn in_box(&mut self, min: Vec3, max: Vec3) -> Vec3 {
+        Vec3::new(
+            lerp(min.x, max.x, self.next_f32()),
+            lerp(min.y, max.y, self.next_f32()),
+            lerp(min.z, max.z, self.next_f32()),
+        )
+    }
+}
+
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
+
+/// Ranges [`ChaosRng::range`] can pick values from.
+pub trait RandomRange<T> {
+    fn sample(self, rng: &mut ChaosRng) -> T;
+}
+
+impl RandomRange<f32> for Range<f32> {
+    fn sample(self, rng: &mut ChaosRng) -> f32 {
+        assert!(self.start < self.end, "Cannot sample an empty range");
+        // Rounding may land on the excluded end.
+        let value = lerp(self.start, self.end, rng.next_f32());
+        if value < self.end { value } else { self.start }
+    }
+}
+
+impl RandomRange<f32> for RangeInclusive<f32> {
+    fn sample(self, rng: &mut ChaosRng) -> f32 {
+        let (start, end) = self.into_inner();
+        assert!(start <= end, "Cannot sample an empty range");
+        let t = rng.next_u32() as f32 / u32::MAX as f32;
+        lerp(start, end, t).clamp(start, end)
+    }
+}
+
+// Samples `0..span` without modulo bias, by rejecting the values above the largest multiple
+// of `span`.
+fn below(rng: &mut ChaosRng, span: u64) -> u64 {
+    let zone = u64::MAX - (u64::MAX - span + 1) % span;
+    loop {
+        let value = rng.next_u64();
+        if value <= zone {
+            return value % span;
+        }
+    }
+}
+
+macro_rules! impl_integer_range {
+    ($($t:ty => $unsigned:ty),*) => {
+        $(
+            impl RandomRange<$t> for Range<$t> {
+                fn sample(self, rng: &mut ChaosRng) -> $t {
+                    assert!(self.start < self.end, "Cannot sample an empty range");
+                    // Differences are taken in the unsigned type of the same width, so signed spans
+                    // aren't sign-extended.
+                    let span = self.end.wrapping_sub(self.start) as $unsigned as u64;
+                    self.start.wrapping_add(below(rng, span) as $t)
+                }
+            }
+
+            impl RandomRange<$t> for RangeInclusive<$t> {
+                fn sample(self, rng: &mut ChaosRng) -> $t {
+                    let (start, end) = self.into_inner();
+                    assert!(start <= end, "Cannot sample an empty range");
+                    let span = (end.wrapping_sub(start) as $unsigned as u64).wrapping_add(1);
+                    if span == 0 {
+                        // The range covers every u64.
+                        return rng.next_u64() as $t;
+                    }
+                    start.wrapping_add(below(rng, span) as $t)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_range!(
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, usize => usize,
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_gives_same_sequence() {
+        let mut one = ChaosRng::new(7);
+        let mut other = ChaosRng::new(7);
+        let mut different = ChaosRng::new(8);
+
+        let values: Vec<u32> = (0..16).map(|_| one.next_u32()).collect();
+        assert_eq!(
+            values,
+            (0..16).map(|_| other.next_u32()).collect::<Vec<_>>()
+        );
+        assert_ne!(
+            values,
+            (0..16).map(|_| different.next_u32()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn ranges_stay_in_bounds() {
+        let mut rng = ChaosRng::new(1);
+        for _ in 0..1000 {
+            assert!((-3..4).contains(&rng.range(-3..4)));
+            assert!((-100..100i8).contains(&rng.range(-100..100i8)));
+            assert!(rng.range(1..=6u8) >= 1 && rng.range(1..=6u8) <= 6);
+            assert!((0.5..1.5).contains(&rng.range(0.5..1.5)));
+            assert!((-1.0..=1.0).contains(&rng.range(-1.0..=1.0f32)));
+        }
+        assert_eq!(rng.range(5..=5), 5);
+        let mut seen = [false; 6];
+        for _ in 0..200 {
+            seen[rng.range(0..6usize)] = true;
+        }
+        assert!(seen.iter().all(|seen| *seen));
+    }
+
+    #[test]
+    fn shapes_and_directions() {
+        let mut rng = ChaosRng::new(3);
+        for _ in 0..100 {
+            assert!((rng.unit_vec2().length() - 1.0).abs() < 1e-5);
+            assert!((rng.unit_vec3().length() - 1.0).abs() < 1e-5);
+            assert!(rng.in_circle(2.0).length() <= 2.0 + 1e-5);
+            assert!(rng.in_sphere(2.0).length() <= 2.0 + 1e-5);
+            let point = rng.in_rect(Vec2::new(-1.0, 2.0), Vec2::new(1.0, 3.0));
+            assert!((-1.0..=1.0).contains(&point.x) && (2.0..=3.0).contains(&point.y));
+        }
+    }
+
+    #[test]
+    fn weighted_choice_follows_weights() {
+        let mut rng = ChaosRng::new(11);
+        let items = [("never", 0.0), ("rare", 1.0), ("common", 9.0)];
+        let mut rare = 0;
+        for _ in 0..1000 {
+            match *rng.weighted_choice(&items).unwrap() {
+                "never" => panic!("Picked an item without weight"),
+                "rare" => rare += 1,
+                _ => (),
+            }
+        }
+        assert!((50..150).contains(&rare), "rare picked {rare} times");
+        assert_eq!(rng.weighted_index(&[0.0, -1.0]), None);
+        assert_eq!(rng.choose::<u32>(&[]), None);
+    }
+}
@@ -17,7 +17,7 @@ use vulkano::{
     device::{
         Device, DeviceCreateInfo, DeviceFeatures, Queue, QueueCreateInfo, physical::PhysicalDevice,
     },
-    format::ClearValue,
+    format::{ClearValue, NumericFormat},
     image::view::ImageView,
     instance::{Instance, InstanceCreateFlags, InstanceCreateInfo},
     memory::allocator::{FreeListAllocator, GenericMemoryAllocator, StandardMemoryAllocator},
@@ -36,6 +36,7 @@ use winit::{raw_window_handle::DisplayHandle, window::Window};
 
 use crate::{
     ecs::{EntityID, world::ChaosWorld},
+    math::color::Color,
     rendering::{adapters::select_physical_device, swapchain::get_swapchain_and_backbuffers},
 };
 
@@ -122,6 +123,7 @@ pub struct ChaosRenderSystem {
     add_render_component: ChaosReceiver,
     directories: HashMap<PathBuf, PathBuf>,
     pending_resize: Option<[u32; 2]>,
+    clear_color: Color,
 }
 
 pub trait ChaosRenderableTrait {
@@ -281,6 +283,7 @@ impl ChaosRenderSystem {
             add_render_component,
             directories: directories.clone(),
             pending_resize: None,
+            clear_color: Color::BLACK,
         }
     }
 
@@ -330,7 +333,7 @@ impl ChaosRenderSystem {
             RenderingAttachmentInfo::image_view(image_views[image_i as usize].clone());
         color_attachment.load_op = AttachmentLoadOp::Clear;
         color_attachment.store_op = AttachmentStoreOp::Store;
-        color_attachment.clear_value = Some(ClearValue::Float([0.0, 0.0, 0.0, 1.0]));
+        color_attachment.clear_value = Some(self.clear_value());
 
         let viewport_extent = self.render_context.viewport().extent;
         let rendering_info = RenderingInfo {
@@ -465,6 +468,28 @@ impl ChaosRenderSystem {
         }
     }
 
+    pub fn clear_color(&self) -> Color {
+        self.clear_color
+    }
+
+    /// The color the frame starts with, black by default.
+    pub fn set_clear_color(&mut self, color: impl Into<Color>) {
+        self.clear_color = color.into();
+    }
+
+    // sRGB swapchain images encode on write, so they are cleared with linear values;
+    // UNORM ones store the channels as they are and get the sRGB encoded color.
+    fn clear_value(&self) -> ClearValue {
+        let linear = self.clear_color.to_linear();
+        let format = self.render_context.swapchain().image_format();
+        if format.numeric_format_color() == Some(NumericFormat::SRGB) {
+            ClearValue::Float(linear.into())
+        } else {
+            let srgba = linear.to_srgba();
+            ClearValue::Float([srgba.red, srgba.green, srgba.blue, srgba.alpha])
+        }
+    }
+
     pub fn render_context(&self) -> &Arc<ChaosRenderContext> {
         &self.render_context
     }
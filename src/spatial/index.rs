@@ -0,0 +1,653 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+};
+
+use crate::{
+    ecs::EntityID,
+    math::{IVec2, Vec2},
+};
+
+/// Bounding circle of an entity in the [`SpatialIndex`], e.g. its position and the bounding
+/// radius of its shape. Keep it in sync with the entity's transform; the
+/// `SpatialIndexSystem` picks up the changes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpatialBounds {
+    pub center: Vec2,
+    pub radius: f32,
+}
+
+impl SpatialBounds {
+    pub fn new(center: Vec2, radius: f32) -> Self {
+        Self {
+            center,
+            radius: radius.max(0.0),
+        }
+    }
+
+    pub fn overlaps(&self, other: &SpatialBounds) -> bool {
+        let combined = self.radius + other.radius;
+        (self.center - other.center).length_squared() <= combined * combined
+    }
+
+    fn overlaps_aabb(&self, min: Vec2, max: Vec2) -> bool {
+        let closest = Vec2::clamp(&self.center, &min, &max);
+        (self.center - closest).length_squared() <= self.radius * self.radius
+    }
+
+    // Distance from `point` to the edge of the circle, zero inside it.
+    fn distance_to(&self, point: Vec2) -> f32 {
+        ((point - self.center).length() - self.radius).max(0.0)
+    }
+
+    // Distance along the ray (`direction` has length one) to where it enters the circle, zero
+    // if it starts inside.
+    fn ray_distance(&self, origin: Vec2, direction: Vec2) -> Option<f32> {
+        let offset = origin - self.center;
+        let b = Vec2::dot(&offset, &direction);
+        let c = offset.length_squared() - self.radius * self.radius;
+        if c > 0.0 && b > 0.0 {
+            return None;
+        }
+        let discriminant = b * b - c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        Some((-b - discriminant.sqrt()).max(0.0))
+    }
+}
+
+/// First entity hit by [`SpatialIndex::raycast`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit {
+    pub entity: EntityID,
+    pub distance: f32,
+    pub point: Vec2,
+}
+
+type Cell = IVec2;
+
+// Entities covering more cells than this are kept out of the grid, see `oversized`.
+const MAX_ENTRY_CELLS: u64 = 1024;
+
+// Bounding box of the occupied cells, so queries know where they can stop.
+struct Extent {
+    cells: Option<(Cell, Cell)>,
+    // Set when a cell on the boundary empties; the next query recomputes `cells`.
+    stale: bool,
+}
+
+struct Entry {
+    bounds: SpatialBounds,
+    min: Cell,
+    max: Cell,
+}
+
+/// Uniform grid over the [`SpatialBounds`] of entities, for proximity and overlap queries
+/// without comparing every pair of entities. Kept up to date by the `SpatialIndexSystem`,
+/// which adds it as a world resource.
+///
+/// Queries return entities sorted by id, so results don't depend on insertion order. The
+/// cell size should be around the size of typical entities; larger entities are stored in
+/// many cells, and ones covering more than 1024 cells in a list every query checks.
+///
+/// ```rust
+/// use chaos_engine::{
+///     math::Vec2,
+///     spatial::index::{SpatialBounds, SpatialIndex},
+/// };
+///
+/// let mut index = SpatialIndex::new(10.0);
+/// index.insert(1, SpatialBounds::new(Vec2::new(0.0, 0.0), 2.0));
+/// index.insert(2, SpatialBounds::new(Vec2::new(30.0, 0.0), 2.0));
+///
+/// assert_eq!(index.query_radius(Vec2::new(3.0, 0.0), 1.5), vec![1]);
+/// assert_eq!(index.nearest(Vec2::new(20.0, 0.0), f32::INFINITY), Some(2));
+/// ```
+pub struct SpatialIndex {
+    cell_size: f32,
+    cells: HashMap<Cell, Vec<EntityID>>,
+    entries: HashMap<EntityID, Entry>,
+    // Behind a `RefCell` so queries can shrink it.
+    extent: RefCell<Extent>,
+    // Entities too large for the grid, tested one by one by every query.
+    oversized: Vec<EntityID>,
+}
+
+impl SpatialIndex {
+    pub fn new(cell_size: f32) -> Self {
+        assert!(cell_size > 0.0, "Spatial index cell size must be positive");
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            entries: HashMap::new(),
+            extent: RefCell::new(Extent {
+                cells: None,
+                stale: false,
+            }),
+            oversized: Vec::new(),
+        }
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains(&self, entity: EntityID) -> bool {
+        self.entries.contains_key(&entity)
+    }
+
+    pub fn bounds(&self, entity: EntityID) -> Option<&SpatialBounds> {
+        self.entries.get(&entity).map(|entry| &entry.bounds)
+    }
+
+    pub fn entities(&self) -> impl Iterator<Item = EntityID> + '_ {
+        self.entries.keys().copied()
+    }
+
+    /// Adds the entity, or moves it if it is already in the index.
+    pub fn insert(&mut self, entity: EntityID, bounds: SpatialBounds) {
+        let (min, max) = self.cell_range(
+            bounds.center - Vec2::one() * bounds.radius,
+            bounds.center + Vec2::one() * bounds.radius,
+        );
+        if let Some(entry) = self.entries.get_mut(&entity) {
+            let unchanged_cells = entry.min == min && entry.max == max;
+            entry.bounds = bounds;
+            if unchanged_cells {
+                return;
+            }
+        }
+        self.remove(entity);
+
+        self.entries.insert(entity, Entry { bounds, min, max });
+        if cell_count(min, max) > MAX_ENTRY_CELLS {
+            self.oversized.push(entity);
+            return;
+        }
+        for_each_cell(min, max, |cell| {
+            self.cells.entry(cell).or_default().push(entity);
+        });
+        let extent = &mut self.extent.get_mut().cells;
+        *extent = Some(match *extent {
+            Some((extent_min, extent_max)) => {
+                (IVec2::min(&extent_min, &min), IVec2::max(&extent_max, &max))
+            }
+            None => (min, max),
+        });
+    }
+
+    pub fn remove(&mut self, entity: EntityID) -> bool {
+        let Some(entry) = self.entries.remove(&entity) else {
+            return false;
+        };
+        if cell_count(entry.min, entry.max) > MAX_ENTRY_CELLS {
+            self.oversized.retain(|other| *other != entity);
+        } else {
+            let extent = self.extent.get_mut();
+            for_each_cell(entry.min, entry.max, |cell| {
+                if let Some(entities) = self.cells.get_mut(&cell) {
+                    entities.retain(|other| *other != entity);
+                    if entities.is_empty() {
+                        self.cells.remove(&cell);
+                        extent.stale |= extent.cells.is_some_and(|(min, max)| {
+                            cell.x == min.x || cell.x == max.x || cell.y == min.y || cell.y == max.y
+                        });
+                    }
+                }
+            });
+        }
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.entries.clear();
+        *self.extent.get_mut() = Extent {
+            cells: None,
+            stale: false,
+        };
+        self.oversized.clear();
+    }
+
+    /// Entities whose bounds overlap the circle.
+    pub fn query_radius(&self, center: Vec2, radius: f32) -> Vec<EntityID> {
+        let query = SpatialBounds::new(center, radius);
+        self.collect(
+            center - Vec2::one() * radius,
+            center + Vec2::one() * radius,
+            |bounds| bounds.overlaps(&query),
+        )
+    }
+
+    /// Entities whose bounds overlap the axis aligned box spanned by `min` and `max`.
+    pub fn query_aabb(&self, min: Vec2, max: Vec2) -> Vec<EntityID> {
+        let (min, max) = (Vec2::min(&min, &max), Vec2::max(&min, &max));
+        self.collect(min, max, |bounds| bounds.overlaps_aabb(min, max))
+    }
+
+    /// Every pair of entities with overlapping bounds, each pair once with the lower id first.
+    pub fn overlapping_pairs(&self) -> Vec<(EntityID, EntityID)> {
+        let mut pairs = HashSet::new();
+        for entities in self.cells.values() {
+            for (index, a) in entities.iter().enumerate() {
+                for b in &entities[index + 1..] {
+                    let pair = ((*a).min(*b), (*a).max(*b));
+                    if !pairs.contains(&pair)
+                        && self.entries[a].bounds.overlaps(&self.entries[b].bounds)
+                    {
+                        pairs.insert(pair);
+                    }
+                }
+            }
+        }
+        for a in &self.oversized {
+            for (b, entry) in &self.entries {
+                if a != b && self.entries[a].bounds.overlaps(&entry.bounds) {
+                    pairs.insert(((*a).min(*b), (*a).max(*b)));
+                }
+            }
+        }
+        let mut pairs: Vec<_> = pairs.into_iter().collect();
+        pairs.sort_unstable();
+        pairs
+    }
+
+    /// The entity whose bounds are closest to `point` (zero distance inside them), within
+    /// `max_distance`. Ties go to the lowest id.
+    pub fn nearest(&self, point: Vec2, max_distance: f32) -> Option<EntityID> {
+        let mut best: Option<(f32, EntityID)> = None;
+        let consider = |entity: EntityID, best: &mut Option<(f32, EntityID)>| {
+            let distance = self.entries[&entity].bounds.distance_to(point);
+            if distance > max_distance {
+                return;
+            }
+            let closer = match *best {
+                Some((best_distance, best_entity)) => {
+                    distance < best_distance || (distance == best_distance && entity < best_entity)
+                }
+                None => true,
+            };
+            if closer {
+                *best = Some((distance, entity));
+            }
+        };
+        self.oversized
+            .iter()
+            .for_each(|entity| consider(*entity, &mut best));
+
+        if let Some((extent_min, extent_max)) = self.extent() {
+            let origin = self.cell_of(point);
+            // Rings beyond the extent are empty, and cells of ring `ring` are at least
+            // `(ring - 1) * cell_size` away.
+            let extent_rings = [
+                origin.x.saturating_sub(extent_min.x),
+                extent_max.x.saturating_sub(origin.x),
+                origin.y.saturating_sub(extent_min.y),
+                extent_max.y.saturating_sub(origin.y),
+            ]
+            .into_iter()
+            .max()
+            .unwrap_or(0)
+            .max(0);
+            let distance_rings = (max_distance / self.cell_size).ceil().min(i32::MAX as f32) as i32;
+            let last_ring = extent_rings.min(distance_rings.saturating_add(1));
+
+            for ring in 0..=last_ring {
+                if best.is_some_and(|(distance, _)| distance <= (ring - 1) as f32 * self.cell_size)
+                {
+                    break;
+                }
+                for_each_ring_cell(origin, ring, |cell| {
+                    for entity in self.cells.get(&cell).into_iter().flatten() {
+                        consider(*entity, &mut best);
+                    }
+                });
+            }
+        }
+        best.map(|(_, entity)| entity)
+    }
+
+    /// The first entity the ray from `origin` along `direction` hits within `max_distance`.
+    /// Entities containing `origin` are hit at distance zero.
+    pub fn raycast(&self, origin: Vec2, direction: Vec2, max_distance: f32) -> Option<RayHit> {
+        if direction.length_squared() == 0.0 {
+            return None;
+        }
+        let direction = Vec2::normalized(&direction);
+
+        let mut best: Option<RayHit> = None;
+        let consider = |entity: EntityID, best: &mut Option<RayHit>| {
+            let Some(distance) = self.entries[&entity]
+                .bounds
+                .ray_distance(origin, direction)
+                .filter(|distance| *distance <= max_distance)
+            else {
+                return;
+            };
+            let closer = best.is_none_or(|hit| {
+                distance < hit.distance || (distance == hit.distance && entity < hit.entity)
+            });
+            if closer {
+                *best = Some(RayHit {
+                    entity,
+                    distance,
+                    point: origin + direction * distance,
+                });
+            }
+        };
+        self.oversized
+            .iter()
+            .for_each(|entity| consider(*entity, &mut best));
+        let Some((min_cell, max_cell)) = self.extent() else {
+            return best;
+        };
+
+        // Outside the occupied cells there is nothing to hit, so the walk only covers the part
+        // of the ray between entering and leaving them.
+        let extent_min = min_cell.as_vec2() * self.cell_size;
+        let extent_max = (max_cell + IVec2::one()).as_vec2() * self.cell_size;
+        let mut enter = 0.0f32;
+        let mut exit = max_distance;
+        for (origin, direction, min, max) in [
+            (origin.x, direction.x, extent_min.x, extent_max.x),
+            (origin.y, direction.y, extent_min.y, extent_max.y),
+        ] {
+            if direction == 0.0 {
+                if origin < min || origin > max {
+                    return best;
+                }
+                continue;
+            }
+            let (a, b) = ((min - origin) / direction, (max - origin) / direction);
+            enter = enter.max(a.min(b));
+            exit = exit.min(a.max(b));
+        }
+        if enter > exit {
+            return best;
+        }
+        let limit = exit;
+
+        // Walks the cells along the ray (Amanatides & Woo), testing the entities in each. The
+        // start cell is clamped, since rounding can put the entry point just outside.
+        let start = origin + direction * enter;
+        let start_cell = self.cell_of(start);
+        let mut cell = IVec2::new(
+            start_cell.x.clamp(min_cell.x, max_cell.x),
+            start_cell.y.clamp(min_cell.y, max_cell.y),
+        );
+        let step = IVec2::new(direction.x.signum() as i32, direction.y.signum() as i32);
+        let boundary = |cell: i32, step: i32| (cell + step.max(0)) as f32 * self.cell_size;
+        let axis_distance = |position: f32, direction: f32, boundary: f32| {
+            if direction == 0.0 {
+                f32::INFINITY
+            } else {
+                (boundary - position) / direction
+            }
+        };
+        let mut next = (
+            enter + axis_distance(start.x, direction.x, boundary(cell.x, step.x)),
+            enter + axis_distance(start.y, direction.y, boundary(cell.y, step.y)),
+        );
+        let delta = (
+            (self.cell_size / direction.x).abs(),
+            (self.cell_size / direction.y).abs(),
+        );
+
+        loop {
+            for entity in self.cells.get(&cell).into_iter().flatten() {
+                consider(*entity, &mut best);
+            }
+
+            let cell_exit = next.0.min(next.1);
+            if best.is_some_and(|hit| hit.distance <= cell_exit) || cell_exit > limit {
+                break;
+            }
+            if next.0 < next.1 {
+                cell.x += step.x;
+                next.0 += delta.0;
+            } else {
+                cell.y += step.y;
+                next.1 += delta.1;
+            }
+        }
+        best
+    }
+
+    fn extent(&self) -> Option<(Cell, Cell)> {
+        let mut extent = self.extent.borrow_mut();
+        if extent.stale {
+            extent.cells = self.cells.keys().fold(None, |cells, cell| {
+                Some(match cells {
+                    Some((min, max)) => (IVec2::min(&min, cell), IVec2::max(&max, cell)),
+                    None => (*cell, *cell),
+                })
+            });
+            extent.stale = false;
+        }
+        extent.cells
+    }
+
+    fn cell_of(&self, point: Vec2) -> Cell {
+        IVec2::floor(&(point / self.cell_size))
+    }
+
+    fn cell_range(&self, min: Vec2, max: Vec2) -> (Cell, Cell) {
+        (self.cell_of(min), self.cell_of(max))
+    }
+
+    fn collect(
+        &self,
+        min: Vec2,
+        max: Vec2,
+        filter: impl Fn(&SpatialBounds) -> bool,
+    ) -> Vec<EntityID> {
+        let mut found: Vec<EntityID> = self
+            .oversized
+            .iter()
+            .copied()
+            .filter(|entity| filter(&self.entries[entity].bounds))
+            .collect();
+
+        // Only the occupied cells can hold entities; when the range still spans more cells
+        // than that, going through the occupied ones is faster.
+        let (min, max) = self.cell_range(min, max);
+        let Some((extent_min, extent_max)) = self.extent() else {
+            return found;
+        };
+        let (min, max) = (IVec2::max(&min, &extent_min), IVec2::min(&max, &extent_max));
+        if min.x > max.x || min.y > max.y {
+            return found;
+        }
+        let mut add = |entities: &Vec<EntityID>| {
+            for entity in entities {
+                if filter(&self.entries[entity].bounds) {
+                    found.push(*entity);
+                }
+            }
+        };
+        if cell_count(min, max) > self.cells.len() as u64 {
+            for (cell, entities) in &self.cells {
+                if (min.x..=max.x).contains(&cell.x) && (min.y..=max.y).contains(&cell.y) {
+                    add(entities);
+                }
+            }
+        } else {
+            for_each_cell(min, max, |cell| {
+                self.cells.get(&cell).into_iter().for_each(&mut add);
+            });
+        }
+        found.sort_unstable();
+        found.dedup();
+        found
+    }
+}
+
+// Number of cells from `min` to `max`, saturating for ranges over the whole grid.
+fn cell_count(min: Cell, max: Cell) -> u64 {
+    let width = (max.x as i64 - min.x as i64 + 1).max(0) as u64;
+    let height = (max.y as i64 - min.y as i64 + 1).max(0) as u64;
+    width.saturating_mul(height)
+}
+
+fn for_each_cell(min: Cell, max: Cell, mut f: impl FnMut(Cell)) {
+    for x in min.x..=max.x {
+        for y in min.y..=max.y {
+            f(IVec2::new(x, y));
+        }
+    }
+}
+
+// Cells at exactly `ring` cells (Chebyshev distance) from `origin`.
+fn for_each_ring_cell(origin: Cell, ring: i32, mut f: impl FnMut(Cell)) {
+    if ring == 0 {
+        f(origin);
+        return;
+    }
+    for x in origin.x - ring..=origin.x + ring {
+        f(IVec2::new(x, origin.y - ring));
+        f(IVec2::new(x, origin.y + ring));
+    }
+    for y in origin.y - ring + 1..origin.y + ring {
+        f(IVec2::new(origin.x - ring, y));
+        f(IVec2::new(origin.x + ring, y));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queries_find_entities_across_cells() {
+        let mut index = SpatialIndex::new(2.0);
+        index.insert(1, SpatialBounds::new(Vec2::new(-5.0, -5.0), 1.0));
+        index.insert(2, SpatialBounds::new(Vec2::new(0.5, 0.5), 0.25));
+        index.insert(3, SpatialBounds::new(Vec2::new(6.0, 1.0), 3.0));
+        index.insert(4, SpatialBounds::new(Vec2::new(20.0, 20.0), 1.0));
+
+        assert_eq!(
+            index.query_aabb(Vec2::new(4.0, 4.0), Vec2::new(-6.0, -4.5)),
+            vec![1, 2, 3]
+        );
+        assert_eq!(index.query_radius(Vec2::new(2.5, 1.0), 0.6), vec![3]);
+
+        assert_eq!(index.nearest(Vec2::new(1.0, 1.0), f32::INFINITY), Some(2));
+        assert_eq!(index.nearest(Vec2::new(17.0, 17.0), f32::INFINITY), Some(4));
+        assert_eq!(index.nearest(Vec2::new(17.0, 17.0), 1.0), None);
+        assert_eq!(index.nearest(Vec2::new(-40.0, 3.0), f32::INFINITY), Some(1));
+
+        index.remove(3);
+        assert!(index.query_radius(Vec2::new(6.0, 1.0), 0.5).is_empty());
+    }
+
+    #[test]
+    fn raycast_returns_the_first_hit() {
+        let mut index = SpatialIndex::new(2.0);
+        index.insert(1, SpatialBounds::new(Vec2::new(10.0, 0.0), 1.0));
+        index.insert(2, SpatialBounds::new(Vec2::new(5.0, 0.0), 1.0));
+        index.insert(3, SpatialBounds::new(Vec2::new(0.0, 8.0), 1.0));
+
+        let hit = index
+            .raycast(Vec2::new(-3.0, 0.0), Vec2::new(2.0, 0.0), 100.0)
+            .unwrap();
+        assert_eq!(hit.entity, 2);
+        assert!((hit.distance - 7.0).abs() < 1e-5);
+        assert!((hit.point.x - 4.0).abs() < 1e-5);
+
+        assert_eq!(
+            index
+                .raycast(Vec2::new(0.0, 0.0), Vec2::new(0.0, 1.0), 100.0)
+                .map(|hit| hit.entity),
+            Some(3)
+        );
+        assert_eq!(
+            index.raycast(Vec2::new(-3.0, 0.0), Vec2::new(1.0, 0.0), 5.0),
+            None
+        );
+        assert_eq!(
+            index.raycast(Vec2::new(-3.0, 0.0), Vec2::new(-1.0, 0.0), f32::INFINITY),
+            None
+        );
+        let diagonal = index
+            .raycast(Vec2::new(-20.0, -20.0), Vec2::new(1.0, 1.0), f32::INFINITY)
+            .map(|hit| hit.entity);
+        assert_eq!(diagonal, None);
+    }
+
+    #[test]
+    fn extent_shrinks_when_boundary_cells_empty() {
+        let mut index = SpatialIndex::new(1.0);
+        index.insert(1, SpatialBounds::new(Vec2::new(0.5, 0.5), 0.25));
+        index.insert(2, SpatialBounds::new(Vec2::new(5000.5, 0.5), 0.25));
+        index.insert(3, SpatialBounds::new(Vec2::new(2.5, 0.5), 0.25));
+        assert_eq!(
+            index.extent(),
+            Some((IVec2::new(0, 0), IVec2::new(5000, 0)))
+        );
+
+        // Moving the far entity next to the others leaves its old cell empty.
+        index.insert(2, SpatialBounds::new(Vec2::new(1.5, 0.5), 0.25));
+        assert_eq!(index.extent(), Some((IVec2::new(0, 0), IVec2::new(2, 0))));
+        index.remove(1);
+        assert_eq!(index.extent(), Some((IVec2::new(1, 0), IVec2::new(2, 0))));
+        assert_eq!(
+            index.nearest(Vec2::new(-100.0, 0.5), f32::INFINITY),
+            Some(2)
+        );
+
+        index.remove(2);
+        index.remove(3);
+        assert_eq!(index.extent(), None);
+    }
+
+    #[test]
+    fn huge_queries_and_entities_stay_cheap() {
+        let mut index = SpatialIndex::new(1.0);
+        index.insert(1, SpatialBounds::new(Vec2::zero(), 1.0));
+        index.insert(2, SpatialBounds::new(Vec2::new(5000.0, 0.0), 1.0));
+
+        assert_eq!(index.query_radius(Vec2::zero(), 20000.0), vec![1, 2]);
+        assert_eq!(index.query_radius(Vec2::zero(), f32::INFINITY), vec![1, 2]);
+        assert_eq!(index.query_radius(Vec2::zero(), f32::NAN), vec![1]);
+        assert_eq!(
+            index.query_aabb(Vec2::splat(f32::NEG_INFINITY), Vec2::splat(f32::INFINITY)),
+            vec![1, 2]
+        );
+
+        // Too large for the grid, but still found by every query.
+        index.insert(3, SpatialBounds::new(Vec2::new(0.0, 1e6), 1e6));
+        assert_eq!(index.query_radius(Vec2::new(3.0, 3.0), 0.5), vec![3]);
+        assert_eq!(index.nearest(Vec2::new(0.0, 10.0), 1.0), Some(3));
+        assert_eq!(
+            index
+                .raycast(Vec2::new(10.0, -10.0), Vec2::new(0.0, 1.0), 100.0)
+                .map(|hit| hit.entity),
+            Some(3)
+        );
+        assert_eq!(index.overlapping_pairs(), vec![(1, 3)]);
+
+        index.insert(3, SpatialBounds::new(Vec2::new(0.0, 3.0), 1.0));
+        assert!(index.query_radius(Vec2::new(3.0, 3.0), 0.5).is_empty());
+        assert!(index.remove(3));
+        assert_eq!(index.overlapping_pairs(), vec![]);
+
+        // The walk starts where the ray reaches the occupied cells, not at a far away origin.
+        let far = |origin: Vec2, direction: Vec2| {
+            index
+                .raycast(origin, direction, f32::INFINITY)
+                .map(|hit| hit.entity)
+        };
+        assert_eq!(far(Vec2::new(-1e7, 0.0), Vec2::new(1.0, 0.0)), Some(1));
+        assert_eq!(far(Vec2::new(1e7, 0.5), Vec2::new(-1.0, 0.0)), Some(2));
+        assert_eq!(far(Vec2::new(2500.0, -1e7), Vec2::new(0.0, 1.0)), None);
+    }
+}
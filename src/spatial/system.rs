@@ -0,0 +1,133 @@
+use std::collections::HashSet;
+
+use crate::{
+    ecs::{system::ChaosSystem, world::ChaosWorld},
+    spatial::index::{SpatialBounds, SpatialIndex},
+    transform::component::GlobalTransform,
+};
+
+/// Adds a [`SpatialIndex`] resource and keeps it in sync with the [`SpatialBounds`]
+/// components: moved entities are re-indexed, entities that lost their bounds or were
+/// despawned are dropped. Bounds on entities with a [`GlobalTransform`] are centered on its
+/// translation first. Add it after the `TransformSystem`, so queries in later systems see
+/// this update's positions.
+pub struct SpatialIndexSystem {
+    cell_size: f32,
+}
+
+impl SpatialIndexSystem {
+    pub fn new(cell_size: f32) -> Self {
+        Self { cell_size }
+    }
+
+    pub(crate) fn sync(world: &mut ChaosWorld) -> Result<(), &'static str> {
+        for (_, (global, bounds)) in world
+            .query::<(&GlobalTransform, &mut SpatialBounds)>()
+            .map_err(|_| "Failed to query transformed bounds")?
+        {
+            bounds.center = global.translation;
+        }
+
+        let mut current = Vec::new();
+        for (entity, (bounds,)) in world
+            .query::<(&SpatialBounds,)>()
+            .map_err(|_| "Failed to query spatial bounds")?
+        {
+            current.push((entity, *bounds));
+        }
+
+        let index = world
+            .get_resource_mut::<SpatialIndex>()
+            .ok_or("Missing spatial index resource")?;
+        let seen: HashSet<_> = current.iter().map(|(entity, _)| *entity).collect();
+        let gone: Vec<_> = index
+            .entities()
+            .filter(|entity| !seen.contains(entity))
+            .collect();
+        for entity in gone {
+            index.remove(entity);
+        }
+        for (entity, bounds) in current {
+            if index.bounds(entity) != Some(&bounds) {
+                index.insert(entity, bounds);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ChaosSystem for SpatialIndexSystem {
+    fn initialize(&mut self, world: &mut ChaosWorld) -> Result<(), &'static str> {
+        if world.get_resource::<SpatialIndex>().is_none() {
+            world.insert_resource(SpatialIndex::new(self.cell_size));
+        }
+        Self::sync(world)
+    }
+
+    fn update(&mut self, world: &mut ChaosWorld) -> Result<(), &'static str> {
+        Self::sync(world)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{Vec2, transform::Transform2D};
+
+    fn index(world: &ChaosWorld) -> &SpatialIndex {
+        world.get_resource::<SpatialIndex>().unwrap()
+    }
+
+    #[test]
+    fn index_follows_bounds_components() {
+        let mut world = ChaosWorld::new();
+        let mut system = SpatialIndexSystem::new(4.0);
+        let ship = world
+            .spawn()
+            .with(SpatialBounds::new(Vec2::zero(), 1.0))
+            .build();
+        let rock = world
+            .spawn()
+            .with(SpatialBounds::new(Vec2::new(10.0, 0.0), 2.0))
+            .build();
+        system.initialize(&mut world).unwrap();
+        assert_eq!(index(&world).query_radius(Vec2::zero(), 0.5), vec![ship]);
+        assert!(index(&world).overlapping_pairs().is_empty());
+
+        world
+            .get_component_mut::<SpatialBounds>(ship)
+            .unwrap()
+            .center = Vec2::new(8.0, 0.0);
+        system.update(&mut world).unwrap();
+        assert_eq!(index(&world).overlapping_pairs(), vec![(ship, rock)]);
+        assert!(index(&world).query_radius(Vec2::zero(), 0.5).is_empty());
+
+        world.despawn(rock);
+        system.update(&mut world).unwrap();
+        assert!(!index(&world).contains(rock));
+        assert_eq!(index(&world).len(), 1);
+    }
+
+    #[test]
+    fn bounds_follow_global_transforms() {
+        let mut world = ChaosWorld::new();
+        let mut system = SpatialIndexSystem::new(4.0);
+        let rock = world
+            .spawn()
+            .with(GlobalTransform(Transform2D::from_translation(Vec2::new(
+                9.0, 0.0,
+            ))))
+            .with(SpatialBounds::new(Vec2::zero(), 1.0))
+            .build();
+        system.initialize(&mut world).unwrap();
+
+        assert_eq!(
+            world.get_component::<SpatialBounds>(rock).unwrap().center,
+            Vec2::new(9.0, 0.0)
+        );
+        assert_eq!(
+            index(&world).query_radius(Vec2::new(9.0, 0.0), 0.5),
+            vec![rock]
+        );
+    }
+}
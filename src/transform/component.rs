@@ -0,0 +1,87 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::{
+    ecs::EntityID,
+    math::transform::{Transform2D, Transform3D},
+};
+
+/// Pose of an entity relative to its [`Parent`], or to the world without one. Gameplay
+/// code and the `PhysicsSystem` write it; the `TransformSystem` turns it into the
+/// [`GlobalTransform`] that everything else reads.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Transform(pub Transform2D);
+
+/// World pose of an entity, computed by the `TransformSystem`. Read it for rendering and
+/// spatial queries; changes to it are overwritten on the next update.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GlobalTransform(pub Transform2D);
+
+/// The 3D counterpart of [`Transform`], e.g. for models and cameras.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Transform3(pub Transform3D);
+
+/// The 3D counterpart of [`GlobalTransform`], computed by the `TransformSystem` from
+/// [`Transform3`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GlobalTransform3(pub Transform3D);
+
+/// Makes the entity's [`Transform`] or [`Transform3`] relative to the world pose of another
+/// entity with the same kind of transform.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Parent(pub EntityID);
+
+impl Deref for Transform {
+    type Target = Transform2D;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Transform {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Transform2D> for Transform {
+    fn from(transform: Transform2D) -> Self {
+        Self(transform)
+    }
+}
+
+impl Deref for GlobalTransform {
+    type Target = Transform2D;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Deref for Transform3 {
+    type Target = Transform3D;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Transform3 {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Transform3D> for Transform3 {
+    fn from(transform: Transform3D) -> Self {
+        Self(transform)
+    }
+}
+
+impl Deref for GlobalTransform3 {
+    type Target = Transform3D;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
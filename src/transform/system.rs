@@ -0,0 +1,257 @@
+use std::{collections::HashMap, ops::Mul};
+
+use crate::{
+    ecs::{EntityID, component::Component, system::ChaosSystem, world::ChaosWorld},
+    math::transform::{Transform2D, Transform3D},
+    transform::component::{GlobalTransform, GlobalTransform3, Parent, Transform, Transform3},
+};
+
+// A local transform component and the world one computed from it; 2D and 3D hierarchies
+// are propagated the same way, each on its own.
+trait Hierarchy: Component {
+    type Pose: Copy + Mul<Output = Self::Pose>;
+    type Global: Component;
+
+    fn identity() -> Self::Pose;
+    fn pose(&self) -> Self::Pose;
+    fn global(pose: Self::Pose) -> Self::Global;
+    fn set_global(global: &mut Self::Global, pose: Self::Pose);
+}
+
+impl Hierarchy for Transform {
+    type Pose = Transform2D;
+    type Global = GlobalTransform;
+
+    fn identity() -> Transform2D {
+        Transform2D::identity()
+    }
+
+    fn pose(&self) -> Transform2D {
+        self.0
+    }
+
+    fn global(pose: Transform2D) -> GlobalTransform {
+        GlobalTransform(pose)
+    }
+
+    fn set_global(global: &mut GlobalTransform, pose: Transform2D) {
+        global.0 = pose;
+    }
+}
+
+impl Hierarchy for Transform3 {
+    type Pose = Transform3D;
+    type Global = GlobalTransform3;
+
+    fn identity() -> Transform3D {
+        Transform3D::identity()
+    }
+
+    fn pose(&self) -> Transform3D {
+        self.0
+    }
+
+    fn global(pose: Transform3D) -> GlobalTransform3 {
+        GlobalTransform3(pose)
+    }
+
+    fn set_global(global: &mut GlobalTransform3, pose: Transform3D) {
+        global.0 = pose;
+    }
+}
+
+/// Computes the [`GlobalTransform`] of every entity with a [`Transform`], and the
+/// [`GlobalTransform3`] of every entity with a [`Transform3`], following their [`Parent`]s,
+/// and adds the components where they are missing. Add it after the systems that move
+/// entities (including the `PhysicsSystem`) and before the ones that read world poses,
+/// such as the `SpatialIndexSystem` and rendering.
+///
+/// A parent without a `Transform` is ignored, and so is the link that closes a cycle.
+pub struct TransformSystem;
+
+impl TransformSystem {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub(crate) fn propagate(world: &mut ChaosWorld) -> Result<(), &'static str> {
+        Self::propagate_hierarchy::<Transform>(world)?;
+        Self::propagate_hierarchy::<Transform3>(world)
+    }
+
+    fn propagate_hierarchy<H: Hierarchy>(world: &mut ChaosWorld) -> Result<(), &'static str> {
+        let mut locals: HashMap<EntityID, H::Pose> = HashMap::new();
+        for (entity, (transform,)) in world
+            .query::<(&H,)>()
+            .map_err(|_| "Failed to query transforms")?
+        {
+            locals.insert(entity, transform.pose());
+        }
+        let parents: HashMap<EntityID, EntityID> = locals
+            .keys()
+            .filter_map(|entity| {
+                let parent = world.get_component::<Parent>(*entity)?.0;
+                locals.contains_key(&parent).then_some((*entity, parent))
+            })
+            .collect();
+
+        let mut globals: HashMap<EntityID, H::Pose> = HashMap::new();
+        for &entity in locals.keys() {
+            // Walks up to a root or an entity already done, then back down.
+            let mut chain = vec![entity];
+            let mut global = loop {
+                let current = *chain.last().unwrap();
+                if let Some(global) = globals.get(&current) {
+                    chain.pop();
+                    break *global;
+                }
+                match parents.get(&current) {
+                    Some(parent) if !chain.contains(parent) => chain.push(*parent),
+                    _ => break H::identity(),
+                }
+            };
+            while let Some(current) = chain.pop() {
+                global = locals[&current] * global;
+                globals.insert(current, global);
+            }
+        }
+
+        for (entity, global) in globals {
+            match world.get_component_mut::<H::Global>(entity) {
+                Some(component) => H::set_global(component, global),
+                None => world
+                    .add_component(entity, H::global(global))
+                    .map_err(|_| "Failed to add global transform")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for TransformSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChaosSystem for TransformSystem {
+    fn initialize(&mut self, world: &mut ChaosWorld) -> Result<(), &'static str> {
+        Self::propagate(world)
+    }
+
+    fn update(&mut self, world: &mut ChaosWorld) -> Result<(), &'static str> {
+        Self::propagate(world)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{Vec2, Vec3};
+
+    fn global(world: &ChaosWorld, entity: EntityID) -> Transform2D {
+        world.get_component::<GlobalTransform>(entity).unwrap().0
+    }
+
+    #[test]
+    fn children_follow_their_parents() {
+        let mut world = ChaosWorld::new();
+        let ship = world
+            .spawn()
+            .with(Transform(
+                Transform2D::from_translation(Vec2::new(10.0, 0.0)).with_scale(Vec2::splat(2.0)),
+            ))
+            .build();
+        let turret = world
+            .spawn()
+            .with(Transform(Transform2D::from_translation(Vec2::new(
+                0.0, 1.0,
+            ))))
+            .with(Parent(ship))
+            .build();
+        let barrel = world
+            .spawn()
+            .with(Transform(Transform2D::from_translation(Vec2::new(
+                1.0, 0.0,
+            ))))
+            .with(Parent(turret))
+            .build();
+
+        TransformSystem::propagate(&mut world).unwrap();
+        assert_eq!(global(&world, ship).translation, Vec2::new(10.0, 0.0));
+        assert_eq!(global(&world, barrel).translation, Vec2::new(12.0, 2.0));
+        assert_eq!(global(&world, barrel).scale, Vec2::splat(2.0));
+
+        world
+            .get_component_mut::<Transform>(ship)
+            .unwrap()
+            .translation = Vec2::zero();
+        TransformSystem::propagate(&mut world).unwrap();
+        assert_eq!(global(&world, turret).translation, Vec2::new(0.0, 2.0));
+    }
+
+    #[test]
+    fn hierarchies_propagate_in_3d() {
+        let mut world = ChaosWorld::new();
+        let camera_rig = world
+            .spawn()
+            .with(Transform3(
+                Transform3D::from_translation(Vec3::new(0.0, 5.0, 0.0))
+                    .with_scale(Vec3::splat(2.0)),
+            ))
+            .build();
+        let camera = world
+            .spawn()
+            .with(Transform3(Transform3D::from_translation(Vec3::new(
+                0.0, 0.0, -3.0,
+            ))))
+            .with(Parent(camera_rig))
+            .build();
+        // A 2D parent doesn't move a 3D child.
+        let sprite = world.spawn().with(Transform::default()).build();
+        let model = world
+            .spawn()
+            .with(Transform3::default())
+            .with(Parent(sprite))
+            .build();
+
+        TransformSystem::propagate(&mut world).unwrap();
+        let camera = world.get_component::<GlobalTransform3>(camera).unwrap();
+        assert_eq!(camera.translation, Vec3::new(0.0, 5.0, -6.0));
+        assert_eq!(camera.scale, Vec3::splat(2.0));
+        assert_eq!(
+            world.get_component::<GlobalTransform3>(model).unwrap().0,
+            Transform3D::identity()
+        );
+        assert!(world.get_component::<GlobalTransform3>(sprite).is_none());
+    }
+
+    #[test]
+    fn cycles_and_missing_parents_are_ignored() {
+        let mut world = ChaosWorld::new();
+        let a = world
+            .spawn()
+            .with(Transform(Transform2D::from_translation(Vec2::x_axis())))
+            .build();
+        let b = world
+            .spawn()
+            .with(Transform(Transform2D::from_translation(Vec2::y_axis())))
+            .with(Parent(a))
+            .build();
+        world.add_component(a, Parent(b)).unwrap();
+        let orphan = world
+            .spawn()
+            .with(Transform(Transform2D::from_translation(Vec2::one())))
+            .with(Parent(999))
+            .build();
+
+        TransformSystem::propagate(&mut world).unwrap();
+        assert_eq!(global(&world, orphan).translation, Vec2::one());
+        // One of the two ends up as the root, the other below it.
+        let (a, b) = (global(&world, a).translation, global(&world, b).translation);
+        assert!(
+            (a == Vec2::x_axis() && b == Vec2::one()) || (b == Vec2::y_axis() && a == Vec2::one()),
+            "{a:?} {b:?}"
+        );
+    }
+}
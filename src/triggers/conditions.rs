@@ -1,19 +1,46 @@
+use std::marker::PhantomData;
+
+use chaos_communicator::communicator::ChaosReceiver;
+
+use crate::{
+    ecs::{EntityID, component::Component, world::ChaosWorld},
+    math::Vec2,
+    triggers::trigger_event_key::TriggerEventKey,
+};
+
+/// A condition of a [`Trigger`](crate::triggers::trigger::Trigger). Conditions are checked
+/// once per update of the `TriggerSystem`, all of them every time (no short circuiting), so
+/// stateful conditions like timers see every frame.
 pub trait TriggerCondition {
-    fn check_condition(&self, delta_time: f32) -> bool;
+    /// Called once, before the first check, e.g. to register for signals.
+    fn initialize(&mut self, _world: &mut ChaosWorld) {}
+
+    fn check_condition(&mut self, world: &ChaosWorld, delta_time: f32) -> bool;
 }
 
 pub struct AndCondition {
     conditions: Vec<Box<dyn TriggerCondition>>,
 }
 
+impl AndCondition {
+    pub fn new(conditions: Vec<Box<dyn TriggerCondition>>) -> Self {
+        Self { conditions }
+    }
+}
+
 impl TriggerCondition for AndCondition {
-    fn check_condition(&self, delta_time: f32) -> bool {
-        for condition in &self.conditions {
-            if !condition.check_condition(delta_time) {
-                return false;
-            }
+    fn initialize(&mut self, world: &mut ChaosWorld) {
+        for condition in &mut self.conditions {
+            condition.initialize(world);
         }
-        true
+    }
+
+    fn check_condition(&mut self, world: &ChaosWorld, delta_time: f32) -> bool {
+        let mut result = true;
+        for condition in &mut self.conditions {
+            result &= condition.check_condition(world, delta_time);
+        }
+        result
     }
 }
 
@@ -21,13 +48,310 @@ pub struct OrCondition {
     conditions: Vec<Box<dyn TriggerCondition>>,
 }
 
+impl OrCondition {
+    pub fn new(conditions: Vec<Box<dyn TriggerCondition>>) -> Self {
+        Self { conditions }
+    }
+}
+
 impl TriggerCondition for OrCondition {
-    fn check_condition(&self, delta_time: f32) -> bool {
-        for condition in &self.conditions {
-            if condition.check_condition(delta_time) {
-                return true;
-            }
+    fn initialize(&mut self, world: &mut ChaosWorld) {
+        for condition in &mut self.conditions {
+            condition.initialize(world);
+        }
+    }
+
+    fn check_condition(&mut self, world: &ChaosWorld, delta_time: f32) -> bool {
+        let mut result = false;
+        for condition in &mut self.conditions {
+            result |= condition.check_condition(world, delta_time);
+        }
+        result
+    }
+}
+
+pub struct NotCondition {
+    condition: Box<dyn TriggerCondition>,
+}
+
+impl NotCondition {
+    pub fn new(condition: Box<dyn TriggerCondition>) -> Self {
+        Self { condition }
+    }
+}
+
+impl TriggerCondition for NotCondition {
+    fn initialize(&mut self, world: &mut ChaosWorld) {
+        self.condition.initialize(world);
+    }
+
+    fn check_condition(&mut self, world: &ChaosWorld, delta_time: f32) -> bool {
+        !self.condition.check_condition(world, delta_time)
+    }
+}
+
+/// Becomes (and stays) true once `delay` seconds have passed since the first check.
+pub struct DelayCondition {
+    delay: f32,
+    elapsed: f32,
+}
+
+impl DelayCondition {
+    pub fn new(delay: f32) -> Self {
+        Self {
+            delay,
+            elapsed: 0.0,
+        }
+    }
+}
+
+impl TriggerCondition for DelayCondition {
+    fn check_condition(&mut self, _world: &ChaosWorld, delta_time: f32) -> bool {
+        self.elapsed += delta_time;
+        self.elapsed >= self.delay
+    }
+}
+
+/// True for one check every `interval` seconds.
+pub struct IntervalCondition {
+    interval: f32,
+    elapsed: f32,
+}
+
+impl IntervalCondition {
+    pub fn new(interval: f32) -> Self {
+        Self {
+            interval,
+            elapsed: 0.0,
+        }
+    }
+}
+
+impl TriggerCondition for IntervalCondition {
+    fn check_condition(&mut self, _world: &ChaosWorld, delta_time: f32) -> bool {
+        self.elapsed += delta_time;
+        if self.elapsed < self.interval {
+            return false;
+        }
+        // Keep the remainder so the interval doesn't drift with the frame rate, but don't
+        // try to catch up on several missed intervals after a long frame.
+        self.elapsed = (self.elapsed - self.interval).min(self.interval);
+        true
+    }
+}
+
+/// True while `entity` has a component of type `T`.
+pub struct HasComponentCondition<T: Component> {
+    entity: EntityID,
+    component: PhantomData<T>,
+}
+
+impl<T: Component> HasComponentCondition<T> {
+    pub fn new(entity: EntityID) -> Self {
+        Self {
+            entity,
+            component: PhantomData,
+        }
+    }
+}
+
+impl<T: Component> TriggerCondition for HasComponentCondition<T> {
+    fn check_condition(&mut self, world: &ChaosWorld, _delta_time: f32) -> bool {
+        world.get_component::<T>(self.entity).is_some()
+    }
+}
+
+/// True while `entity` has a component of type `T` for which `predicate` holds.
+pub struct ComponentPredicateCondition<T: Component> {
+    entity: EntityID,
+    predicate: Box<dyn Fn(&T) -> bool>,
+}
+
+impl<T: Component> ComponentPredicateCondition<T> {
+    pub fn new(entity: EntityID, predicate: impl Fn(&T) -> bool + 'static) -> Self {
+        Self {
+            entity,
+            predicate: Box::new(predicate),
+        }
+    }
+}
+
+impl<T: Component> TriggerCondition for ComponentPredicateCondition<T> {
+    fn check_condition(&mut self, world: &ChaosWorld, _delta_time: f32) -> bool {
+        world
+            .get_component::<T>(self.entity)
+            .is_some_and(|component| (self.predicate)(component))
+    }
+}
+
+/// True when a message for `key` (e.g. an input signal from `DeviceEventSystem::bind`)
+/// arrived since the previous check.
+pub struct SignalReceivedCondition {
+    key: TriggerEventKey,
+    receiver: Option<ChaosReceiver>,
+}
+
+impl SignalReceivedCondition {
+    pub fn new(key: TriggerEventKey) -> Self {
+        Self {
+            key,
+            receiver: None,
+        }
+    }
+}
+
+impl TriggerCondition for SignalReceivedCondition {
+    fn initialize(&mut self, world: &mut ChaosWorld) {
+        self.receiver = Some(world.register_for(self.key));
+    }
+
+    fn check_condition(&mut self, _world: &ChaosWorld, _delta_time: f32) -> bool {
+        let Some(receiver) = self.receiver.as_mut() else {
+            return false;
+        };
+
+        let mut received = false;
+        while receiver.receive().is_some() {
+            received = true;
         }
-        false
+        received
+    }
+}
+
+/// True on the check in which `entity` moves into the rectangle `min..=max`. The position
+/// is read from the entity's `T` component with `position`.
+pub struct EntityEnteredRegionCondition<T: Component> {
+    entity: EntityID,
+    min: Vec2,
+    max: Vec2,
+    position: Box<dyn Fn(&T) -> Vec2>,
+    inside: bool,
+}
+
+impl<T: Component> EntityEnteredRegionCondition<T> {
+    pub fn new(
+        entity: EntityID,
+        min: Vec2,
+        max: Vec2,
+        position: impl Fn(&T) -> Vec2 + 'static,
+    ) -> Self {
+        Self {
+            entity,
+            min,
+            max,
+            position: Box::new(position),
+            inside: false,
+        }
+    }
+}
+
+impl<T: Component> TriggerCondition for EntityEnteredRegionCondition<T> {
+    fn check_condition(&mut self, world: &ChaosWorld, _delta_time: f32) -> bool {
+        let inside = world
+            .get_component::<T>(self.entity)
+            .map(|component| (self.position)(component))
+            .is_some_and(|position| {
+                (self.min.x..=self.max.x).contains(&position.x)
+                    && (self.min.y..=self.max.y).contains(&position.y)
+            });
+        let entered = inside && !self.inside;
+        self.inside = inside;
+        entered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Position(Vec2);
+
+    #[test]
+    fn interval_fires_once_per_interval_and_delay_stays_true() {
+        let world = ChaosWorld::new();
+        let mut interval = IntervalCondition::new(1.0);
+        let mut delay = DelayCondition::new(1.0);
+
+        let interval_results: Vec<bool> = (0..5)
+            .map(|_| interval.check_condition(&world, 0.5))
+            .collect();
+        let delay_results: Vec<bool> = (0..3).map(|_| delay.check_condition(&world, 0.5)).collect();
+
+        assert_eq!(interval_results, vec![false, true, false, true, false]);
+        assert_eq!(delay_results, vec![false, true, true]);
+    }
+
+    #[test]
+    fn component_conditions_look_at_the_world() {
+        let mut world = ChaosWorld::new();
+        let entity = world.spawn().with(Position(Vec2::new(5.0, 0.0))).build();
+
+        let mut has_position = HasComponentCondition::<Position>::new(entity);
+        let mut far_right =
+            ComponentPredicateCondition::new(entity, |position: &Position| position.0.x > 3.0);
+        let mut not_far_right = NotCondition::new(Box::new(ComponentPredicateCondition::new(
+            entity,
+            |position: &Position| position.0.x > 3.0,
+        )));
+
+        assert!(has_position.check_condition(&world, 0.0));
+        assert!(far_right.check_condition(&world, 0.0));
+        assert!(!not_far_right.check_condition(&world, 0.0));
+
+        world.remove_component::<Position>(entity).unwrap();
+        assert!(!has_position.check_condition(&world, 0.0));
+        assert!(!far_right.check_condition(&world, 0.0));
+    }
+
+    #[test]
+    fn entered_region_is_true_only_when_entering() {
+        let mut world = ChaosWorld::new();
+        let entity = world.spawn().with(Position(Vec2::new(-5.0, 0.0))).build();
+        let mut entered = EntityEnteredRegionCondition::new(
+            entity,
+            Vec2::new(-1.0, -1.0),
+            Vec2::new(1.0, 1.0),
+            |position: &Position| position.0,
+        );
+        let mut move_to = |world: &mut ChaosWorld, x: f32| {
+            world.get_component_mut::<Position>(entity).unwrap().0.x = x;
+            entered.check_condition(world, 0.0)
+        };
+
+        assert!(!move_to(&mut world, -5.0));
+        assert!(move_to(&mut world, 0.0));
+        assert!(!move_to(&mut world, 0.5));
+        assert!(!move_to(&mut world, 5.0));
+        assert!(move_to(&mut world, 1.0));
+    }
+
+    struct CountingCondition {
+        checks: std::rc::Rc<std::cell::Cell<u32>>,
+        result: bool,
+    }
+
+    impl TriggerCondition for CountingCondition {
+        fn check_condition(&mut self, _world: &ChaosWorld, _delta_time: f32) -> bool {
+            self.checks.set(self.checks.get() + 1);
+            self.result
+        }
+    }
+
+    #[test]
+    fn and_or_check_every_condition() {
+        let world = ChaosWorld::new();
+        let checks = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counting = |result| {
+            Box::new(CountingCondition {
+                checks: checks.clone(),
+                result,
+            })
+        };
+        let mut and = AndCondition::new(vec![counting(false), counting(true)]);
+        let mut or = OrCondition::new(vec![counting(true), counting(false)]);
+
+        assert!(!and.check_condition(&world, 0.0));
+        assert!(or.check_condition(&world, 0.0));
+        assert_eq!(checks.get(), 4);
     }
 }
@@ -1,4 +1,5 @@
 pub mod conditions;
+pub mod script;
 pub mod system;
 pub mod trigger;
 pub mod trigger_event_key;
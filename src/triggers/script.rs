@@ -0,0 +1,715 @@
+//! Triggers declared in files, so they can be changed without recompiling.
+//!
+//! Trigger scripts are TOML files: every `[[trigger]]` table declares one trigger,
+//! followed by the `[[trigger.condition]]` and `[[trigger.action]]` tables that belong to
+//! it. The `id` of a condition or action picks the implementation registered under that
+//! name in a [`TriggerRegistry`], and all other keys of the table are passed to it as
+//! [`TriggerParams`].
+//!
+//! ```toml
+//! [[trigger]]
+//! name = "boss_fight"
+//! mode = "once"            # "repeat" (default), "once" or "rising_edge"
+//!
+//! [[trigger.condition]]
+//! id = "ship_in_area"
+//! min = [-10.0, -10.0]
+//! max = [10.0, 10.0]
+//!
+//! [[trigger.condition]]
+//! id = "asteroids_destroyed"
+//! count = 3
+//!
+//! [[trigger.action]]
+//! id = "spawn_boss"
+//! ```
+//!
+//! Instead of `mode`, a trigger can set `times = 3` to fire three times, or `cooldown = 1.5`
+//! to fire at most every one and a half seconds. Parameters can be any TOML value except
+//! dates and times.
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use chaos_communicator::message::ChaosMessageBuilder;
+use toml::{
+    Spanned,
+    de::{DeString, DeTable, DeValue},
+};
+
+use crate::{
+    ecs::world::ChaosWorld,
+    math::Vec2,
+    triggers::{
+        conditions::{
+            DelayCondition, IntervalCondition, SignalReceivedCondition, TriggerCondition,
+        },
+        trigger::{Trigger, TriggerBuilder, TriggerCallback, TriggerMode},
+        trigger_event_key::TriggerEventKey,
+    },
+};
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum TriggerScriptError {
+    Io {
+        file: PathBuf,
+        error: String,
+    },
+    Invalid {
+        file: PathBuf,
+        line: usize,
+        message: String,
+    },
+}
+
+impl Display for TriggerScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TriggerScriptError::Io { file, error } => {
+                write!(
+                    f,
+                    "Failed to read trigger script {}: {}",
+                    file.display(),
+                    error
+                )
+            }
+            TriggerScriptError::Invalid {
+                file,
+                line,
+                message,
+            } => write!(f, "{}:{}: {}", file.display(), line, message),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum TriggerValue {
+    String(String),
+    Number(f64),
+    Bool(bool),
+    Array(Vec<TriggerValue>),
+    Table(HashMap<String, TriggerValue>),
+}
+
+/// The keys of a condition or action table, handed to its registered factory.
+#[derive(Debug)]
+pub struct TriggerParams {
+    file: PathBuf,
+    // Line of the table header, for errors about missing keys.
+    line: usize,
+    values: HashMap<String, (TriggerValue, usize)>,
+    // Keys read by the factory; any other key is reported as unknown.
+    used: RefCell<HashSet<String>>,
+}
+
+impl TriggerParams {
+    fn new(file: &Path, line: usize) -> Self {
+        Self {
+            file: file.to_path_buf(),
+            line,
+            values: HashMap::new(),
+            used: RefCell::new(HashSet::new()),
+        }
+    }
+
+    /// An error pointing at `key`'s line, or at the table if `key` isn't set.
+    pub fn error(&self, key: &str, message: impl Into<String>) -> TriggerScriptError {
+        TriggerScriptError::Invalid {
+            file: self.file.clone(),
+            line: self.values.get(key).map_or(self.line, |(_, line)| *line),
+            message: message.into(),
+        }
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    pub fn get(&self, key: &str) -> Result<&TriggerValue, TriggerScriptError> {
+        self.used.borrow_mut().insert(key.to_string());
+        self.values
+            .get(key)
+            .map(|(value, _)| value)
+            .ok_or_else(|| self.error(key, format!("Missing parameter `{key}`")))
+    }
+
+    pub fn get_str(&self, key: &str) -> Result<&str, TriggerScriptError> {
+        match self.get(key)? {
+            TriggerValue::String(value) => Ok(value),
+            _ => Err(self.error(key, format!("`{key}` must be a string"))),
+        }
+    }
+
+    pub fn get_f32(&self, key: &str) -> Result<f32, TriggerScriptError> {
+        match self.get(key)? {
+            TriggerValue::Number(value) => Ok(*value as f32),
+            _ => Err(self.error(key, format!("`{key}` must be a number"))),
+        }
+    }
+
+    pub fn get_u32(&self, key: &str) -> Result<u32, TriggerScriptError> {
+        match self.get(key)? {
+            TriggerValue::Number(value)
+                if value.fract() == 0.0 && (1.0..=u32::MAX as f64).contains(value) =>
+            {
+                Ok(*value as u32)
+            }
+            _ => Err(self.error(key, format!("`{key}` must be a positive whole number"))),
+        }
+    }
+
+    pub fn get_bool(&self, key: &str) -> Result<bool, TriggerScriptError> {
+        match self.get(key)? {
+            TriggerValue::Bool(value) => Ok(*value),
+            _ => Err(self.error(key, format!("`{key}` must be true or false"))),
+        }
+    }
+
+    pub fn get_vec2(&self, key: &str) -> Result<Vec2, TriggerScriptError> {
+        if let TriggerValue::Array(values) = self.get(key)?
+            && let [TriggerValue::Number(x), TriggerValue::Number(y)] = values.as_slice()
+        {
+            return Ok(Vec2::new(*x as f32, *y as f32));
+        }
+        Err(self.error(key, format!("`{key}` must be an array of two numbers")))
+    }
+
+    fn check_all_used(&self) -> Result<(), TriggerScriptError> {
+        let used = self.used.borrow();
+        let unknown = self
+            .values
+            .iter()
+            .filter(|(key, _)| !used.contains(*key))
+            .min_by_key(|(_, (_, line))| *line);
+        match unknown {
+            Some((key, _)) => Err(self.error(key, format!("Unknown parameter `{key}`"))),
+            None => Ok(()),
+        }
+    }
+}
+
+pub type TriggerConditionFactory =
+    Box<dyn Fn(&TriggerParams) -> Result<Box<dyn TriggerCondition>, TriggerScriptError>>;
+pub type TriggerActionFactory =
+    Box<dyn Fn(&TriggerParams) -> Result<TriggerCallback, TriggerScriptError>>;
+
+/// The conditions and actions trigger scripts can use, by id.
+///
+/// A new registry knows the conditions `delay` and `interval` (with `seconds`) and `signal`
+/// (with the string `signal` sent by a binding), and the action `send_signal` (with
+/// `signal`).
+pub struct TriggerRegistry {
+    conditions: HashMap<String, TriggerConditionFactory>,
+    actions: HashMap<String, TriggerActionFactory>,
+}
+
+impl Default for TriggerRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TriggerRegistry {
+    pub fn new() -> Self {
+        let mut registry = Self {
+            conditions: HashMap::new(),
+            actions: HashMap::new(),
+        };
+        registry
+            .register_condition("delay", |params| {
+                Ok(Box::new(DelayCondition::new(params.get_f32("seconds")?)))
+            })
+            .register_condition("interval", |params| {
+                Ok(Box::new(IntervalCondition::new(params.get_f32("seconds")?)))
+            })
+            .register_condition("signal", |params| {
+                let key = TriggerEventKey::new(&params.get_str("signal")?);
+                Ok(Box::new(SignalReceivedCondition::new(key)))
+            })
+            .register_action("send_signal", |params| {
+                let key = TriggerEventKey::new(&params.get_str("signal")?);
+                Ok(Box::new(move |world: &mut ChaosWorld| {
+                    let message = ChaosMessageBuilder::new().build_for_event(key);
+                    if let Err(error) = world.try_send_message(message) {
+                        log::debug!("Trigger signal was not delivered: {}", error);
+                    }
+                }))
+            });
+        registry
+    }
+
+    pub fn register_condition<F>(&mut self, id: &str, factory: F) -> &mut Self
+    where
+        F: Fn(&TriggerParams) -> Result<Box<dyn TriggerCondition>, TriggerScriptError> + 'static,
+    {
+        self.conditions.insert(id.to_string(), Box::new(factory));
+        self
+    }
+
+    pub fn register_action<F>(&mut self, id: &str, factory: F) -> &mut Self
+    where
+        F: Fn(&TriggerParams) -> Result<TriggerCallback, TriggerScriptError> + 'static,
+    {
+        self.actions.insert(id.to_string(), Box::new(factory));
+        self
+    }
+
+    /// Reads and builds all triggers in the script at `path`.
+    pub fn load(&self, path: impl AsRef<Path>) -> Result<Vec<Trigger>, TriggerScriptError> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(|error| TriggerScriptError::Io {
+            file: path.to_path_buf(),
+            error: error.to_string(),
+        })?;
+        self.parse(&source, path)
+    }
+
+    /// Builds all triggers in `source`; `file` is only used in errors.
+    pub fn parse(
+        &self,
+        source: &str,
+        file: impl AsRef<Path>,
+    ) -> Result<Vec<Trigger>, TriggerScriptError> {
+        parse_script(source, file.as_ref())?
+            .iter()
+            .map(|table| self.build_trigger(table))
+            .collect()
+    }
+
+    fn build_trigger(&self, table: &TriggerTable) -> Result<Trigger, TriggerScriptError> {
+        let params = &table.params;
+        let mut builder = TriggerBuilder::new();
+        if params.contains("name") {
+            builder = builder.with_name(params.get_str("name")?);
+        }
+
+        let mut modes = ["mode", "times", "cooldown"]
+            .into_iter()
+            .filter(|key| params.contains(key));
+        if let (Some(first), Some(second)) = (modes.next(), modes.next()) {
+            return Err(params.error(
+                second,
+                format!("`{second}` can't be set together with `{first}`"),
+            ));
+        }
+        if params.contains("mode") {
+            let mode = match params.get_str("mode")? {
+                "repeat" => TriggerMode::Repeat,
+                "once" => TriggerMode::Once,
+                "rising_edge" => TriggerMode::RisingEdge,
+                other => {
+                    return Err(params.error(
+                        "mode",
+                        format!(
+                            "Unknown mode `{other}`, expected \"repeat\", \"once\" or \"rising_edge\""
+                        ),
+                    ));
+                }
+            };
+            builder = builder.with_mode(mode);
+        }
+        if params.contains("times") {
+            builder = builder.with_mode(TriggerMode::Times(params.get_u32("times")?));
+        }
+        if params.contains("cooldown") {
+            let cooldown = params.get_f32("cooldown")?;
+            if !cooldown.is_finite() || cooldown < 0.0 {
+                return Err(params.error(
+                    "cooldown",
+                    "`cooldown` must be a finite number of seconds, zero or more",
+                ));
+            }
+            builder = builder.with_mode(TriggerMode::Cooldown(cooldown));
+        }
+        params.check_all_used()?;
+
+        for condition in &table.conditions {
+            let factory = lookup(&self.conditions, condition, "condition")?;
+            builder = builder.with_condition(factory(condition)?);
+            condition.check_all_used()?;
+        }
+
+        if table.actions.is_empty() {
+            return Err(TriggerScriptError::Invalid {
+                file: params.file.clone(),
+                line: params.line,
+                message: "Trigger has no `[[trigger.action]]`".to_string(),
+            });
+        }
+        let mut actions = Vec::new();
+        for action in &table.actions {
+            let factory = lookup(&self.actions, action, "action")?;
+            actions.push(factory(action)?);
+            action.check_all_used()?;
+        }
+
+        Ok(builder
+            .with_callback(Box::new(move |world: &mut ChaosWorld| {
+                for action in &mut actions {
+                    action(world);
+                }
+            }))
+            .build())
+    }
+}
+
+fn lookup<'registry, T>(
+    factories: &'registry HashMap<String, T>,
+    params: &TriggerParams,
+    kind: &str,
+) -> Result<&'registry T, TriggerScriptError> {
+    let id = params.get_str("id")?;
+    factories
+        .get(id)
+        .ok_or_else(|| params.error("id", format!("Unknown {kind} `{id}`")))
+}
+
+struct TriggerTable {
+    params: TriggerParams,
+    conditions: Vec<TriggerParams>,
+    actions: Vec<TriggerParams>,
+}
+
+fn parse_script(source: &str, file: &Path) -> Result<Vec<TriggerTable>, TriggerScriptError> {
+    let script = Script { source, file };
+    let document = DeTable::parse(source).map_err(|error| {
+        script.error(error.span().map_or(0, |span| span.start), error.message())
+    })?;
+
+    let mut triggers = Vec::new();
+    for (key, value) in document.get_ref() {
+        if key.get_ref() != "trigger" {
+            return Err(script.error(
+                key.span().start,
+                format!(
+                    "Unknown key `{}`, expected `[[trigger]]` tables",
+                    key.get_ref()
+                ),
+            ));
+        }
+        for (offset, trigger) in script.tables(value, "trigger")? {
+            let mut table = TriggerTable {
+                params: script.params(offset),
+                conditions: Vec::new(),
+                actions: Vec::new(),
+            };
+            for (key, value) in trigger {
+                match key.get_ref().as_ref() {
+                    "condition" => {
+                        for condition in script.tables(value, "trigger.condition")? {
+                            table.conditions.push(script.table_params(condition)?);
+                        }
+                    }
+                    "action" => {
+                        for action in script.tables(value, "trigger.action")? {
+                            table.actions.push(script.table_params(action)?);
+                        }
+                    }
+                    _ => script.insert(&mut table.params, key, value)?,
+                }
+            }
+            triggers.push(table);
+        }
+    }
+    Ok(triggers)
+}
+
+// The script being parsed, to turn byte offsets into lines.
+struct Script<'a> {
+    source: &'a str,
+    file: &'a Path,
+}
+
+impl Script<'_> {
+    fn line(&self, offset: usize) -> usize {
+        let offset = offset.min(self.source.len());
+        self.source.as_bytes()[..offset]
+            .iter()
+            .filter(|byte| **byte == b'\n')
+            .count()
+            + 1
+    }
+
+    fn error(&self, offset: usize, message: impl Into<String>) -> TriggerScriptError {
+        TriggerScriptError::Invalid {
+            file: self.file.to_path_buf(),
+            line: self.line(offset),
+            message: message.into(),
+        }
+    }
+
+    fn params(&self, offset: usize) -> TriggerParams {
+        TriggerParams::new(self.file, self.line(offset))
+    }
+
+    // The tables of `[[name]]` with their offsets; an array of inline tables works as well.
+    fn tables<'v, 'i>(
+        &self,
+        value: &'v Spanned<DeValue<'i>>,
+        name: &str,
+    ) -> Result<Vec<(usize, &'v DeTable<'i>)>, TriggerScriptError> {
+        let not_tables = || {
+            self.error(
+                value.span().start,
+                format!("`{name}` must be `[[{name}]]` tables"),
+            )
+        };
+        let DeValue::Array(items) = value.get_ref() else {
+            return Err(not_tables());
+        };
+        items
+            .iter()
+            .map(|item| match item.get_ref() {
+                DeValue::Table(table) => Ok((item.span().start, table)),
+                _ => Err(not_tables()),
+            })
+            .collect()
+    }
+
+    fn table_params(
+        &self,
+        (offset, table): (usize, &DeTable),
+    ) -> Result<TriggerParams, TriggerScriptError> {
+        let mut params = self.params(offset);
+        for (key, value) in table {
+            self.insert(&mut params, key, value)?;
+        }
+        Ok(params)
+    }
+
+    fn insert(
+        &self,
+        params: &mut TriggerParams,
+        key: &Spanned<DeString>,
+        value: &Spanned<DeValue>,
+    ) -> Result<(), TriggerScriptError> {
+        let value = self.value(value)?;
+        params.values.insert(
+            key.get_ref().to_string(),
+            (value, self.line(key.span().start)),
+        );
+        Ok(())
+    }
+
+    fn value(&self, value: &Spanned<DeValue>) -> Result<TriggerValue, TriggerScriptError> {
+        let error = |message| self.error(value.span().start, message);
+        Ok(match value.get_ref() {
+            DeValue::String(text) => TriggerValue::String(text.to_string()),
+            DeValue::Integer(integer) => TriggerValue::Number(
+                i64::from_str_radix(integer.as_str(), integer.radix())
+                    .map_err(|_| error("Integer is out of range"))? as f64,
+            ),
+            DeValue::Float(float) => TriggerValue::Number(
+                float
+                    .as_str()
+                    .parse()
+                    .map_err(|_| error("Invalid number"))?,
+            ),
+            DeValue::Boolean(value) => TriggerValue::Bool(*value),
+            DeValue::Datetime(_) => return Err(error("Dates and times are not supported")),
+            DeValue::Array(items) => TriggerValue::Array(
+                items
+                    .iter()
+                    .map(|item| self.value(item))
+                    .collect::<Result<_, _>>()?,
+            ),
+            DeValue::Table(table) => TriggerValue::Table(
+                table
+                    .iter()
+                    .map(|(key, item)| Ok((key.get_ref().to_string(), self.value(item)?)))
+                    .collect::<Result<_, _>>()?,
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Spawned;
+
+    fn registry() -> TriggerRegistry {
+        let mut registry = TriggerRegistry::new();
+        registry
+            .register_condition("always", |_| Ok(Box::new(DelayCondition::new(0.0))))
+            .register_action("spawn", |params| {
+                let count = params.get_u32("count")?;
+                Ok(Box::new(move |world: &mut ChaosWorld| {
+                    for _ in 0..count {
+                        world.spawn().with(Spawned).build();
+                    }
+                }))
+            });
+        registry
+    }
+
+    fn error_line(source: &str) -> (usize, String) {
+        match registry().parse(source, "level.toml") {
+            Err(TriggerScriptError::Invalid {
+                file,
+                line,
+                message,
+            }) => {
+                assert_eq!(file, PathBuf::from("level.toml"));
+                (line, message)
+            }
+            Err(error) => panic!("unexpected error {error}"),
+            Ok(_) => panic!("script should not load"),
+        }
+    }
+
+    #[test]
+    fn builds_triggers_from_a_script() {
+        let source = r#"
+            # Spawns two entities after a second, once.
+            [[trigger]]
+            name = "wave"
+            mode = "once"
+
+            [[trigger.condition]]
+            id = "delay"
+            seconds = 1.0
+
+            [[trigger.action]]
+            id = "spawn"    # comment after a value
+            count = 2
+        "#;
+        let mut world = ChaosWorld::new();
+
+        let mut triggers = registry().parse(source, "level.toml").unwrap();
+        assert_eq!(triggers.len(), 1);
+        let trigger = &mut triggers[0];
+        assert!(!trigger.update(&mut world, 0.5));
+        assert!(trigger.update(&mut world, 0.5));
+
+        let state = trigger.state();
+        assert_eq!(state.name.as_deref(), Some("wave"));
+        assert_eq!(state.mode, TriggerMode::Once);
+        assert!(state.finished);
+        assert_eq!(
+            world.get_all_components_of_type::<Spawned>().unwrap().len(),
+            2
+        );
+    }
+
+    #[test]
+    fn parses_toml_values() {
+        let source = r#"
+            [[trigger]]
+            condition = [{ id = "always" }]
+
+            [[trigger.action]]
+            id = 'spawn'
+            text = """
+two
+lines"""
+            count = 1_000
+            names = ["a", "b"]
+            area = { min = [0, 0], max = [1.5, 2] }
+        "#;
+        let triggers = parse_script(source, Path::new("level.toml")).unwrap();
+        assert_eq!(triggers[0].conditions[0].get_str("id"), Ok("always"));
+
+        let action = &triggers[0].actions[0];
+        assert_eq!(action.get_str("id"), Ok("spawn"));
+        assert_eq!(action.get_str("text"), Ok("two\nlines"));
+        assert_eq!(action.get_u32("count"), Ok(1000));
+        assert_eq!(
+            action.get("names"),
+            Ok(&TriggerValue::Array(vec![
+                TriggerValue::String("a".to_string()),
+                TriggerValue::String("b".to_string())
+            ]))
+        );
+        let number = TriggerValue::Number;
+        assert_eq!(
+            action.get("area"),
+            Ok(&TriggerValue::Table(HashMap::from([
+                (
+                    "min".to_string(),
+                    TriggerValue::Array(vec![number(0.0), number(0.0)])
+                ),
+                (
+                    "max".to_string(),
+                    TriggerValue::Array(vec![number(1.5), number(2.0)])
+                ),
+            ])))
+        );
+        assert_eq!(action.line, 5);
+        assert_eq!(action.values["count"].1, 10);
+    }
+
+    #[test]
+    fn errors_name_the_line() {
+        let unknown_condition = "[[trigger]]\n[[trigger.condition]]\nid = \"teleport\"\n[[trigger.action]]\nid = \"spawn\"\ncount = 1";
+        assert_eq!(
+            error_line(unknown_condition),
+            (3, "Unknown condition `teleport`".to_string())
+        );
+
+        let missing_parameter = "[[trigger]]\n\n[[trigger.action]]\nid = \"spawn\"";
+        assert_eq!(
+            error_line(missing_parameter),
+            (3, "Missing parameter `count`".to_string())
+        );
+
+        let wrong_type =
+            "[[trigger]]\ntimes = \"two\"\n[[trigger.action]]\nid = \"spawn\"\ncount = 1";
+        assert_eq!(
+            error_line(wrong_type),
+            (2, "`times` must be a positive whole number".to_string())
+        );
+
+        let zero = "[[trigger]]\ntimes = 0\n[[trigger.action]]\nid = \"spawn\"\ncount = 1";
+        assert_eq!(
+            error_line(zero),
+            (2, "`times` must be a positive whole number".to_string())
+        );
+
+        for cooldown in ["-1", "nan", "inf"] {
+            let source = format!(
+                "[[trigger]]\ncooldown = {cooldown}\n[[trigger.action]]\nid = \"spawn\"\ncount = 1"
+            );
+            assert_eq!(
+                error_line(&source),
+                (
+                    2,
+                    "`cooldown` must be a finite number of seconds, zero or more".to_string()
+                )
+            );
+        }
+
+        let conflicting_modes = "[[trigger]]\nmode = \"once\"\ncooldown = 2\n[[trigger.action]]\nid = \"spawn\"\ncount = 1";
+        assert_eq!(
+            error_line(conflicting_modes),
+            (
+                3,
+                "`cooldown` can't be set together with `mode`".to_string()
+            )
+        );
+
+        let unknown_parameter =
+            "[[trigger]]\n[[trigger.action]]\nid = \"spawn\"\ncount = 1\nspeed = 2";
+        assert_eq!(
+            error_line(unknown_parameter),
+            (5, "Unknown parameter `speed`".to_string())
+        );
+
+        assert_eq!(error_line("[[trigger]]").0, 1);
+        assert_eq!(error_line("id = \"spawn\"").0, 1);
+        assert_eq!(error_line("[[trigger]]\n[trigger.action]").0, 2);
+        assert_eq!(error_line("[[trigger]]\nmode = once").0, 2);
+    }
+
+    #[test]
+    fn missing_file_is_an_io_error() {
+        let result = registry().load("does/not/exist.toml");
+
+        assert!(matches!(result, Err(TriggerScriptError::Io { .. })));
+    }
+}
@@ -1,5 +1,10 @@
 use crate::ecs::{system::ChaosSystem, world::ChaosWorld};
-use crate::triggers::trigger::Trigger;
+use std::path::Path;
+
+use crate::triggers::{
+    script::{TriggerRegistry, TriggerScriptError},
+    trigger::{Trigger, TriggerHandle},
+};
 
 pub struct TriggerSystem {
     triggers: Vec<Trigger>,
@@ -12,8 +17,25 @@ impl TriggerSystem {
         }
     }
 
-    pub fn add_trigger(&mut self, trigger: Trigger) {
+    /// Adds a trigger; the returned handle enables, disables or removes it later.
+    pub fn add_trigger(&mut self, trigger: Trigger) -> TriggerHandle {
+        let handle = trigger.handle();
         self.triggers.push(trigger);
+        handle
+    }
+
+    /// Adds all triggers of the script at `path`, see [`crate::triggers::script`]. Nothing is
+    /// added if the script has an error.
+    pub fn load_triggers(
+        &mut self,
+        path: impl AsRef<Path>,
+        registry: &TriggerRegistry,
+    ) -> Result<Vec<TriggerHandle>, TriggerScriptError> {
+        let triggers = registry.load(path)?;
+        Ok(triggers
+            .into_iter()
+            .map(|trigger| self.add_trigger(trigger))
+            .collect())
     }
 
     pub fn get_triggers(&self) -> &Vec<Trigger> {
@@ -29,8 +51,10 @@ impl ChaosSystem for TriggerSystem {
     fn update(&mut self, world: &mut ChaosWorld) -> Result<(), &'static str> {
         let delta_time = world.get_time().delta_time();
         for trigger in &mut self.triggers {
-            trigger.update(delta_time);
+            trigger.update(world, delta_time);
         }
+        // Also drops triggers removed through a handle while they weren't being updated.
+        self.triggers.retain(|trigger| !trigger.is_finished());
         Ok(())
     }
 }
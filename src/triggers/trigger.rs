@@ -1,32 +1,170 @@
-use crate::triggers::conditions::TriggerCondition;
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{ecs::world::ChaosWorld, triggers::conditions::TriggerCondition};
+
+pub type TriggerCallback = Box<dyn FnMut(&mut ChaosWorld)>;
+
+/// When a trigger whose conditions hold fires its callback.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TriggerMode {
+    /// Every update while the conditions hold.
+    #[default]
+    Repeat,
+    /// Fire once, then the trigger is removed.
+    Once,
+    /// Fire the given number of times, then the trigger is removed.
+    Times(u32),
+    /// Fire at most once per the given number of seconds.
+    Cooldown(f32),
+    /// Fire only in the update in which the conditions start to hold.
+    RisingEdge,
+}
+
+/// Runtime state of a trigger, for debugging and for [`TriggerHandle`]s.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TriggerState {
+    pub name: Option<String>,
+    pub mode: TriggerMode,
+    pub enabled: bool,
+    // Whether all conditions held in the last update the trigger was checked in.
+    pub conditions_met: bool,
+    pub fire_count: u32,
+    pub cooldown_remaining: f32,
+    // Set when the trigger is done (fired its last time or was removed through a handle);
+    // the `TriggerSystem` drops it on its next update.
+    pub finished: bool,
+}
+
+/// Controls a trigger after it was added to the `TriggerSystem`. Handles can be cloned and
+/// kept anywhere, e.g. in a trigger callback; they stay valid after the trigger was removed.
+#[derive(Clone, Debug)]
+pub struct TriggerHandle {
+    state: Rc<RefCell<TriggerState>>,
+}
+
+impl TriggerHandle {
+    /// Disabled triggers are not checked at all, so their conditions don't advance either.
+    pub fn enable(&self) {
+        self.state.borrow_mut().enabled = true;
+    }
+
+    pub fn disable(&self) {
+        self.state.borrow_mut().enabled = false;
+    }
+
+    pub fn remove(&self) {
+        self.state.borrow_mut().finished = true;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.state.borrow().enabled
+    }
+
+    /// Whether the trigger is still in the system, i.e. hasn't finished or been removed.
+    pub fn is_active(&self) -> bool {
+        !self.state.borrow().finished
+    }
+
+    pub fn state(&self) -> TriggerState {
+        self.state.borrow().clone()
+    }
+}
 
 pub struct Trigger {
     conditions: Vec<Box<dyn TriggerCondition>>,
-    callback: Box<dyn FnMut()>,
+    callback: TriggerCallback,
+    initialized: bool,
+    state: Rc<RefCell<TriggerState>>,
 }
 
 impl Trigger {
-    pub fn new(conditions: Vec<Box<dyn TriggerCondition>>, callback: Box<dyn FnMut()>) -> Self {
+    pub fn new(conditions: Vec<Box<dyn TriggerCondition>>, callback: TriggerCallback) -> Self {
         Trigger {
             conditions,
             callback,
+            initialized: false,
+            state: Rc::new(RefCell::new(TriggerState {
+                enabled: true,
+                ..Default::default()
+            })),
         }
     }
 
-    pub fn update(&mut self, delta_time: f32) {
-        for condition in &self.conditions {
-            if !condition.check_condition(delta_time) {
-                return;
+    pub fn handle(&self) -> TriggerHandle {
+        TriggerHandle {
+            state: self.state.clone(),
+        }
+    }
+
+    pub fn state(&self) -> TriggerState {
+        self.state.borrow().clone()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.state.borrow().finished
+    }
+
+    /// Checks the conditions and runs the callback if all of them hold and the trigger's
+    /// mode allows it. Returns whether the callback ran.
+    pub fn update(&mut self, world: &mut ChaosWorld, delta_time: f32) -> bool {
+        {
+            let state = self.state.borrow();
+            if state.finished || !state.enabled {
+                return false;
+            }
+        }
+
+        if !self.initialized {
+            for condition in &mut self.conditions {
+                condition.initialize(world);
             }
+            self.initialized = true;
         }
 
-        (self.callback)();
+        // Every condition is checked, even after one failed, so stateful conditions (timers,
+        // edge detection) see every update.
+        let mut fulfilled = true;
+        for condition in &mut self.conditions {
+            fulfilled &= condition.check_condition(world, delta_time);
+        }
+
+        let fire = {
+            let mut state = self.state.borrow_mut();
+            let was_met = state.conditions_met;
+            state.conditions_met = fulfilled;
+            state.cooldown_remaining = (state.cooldown_remaining - delta_time).max(0.0);
+
+            let fire = fulfilled
+                && match state.mode {
+                    TriggerMode::Repeat | TriggerMode::Once | TriggerMode::Times(_) => true,
+                    TriggerMode::Cooldown(_) => state.cooldown_remaining <= 0.0,
+                    TriggerMode::RisingEdge => !was_met,
+                };
+            if fire {
+                state.fire_count += 1;
+                match state.mode {
+                    TriggerMode::Once => state.finished = true,
+                    TriggerMode::Times(times) => state.finished = state.fire_count >= times,
+                    TriggerMode::Cooldown(cooldown) => state.cooldown_remaining = cooldown,
+                    TriggerMode::Repeat | TriggerMode::RisingEdge => (),
+                }
+            }
+            fire
+        };
+
+        // The state isn't borrowed here, so the callback may use handles, even its own.
+        if fire {
+            (self.callback)(world);
+        }
+        fire
     }
 }
 
 pub struct TriggerBuilder {
     conditions: Vec<Box<dyn TriggerCondition>>,
-    callback: Option<Box<dyn FnMut()>>,
+    callback: Option<TriggerCallback>,
+    mode: TriggerMode,
+    name: Option<String>,
 }
 
 impl TriggerBuilder {
@@ -34,6 +172,8 @@ impl TriggerBuilder {
         TriggerBuilder {
             conditions: Vec::new(),
             callback: None,
+            mode: TriggerMode::default(),
+            name: None,
         }
     }
     pub fn with_condition(mut self, condition: Box<dyn TriggerCondition>) -> Self {
@@ -41,15 +181,191 @@ impl TriggerBuilder {
         self
     }
 
-    pub fn with_callback(mut self, callback: Box<dyn FnMut()>) -> Self {
+    pub fn with_callback(mut self, callback: TriggerCallback) -> Self {
         self.callback = Some(callback);
         self
     }
 
+    pub fn with_mode(mut self, mode: TriggerMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Names the trigger in its `TriggerState`, for debugging.
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
     pub fn build(self) -> Trigger {
-        Trigger::new(
+        let trigger = Trigger::new(
             self.conditions,
             self.callback.expect("Callback must be set"),
-        )
+        );
+        {
+            let mut state = trigger.state.borrow_mut();
+            state.mode = self.mode;
+            state.name = self.name;
+        }
+        trigger
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ecs::system::ChaosSystem,
+        triggers::{
+            conditions::{DelayCondition, SignalReceivedCondition},
+            system::TriggerSystem,
+            trigger_event_key::TriggerEventKey,
+        },
+    };
+    use chaos_communicator::message::ChaosMessageBuilder;
+
+    struct Spawned;
+
+    #[test]
+    fn callback_can_change_the_world_once_conditions_hold() {
+        let mut world = ChaosWorld::new();
+        let mut trigger = TriggerBuilder::new()
+            .with_condition(Box::new(DelayCondition::new(1.0)))
+            .with_callback(Box::new(|world: &mut ChaosWorld| {
+                world.spawn().with(Spawned).build();
+            }))
+            .build();
+
+        assert!(!trigger.update(&mut world, 0.5));
+        assert!(trigger.update(&mut world, 0.5));
+        assert_eq!(
+            world.get_all_components_of_type::<Spawned>().unwrap().len(),
+            1
+        );
+    }
+
+    #[test]
+    fn signal_condition_is_registered_when_the_trigger_first_runs() {
+        let mut world = ChaosWorld::new();
+        let key = TriggerEventKey::new(&"boss");
+        let mut system = TriggerSystem::new();
+        system.add_trigger(
+            TriggerBuilder::new()
+                .with_condition(Box::new(SignalReceivedCondition::new(key)))
+                .with_callback(Box::new(|world: &mut ChaosWorld| {
+                    world.spawn().with(Spawned).build();
+                }))
+                .build(),
+        );
+
+        system.update(&mut world).unwrap();
+        world.send_message(ChaosMessageBuilder::new().build_for_event(key));
+        system.update(&mut world).unwrap();
+        system.update(&mut world).unwrap();
+
+        assert_eq!(
+            world.get_all_components_of_type::<Spawned>().unwrap().len(),
+            1
+        );
+    }
+
+    fn fire_counts(mode: TriggerMode, updates: usize) -> Vec<bool> {
+        let mut world = ChaosWorld::new();
+        let mut trigger = TriggerBuilder::new()
+            .with_mode(mode)
+            .with_callback(Box::new(|_: &mut ChaosWorld| {}))
+            .build();
+        (0..updates)
+            .map(|_| trigger.update(&mut world, 0.5))
+            .collect()
+    }
+
+    #[test]
+    fn modes_limit_how_often_the_callback_runs() {
+        assert_eq!(fire_counts(TriggerMode::Repeat, 3), vec![true, true, true]);
+        assert_eq!(fire_counts(TriggerMode::Once, 3), vec![true, false, false]);
+        assert_eq!(
+            fire_counts(TriggerMode::Times(2), 3),
+            vec![true, true, false]
+        );
+        assert_eq!(
+            fire_counts(TriggerMode::Cooldown(1.0), 5),
+            vec![true, false, true, false, true]
+        );
+        assert_eq!(
+            fire_counts(TriggerMode::RisingEdge, 3),
+            vec![true, false, false]
+        );
+    }
+
+    #[test]
+    fn rising_edge_fires_again_after_conditions_stopped_holding() {
+        let mut world = ChaosWorld::new();
+        let entity = world.spawn().with(Spawned).build();
+        let mut trigger = TriggerBuilder::new()
+            .with_mode(TriggerMode::RisingEdge)
+            .with_condition(Box::new(
+                crate::triggers::conditions::HasComponentCondition::<Spawned>::new(entity),
+            ))
+            .with_callback(Box::new(|_: &mut ChaosWorld| {}))
+            .build();
+
+        assert!(trigger.update(&mut world, 0.0));
+        assert!(!trigger.update(&mut world, 0.0));
+        world.remove_component::<Spawned>(entity).unwrap();
+        assert!(!trigger.update(&mut world, 0.0));
+        world.add_component(entity, Spawned).unwrap();
+        assert!(trigger.update(&mut world, 0.0));
+        assert_eq!(trigger.state().fire_count, 2);
+    }
+
+    #[test]
+    fn handles_enable_disable_and_remove_triggers() {
+        let mut world = ChaosWorld::new();
+        let mut system = TriggerSystem::new();
+        let handle = system.add_trigger(
+            TriggerBuilder::new()
+                .with_callback(Box::new(|world: &mut ChaosWorld| {
+                    world.spawn().with(Spawned).build();
+                }))
+                .build(),
+        );
+        let spawned = |world: &ChaosWorld| {
+            world
+                .get_all_components_of_type::<Spawned>()
+                .map_or(0, |components| components.len())
+        };
+
+        handle.disable();
+        system.update(&mut world).unwrap();
+        assert_eq!(spawned(&world), 0);
+
+        handle.enable();
+        system.update(&mut world).unwrap();
+        assert_eq!(spawned(&world), 1);
+        assert_eq!(handle.state().fire_count, 1);
+
+        handle.remove();
+        system.update(&mut world).unwrap();
+        assert_eq!(spawned(&world), 1);
+        assert!(!handle.is_active());
+        assert!(system.get_triggers().is_empty());
+    }
+
+    #[test]
+    fn finished_triggers_are_dropped_by_the_system() {
+        let mut world = ChaosWorld::new();
+        let mut system = TriggerSystem::new();
+        let handle = system.add_trigger(
+            TriggerBuilder::new()
+                .with_mode(TriggerMode::Once)
+                .with_callback(Box::new(|_: &mut ChaosWorld| {}))
+                .build(),
+        );
+
+        system.update(&mut world).unwrap();
+
+        assert!(!handle.is_active());
+        assert!(system.get_triggers().is_empty());
     }
 }
@@ -0,0 +1,365 @@
+use std::hash::Hash;
+
+use crate::{
+    ecs::{EntityID, component::Component, world::ChaosWorld},
+    math::{Vec2, Vec3, Vec4, color::LinearRgba, quaternion::Quaternion},
+    triggers::trigger_event_key::TriggerEventKey,
+    tween::easing::Easing,
+};
+
+/// A value a [`Tween`] can animate. Colors are tweened as `LinearRgba`, so they blend in
+/// linear light.
+pub trait Tweenable: Clone + 'static {
+    fn interpolate(from: &Self, to: &Self, t: f32) -> Self;
+}
+
+impl Tweenable for f32 {
+    fn interpolate(from: &Self, to: &Self, t: f32) -> Self {
+        from + (to - from) * t
+    }
+}
+
+impl Tweenable for Vec2 {
+    fn interpolate(from: &Self, to: &Self, t: f32) -> Self {
+        Vec2::lerp(from, to, t)
+    }
+}
+
+impl Tweenable for Vec3 {
+    fn interpolate(from: &Self, to: &Self, t: f32) -> Self {
+        Vec3::lerp(from, to, t)
+    }
+}
+
+impl Tweenable for Vec4 {
+    fn interpolate(from: &Self, to: &Self, t: f32) -> Self {
+        Vec4::lerp(from, to, t)
+    }
+}
+
+impl Tweenable for LinearRgba {
+    fn interpolate(from: &Self, to: &Self, t: f32) -> Self {
+        LinearRgba::lerp(from, to, t)
+    }
+}
+
+impl Tweenable for Quaternion {
+    fn interpolate(from: &Self, to: &Self, t: f32) -> Self {
+        Quaternion::slerp(*from, *to, t)
+    }
+}
+
+/// Something that sets a state of an entity for every point in time of a fixed duration.
+/// Played by an [`Animation`].
+pub trait Tweening {
+    fn duration(&self) -> f32;
+
+    /// Sets the state at `time` seconds, `0.0..=duration()`.
+    fn apply(&mut self, world: &mut ChaosWorld, entity: EntityID, time: f32);
+}
+
+pub type TweenSetter<C, V> = Box<dyn Fn(&mut C, V)>;
+
+/// Animates one field of the entity's `C` component from `from` to `to`.
+///
+/// ```rust
+/// use chaos_engine::{math::Vec2, tween::{animation::Tween, easing::Easing}};
+///
+/// struct Position(Vec2);
+///
+/// let slide = Tween::new(Vec2::zero(), Vec2::new(10.0, 0.0), 0.5, |position: &mut Position, value| {
+///     position.0 = value
+/// })
+/// .with_easing(Easing::QuadOut);
+/// ```
+pub struct Tween<C: Component, V: Tweenable> {
+    from: V,
+    to: V,
+    duration: f32,
+    easing: Easing,
+    set: TweenSetter<C, V>,
+}
+
+impl<C: Component, V: Tweenable> Tween<C, V> {
+    pub fn new(from: V, to: V, duration: f32, set: impl Fn(&mut C, V) + 'static) -> Self {
+        Self {
+            from,
+            to,
+            duration: duration.max(0.0),
+            easing: Easing::Linear,
+            set: Box::new(set),
+        }
+    }
+
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+impl<C: Component, V: Tweenable> Tweening for Tween<C, V> {
+    fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    fn apply(&mut self, world: &mut ChaosWorld, entity: EntityID, time: f32) {
+        let progress = if self.duration > 0.0 {
+            time / self.duration
+        } else {
+            1.0
+        };
+        let value = V::interpolate(&self.from, &self.to, self.easing.apply(progress));
+        if let Some(component) = world.get_component_mut::<C>(entity) {
+            (self.set)(component, value);
+        }
+    }
+}
+
+/// A pause inside a [`Sequence`].
+pub struct Wait(pub f32);
+
+impl Tweening for Wait {
+    fn duration(&self) -> f32 {
+        self.0.max(0.0)
+    }
+
+    fn apply(&mut self, _world: &mut ChaosWorld, _entity: EntityID, _time: f32) {}
+}
+
+/// Plays tweens one after another.
+#[derive(Default)]
+pub struct Sequence {
+    steps: Vec<Box<dyn Tweening>>,
+}
+
+impl Sequence {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn then(mut self, step: impl Tweening + 'static) -> Self {
+        self.steps.push(Box::new(step));
+        self
+    }
+
+    pub fn then_wait(self, seconds: f32) -> Self {
+        self.then(Wait(seconds))
+    }
+}
+
+impl Tweening for Sequence {
+    fn duration(&self) -> f32 {
+        self.steps.iter().map(|step| step.duration()).sum()
+    }
+
+    fn apply(&mut self, world: &mut ChaosWorld, entity: EntityID, time: f32) {
+        // Earlier steps are applied at their end, so a long frame that skips over a step
+        // still leaves its final state behind.
+        let mut start = 0.0;
+        for step in &mut self.steps {
+            let duration = step.duration();
+            if time < start + duration {
+                step.apply(world, entity, time - start);
+                return;
+            }
+            step.apply(world, entity, duration);
+            start += duration;
+        }
+    }
+}
+
+/// Animates several tweens at the same time; lasts as long as the longest.
+#[derive(Default)]
+pub struct Parallel {
+    tracks: Vec<Box<dyn Tweening>>,
+}
+
+impl Parallel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, track: impl Tweening + 'static) -> Self {
+        self.tracks.push(Box::new(track));
+        self
+    }
+}
+
+impl Tweening for Parallel {
+    fn duration(&self) -> f32 {
+        self.tracks
+            .iter()
+            .map(|track| track.duration())
+            .fold(0.0, f32::max)
+    }
+
+    fn apply(&mut self, world: &mut ChaosWorld, entity: EntityID, time: f32) {
+        for track in &mut self.tracks {
+            let time = time.min(track.duration());
+            track.apply(world, entity, time);
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TweenRepeat {
+    /// Play once, then finish.
+    #[default]
+    Once,
+    /// Start over from the beginning after every pass.
+    Loop,
+    /// Play forwards, then backwards, and so on.
+    PingPong,
+}
+
+pub type TweenCallback = Box<dyn FnMut(&mut ChaosWorld, EntityID)>;
+
+/// What an [`Animation`] does when it finishes.
+pub enum TweenAction {
+    /// Sends a message for the key, with the animated entity as the `entity_id` param.
+    Signal(TriggerEventKey),
+    Callback(TweenCallback),
+}
+
+/// Plays a [`Tweening`] on the entity it is attached to through an [`Animator`].
+pub struct Animation {
+    tweening: Box<dyn Tweening>,
+    repeat: TweenRepeat,
+    // Passes to play before finishing, `None` for forever. A ping-pong pass is one way.
+    passes: Option<u32>,
+    passes_done: u32,
+    elapsed: f32,
+    backwards: bool,
+    paused: bool,
+    finished: bool,
+    pub(crate) on_complete: Option<TweenAction>,
+}
+
+impl Animation {
+    pub fn new(tweening: impl Tweening + 'static) -> Self {
+        Self {
+            tweening: Box::new(tweening),
+            repeat: TweenRepeat::Once,
+            passes: Some(1),
+            passes_done: 0,
+            elapsed: 0.0,
+            backwards: false,
+            paused: false,
+            finished: false,
+            on_complete: None,
+        }
+    }
+
+    /// Repeats forever unless limited with [`Animation::times`].
+    pub fn with_repeat(mut self, repeat: TweenRepeat) -> Self {
+        self.repeat = repeat;
+        self.passes = match repeat {
+            TweenRepeat::Once => Some(1),
+            TweenRepeat::Loop | TweenRepeat::PingPong => None,
+        };
+        self
+    }
+
+    /// Finishes after `passes` passes; for ping-pong, there and back are two passes.
+    pub fn times(mut self, passes: u32) -> Self {
+        self.passes = Some(passes.max(1));
+        self
+    }
+
+    /// Sends a message for `signal` when the animation finishes; receive it with
+    /// `ChaosWorld::register_for_trigger(signal)`.
+    pub fn with_signal<T: Hash>(mut self, signal: &T) -> Self {
+        self.on_complete = Some(TweenAction::Signal(TriggerEventKey::new(signal)));
+        self
+    }
+
+    pub fn with_callback(
+        mut self,
+        callback: impl FnMut(&mut ChaosWorld, EntityID) + 'static,
+    ) -> Self {
+        self.on_complete = Some(TweenAction::Callback(Box::new(callback)));
+        self
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    // Advances the animation and applies its state. Returns whether it finished in this
+    // step.
+    pub(crate) fn advance(
+        &mut self,
+        world: &mut ChaosWorld,
+        entity: EntityID,
+        delta_time: f32,
+    ) -> bool {
+        if self.paused || self.finished {
+            return false;
+        }
+
+        let duration = self.tweening.duration();
+        self.elapsed += delta_time;
+        while self.elapsed >= duration && !self.finished {
+            self.passes_done += 1;
+            if self.passes.is_some_and(|passes| self.passes_done >= passes) || duration <= 0.0 {
+                self.elapsed = duration;
+                self.finished = true;
+            } else {
+                self.elapsed -= duration;
+                if self.repeat == TweenRepeat::PingPong {
+                    self.backwards = !self.backwards;
+                }
+            }
+        }
+
+        let time = if self.backwards {
+            duration - self.elapsed
+        } else {
+            self.elapsed
+        };
+        self.tweening.apply(world, entity, time);
+        self.finished
+    }
+}
+
+/// Component playing the [`Animation`]s of its entity, driven by the `TweenSystem`.
+/// Finished animations are removed.
+#[derive(Default)]
+pub struct Animator {
+    pub(crate) animations: Vec<Animation>,
+}
+
+impl Animator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, animation: Animation) -> Self {
+        self.play(animation);
+        self
+    }
+
+    pub fn play(&mut self, animation: Animation) {
+        self.animations.push(animation);
+    }
+
+    pub fn animations_mut(&mut self) -> impl Iterator<Item = &mut Animation> {
+        self.animations.iter_mut()
+    }
+
+    pub fn is_playing(&self) -> bool {
+        !self.animations.is_empty()
+    }
+
+    pub fn stop_all(&mut self) {
+        self.animations.clear();
+    }
+}
@@ -0,0 +1,147 @@
+use std::f32::consts::PI;
+
+/// Maps the linear progress of a tween (`0.0..=1.0`) to the progress used for
+/// interpolation. `In` variants start slowly, `Out` variants end slowly. `Back` and
+/// `Elastic` overshoot, so they produce values outside `0.0..=1.0`.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum Easing {
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticOut,
+    BounceOut,
+    Custom(fn(f32) -> f32),
+}
+
+// Overshoot of the `Back` easings, the customary value for a 10% overshoot.
+const BACK_OVERSHOOT: f32 = 1.70158;
+
+impl Easing {
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::QuadInOut => in_out(t, |t| t * t),
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => in_out(t, |t| t * t * t),
+            Easing::SineIn => 1.0 - (t * PI / 2.0).cos(),
+            Easing::SineOut => (t * PI / 2.0).sin(),
+            Easing::SineInOut => -((PI * t).cos() - 1.0) / 2.0,
+            Easing::ExpoIn => expo_in(t),
+            Easing::ExpoOut => 1.0 - expo_in(1.0 - t),
+            Easing::ExpoInOut => in_out(t, expo_in),
+            Easing::BackIn => back_in(t),
+            Easing::BackOut => 1.0 - back_in(1.0 - t),
+            Easing::BackInOut => in_out(t, back_in),
+            Easing::ElasticOut => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else {
+                    2f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0
+                }
+            }
+            Easing::BounceOut => bounce_out(t),
+            Easing::Custom(easing) => easing(t),
+        }
+    }
+}
+
+// Builds an in-out easing from an in easing: the first half eases in, the second half is
+// its mirror image.
+fn in_out(t: f32, ease_in: impl Fn(f32) -> f32) -> f32 {
+    if t < 0.5 {
+        ease_in(2.0 * t) / 2.0
+    } else {
+        1.0 - ease_in(2.0 - 2.0 * t) / 2.0
+    }
+}
+
+fn expo_in(t: f32) -> f32 {
+    if t == 0.0 {
+        0.0
+    } else {
+        2f32.powf(10.0 * t - 10.0)
+    }
+}
+
+fn back_in(t: f32) -> f32 {
+    (BACK_OVERSHOOT + 1.0) * t * t * t - BACK_OVERSHOOT * t * t
+}
+
+fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Easing; 18] = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::SineIn,
+        Easing::SineOut,
+        Easing::SineInOut,
+        Easing::ExpoIn,
+        Easing::ExpoOut,
+        Easing::ExpoInOut,
+        Easing::BackIn,
+        Easing::BackOut,
+        Easing::BackInOut,
+        Easing::ElasticOut,
+        Easing::BounceOut,
+    ];
+
+    #[test]
+    fn easings_start_at_zero_and_end_at_one() {
+        for easing in ALL {
+            assert!(easing.apply(0.0).abs() < 1e-3, "{easing:?} at 0");
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-3, "{easing:?} at 1");
+        }
+    }
+
+    #[test]
+    fn in_and_out_easings_mirror_each_other() {
+        assert_eq!(Easing::QuadIn.apply(0.25), 0.0625);
+        assert_eq!(Easing::QuadOut.apply(0.75), 1.0 - 0.0625);
+        assert_eq!(Easing::QuadInOut.apply(0.5), 0.5);
+        assert!(Easing::BackIn.apply(0.2) < 0.0);
+        assert!(Easing::BackOut.apply(0.8) > 1.0);
+        assert_eq!(Easing::Custom(|t| t * 0.5).apply(2.0), 0.5);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod animation;
+pub mod easing;
+pub mod system;
@@ -0,0 +1,224 @@
+use chaos_communicator::message::ChaosMessageBuilder;
+
+use crate::{
+    ecs::{EntityID, system::ChaosSystem, world::ChaosWorld},
+    tween::animation::{Animation, Animator, TweenAction},
+};
+
+/// Plays the animations of every [`Animator`], following the world's time scale.
+pub struct TweenSystem;
+
+impl TweenSystem {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub(crate) fn advance(world: &mut ChaosWorld, delta_time: f32) -> Result<(), &'static str> {
+        // The animations are taken out of their animators while they run, so they can
+        // change components of the world.
+        let mut playing: Vec<(EntityID, Vec<Animation>)> = Vec::new();
+        for (entity, (animator,)) in world
+            .query::<(&mut Animator,)>()
+            .map_err(|_| "Failed to query animators")?
+        {
+            if animator.is_playing() {
+                playing.push((entity, std::mem::take(&mut animator.animations)));
+            }
+        }
+
+        for (entity, mut animations) in playing {
+            let mut completed = Vec::new();
+            animations.retain_mut(|animation| {
+                if animation.advance(world, entity, delta_time) {
+                    completed.extend(animation.on_complete.take());
+                }
+                !animation.is_finished()
+            });
+
+            // Animations started while these ran are kept after them. If the animator
+            // was removed, the running animations stop.
+            if let Some(animator) = world.get_component_mut::<Animator>(entity) {
+                animations.append(&mut animator.animations);
+                animator.animations = animations;
+            }
+
+            for action in completed {
+                match action {
+                    TweenAction::Signal(key) => {
+                        let message = ChaosMessageBuilder::new()
+                            .with_param("entity_id", entity)
+                            .build_for_event(key);
+                        if let Err(error) = world.try_send_message(message) {
+                            log::debug!("Tween signal was not delivered: {}", error);
+                        }
+                    }
+                    TweenAction::Callback(mut callback) => callback(world, entity),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for TweenSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChaosSystem for TweenSystem {
+    fn initialize(&mut self, _world: &mut ChaosWorld) -> Result<(), &'static str> {
+        Ok(())
+    }
+
+    fn update(&mut self, world: &mut ChaosWorld) -> Result<(), &'static str> {
+        let delta_time = world.get_time().delta_time();
+        Self::advance(world, delta_time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        math::Vec2,
+        tween::{
+            animation::{Parallel, Sequence, Tween, TweenRepeat},
+            easing::Easing,
+        },
+    };
+
+    struct Position(Vec2);
+    struct Opacity(f32);
+
+    fn slide(to: f32) -> Tween<Position, Vec2> {
+        Tween::new(
+            Vec2::zero(),
+            Vec2::new(to, 0.0),
+            1.0,
+            |position: &mut Position, value| position.0 = value,
+        )
+    }
+
+    fn fade(from: f32, to: f32) -> Tween<Opacity, f32> {
+        Tween::new(from, to, 1.0, |opacity: &mut Opacity, value| {
+            opacity.0 = value
+        })
+    }
+
+    fn x(world: &ChaosWorld, entity: EntityID) -> f32 {
+        world.get_component::<Position>(entity).unwrap().0.x
+    }
+
+    #[test]
+    fn tween_eases_a_component_field() {
+        let mut world = ChaosWorld::new();
+        let entity = world
+            .spawn()
+            .with(Position(Vec2::zero()))
+            .with(Animator::new().with(Animation::new(slide(10.0).with_easing(Easing::QuadIn))))
+            .build();
+
+        TweenSystem::advance(&mut world, 0.5).unwrap();
+        assert_eq!(x(&world, entity), 2.5);
+
+        TweenSystem::advance(&mut world, 0.75).unwrap();
+        assert_eq!(x(&world, entity), 10.0);
+        assert!(
+            !world
+                .get_component::<Animator>(entity)
+                .unwrap()
+                .is_playing()
+        );
+    }
+
+    #[test]
+    fn loop_and_ping_pong_repeat_passes() {
+        let mut world = ChaosWorld::new();
+        let looping = world
+            .spawn()
+            .with(Position(Vec2::zero()))
+            .with(Animator::new().with(Animation::new(slide(10.0)).with_repeat(TweenRepeat::Loop)))
+            .build();
+        let ping_pong = world
+            .spawn()
+            .with(Position(Vec2::zero()))
+            .with(
+                Animator::new().with(
+                    Animation::new(slide(10.0))
+                        .with_repeat(TweenRepeat::PingPong)
+                        .times(2),
+                ),
+            )
+            .build();
+
+        TweenSystem::advance(&mut world, 1.25).unwrap();
+        assert_eq!(x(&world, looping), 2.5);
+        assert_eq!(x(&world, ping_pong), 7.5);
+
+        TweenSystem::advance(&mut world, 1.0).unwrap();
+        assert_eq!(x(&world, looping), 2.5);
+        assert_eq!(x(&world, ping_pong), 0.0);
+        assert!(
+            world
+                .get_component::<Animator>(looping)
+                .unwrap()
+                .is_playing()
+        );
+        assert!(
+            !world
+                .get_component::<Animator>(ping_pong)
+                .unwrap()
+                .is_playing()
+        );
+    }
+
+    #[test]
+    fn sequences_and_parallel_tracks_combine_tweens() {
+        let mut world = ChaosWorld::new();
+        let entity = world
+            .spawn()
+            .with(Position(Vec2::zero()))
+            .with(Opacity(0.0))
+            .with(
+                Animator::new().with(Animation::new(
+                    Sequence::new()
+                        .then(Parallel::new().with(slide(10.0)).with(fade(0.0, 1.0)))
+                        .then_wait(1.0)
+                        .then(fade(1.0, 0.0)),
+                )),
+            )
+            .build();
+        let opacity = |world: &ChaosWorld| world.get_component::<Opacity>(entity).unwrap().0;
+
+        TweenSystem::advance(&mut world, 0.5).unwrap();
+        assert_eq!((x(&world, entity), opacity(&world)), (5.0, 0.5));
+
+        // Skipping over the end of the first step still leaves its final state.
+        TweenSystem::advance(&mut world, 1.0).unwrap();
+        assert_eq!((x(&world, entity), opacity(&world)), (10.0, 1.0));
+
+        TweenSystem::advance(&mut world, 1.25).unwrap();
+        assert_eq!(opacity(&world), 0.25);
+    }
+
+    #[test]
+    fn finished_animation_signals_completion() {
+        let mut world = ChaosWorld::new();
+        let mut receiver = world.register_for_trigger("faded");
+        let entity = world
+            .spawn()
+            .with(Opacity(1.0))
+            .with(Animator::new().with(Animation::new(fade(1.0, 0.0)).with_signal(&"faded")))
+            .build();
+
+        TweenSystem::advance(&mut world, 0.5).unwrap();
+        assert!(receiver.receive().is_none());
+
+        TweenSystem::advance(&mut world, 0.5).unwrap();
+        let message = receiver
+            .receive()
+            .expect("animation should signal completion");
+        assert_eq!(message.get::<EntityID>("entity_id"), Some(entity));
+    }
+}